@@ -0,0 +1,32 @@
+//! Amount arithmetic and fee formulas shared by the node library (`ain-core`) and the wasm
+//! modules, so both compute the same values from the same code.
+
+mod coin;
+
+pub use self::coin::Amount;
+
+/// Coefficient of the dex stabilization fee used until governance sets another one (`1.8`)
+pub const DEFAULT_DEX_FEE_COEFFICIENT: Amount = Amount(180000000);
+
+const RATIO_HALF: Amount = Amount(Amount::COIN.0 / 2);
+
+/// **DFIP-2206-D**
+///
+/// Calculates the dynamic dex stabilization fee for DUSD on DUSD-DFI pair. The size of the fee
+/// is determined by the ratio of algorithmic DUSD to the total amount of outstanding DUSD.
+/// Returns `None` when `dusd_supply` is zero.
+pub fn dex_stabilization_fee(
+    algo_dusd: Amount,
+    dusd_supply: Amount,
+    coefficient: Amount,
+) -> Option<Amount> {
+    let ratio = Amount::COIN - algo_dusd.checked_price_divide(dusd_supply)?;
+
+    if ratio > RATIO_HALF {
+        Some(Amount::from_raw(
+            coefficient.as_raw().powf((ratio - RATIO_HALF).as_raw()) - 1.0,
+        ))
+    } else {
+        Some(Amount(0))
+    }
+}
@@ -1,12 +1,10 @@
-mod coin;
-
-use self::coin::Amount;
+use ain_coin::{dex_stabilization_fee, Amount, DEFAULT_DEX_FEE_COEFFICIENT};
 
 use std::error::Error;
 use std::sync::RwLock;
 
 lazy_static::lazy_static! {
-    static ref COEFF_DEX_FEE: RwLock<Amount> = RwLock::new(Amount(180000000));
+    static ref COEFF_DEX_FEE: RwLock<Amount> = RwLock::new(DEFAULT_DEX_FEE_COEFFICIENT);
     static ref COEFF_DISCOUNT: RwLock<Amount> = RwLock::new(Amount(50000000000));
     static ref COEFF_PREMIUM: RwLock<Amount> = RwLock::new(Amount(340000000));
 }
@@ -14,7 +12,6 @@ lazy_static::lazy_static! {
 const DUSD_PRICE_FLOOR: Amount = Amount(99000000);
 const DUSD_PRICE_CEIL: Amount = Amount(105000000);
 const DUSD_PRICE_IDEAL: Amount = Amount(101000000);
-const RATIO_HALF: Amount = Amount(Amount::COIN.0 / 2);
 
 #[cxx::bridge]
 mod calc {
@@ -54,17 +51,10 @@ fn set_interest_rate_coefficients(discount: i64, premium: i64) {
 /// Calculates the dynamic dex stabilization fee for DUSD on DUSD-DFI pair. The size of the fee
 /// is determined by the ratio of algorithmic DUSD to the total amount of outstanding DUSD.
 fn calc_dex_fee(algo_dusd: i64, dusd_supply: i64) -> Result<i64, Box<dyn Error>> {
-    let ratio = Amount::COIN
-        - Amount(algo_dusd)
-            .checked_price_divide(Amount(dusd_supply))
-            .ok_or("Cannot divide given DUSD supply")?;
-
-    let coeff = COEFF_DEX_FEE.read().unwrap().as_raw();
-    if ratio > RATIO_HALF {
-        Ok(Amount::from_raw(coeff.powf((ratio - RATIO_HALF).as_raw()) - 1.0).0)
-    } else {
-        Ok(0)
-    }
+    let coeff = *COEFF_DEX_FEE.read().unwrap();
+    let fee = dex_stabilization_fee(Amount(algo_dusd), Amount(dusd_supply), coeff)
+        .ok_or("Cannot divide given DUSD supply")?;
+    Ok(fee.0)
 }
 
 /// **DFIP-2206-E**
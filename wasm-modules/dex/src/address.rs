@@ -0,0 +1,139 @@
+use sha2::{Digest, Sha256};
+
+/// Base58 version bytes of pay-to-pubkey-hash and pay-to-script-hash addresses on mainnet,
+/// testnet and regtest
+const BASE58_VERSIONS: [u8; 6] = [18, 90, 15, 128, 111, 196];
+
+/// Human readable parts of segwit addresses on mainnet, testnet and regtest
+const BECH32_HRPS: [&str; 3] = ["df", "tf", "bcrt"];
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Whether `address` is a well formed DeFiChain address: a base58check P2PKH or P2SH address,
+/// or a segwit address. Only the encoding and checksum are checked, not whether it is spendable.
+pub fn is_valid(address: &str) -> bool {
+    is_valid_base58(address) || is_valid_segwit(address)
+}
+
+fn is_valid_base58(address: &str) -> bool {
+    // 25 bytes never take more than 35 characters
+    if address.len() > 35 {
+        return false;
+    }
+    let decoded = match base58_decode(address) {
+        Some(decoded) if decoded.len() == 25 => decoded,
+        _ => return false,
+    };
+    let (payload, checksum) = decoded.split_at(21);
+    Sha256::digest(Sha256::digest(payload))[..4] == *checksum
+        && BASE58_VERSIONS.contains(&payload[0])
+}
+
+fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    // Big endian base 256 digits, one leading zero byte per leading '1'
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in s.bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    Some([vec![0; zeros], bytes].concat())
+}
+
+fn is_valid_segwit(address: &str) -> bool {
+    if address.len() > 90
+        || (address.bytes().any(|c| c.is_ascii_lowercase())
+            && address.bytes().any(|c| c.is_ascii_uppercase()))
+    {
+        return false;
+    }
+    let address = address.to_ascii_lowercase();
+    let (hrp, data) = match address.rsplit_once('1') {
+        Some(split) => split,
+        None => return false,
+    };
+    if !BECH32_HRPS.contains(&hrp) || data.len() < 7 {
+        return false;
+    }
+    let values = match data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|&a| a == c).map(|v| v as u8))
+        .collect::<Option<Vec<_>>>()
+    {
+        Some(values) => values,
+        None => return false,
+    };
+
+    // The witness version picks the checksum: bech32 for version 0, bech32m for later ones
+    let version = values[0];
+    let checksum = match version {
+        0 => BECH32_CONST,
+        1..=16 => BECH32M_CONST,
+        _ => return false,
+    };
+    if bech32_polymod(hrp, &values) != checksum {
+        return false;
+    }
+    match convert_bits(&values[1..values.len() - 6]) {
+        Some(program) if version == 0 => program.len() == 20 || program.len() == 32,
+        Some(program) => (2..=40).contains(&program.len()),
+        None => false,
+    }
+}
+
+fn bech32_polymod(hrp: &str, values: &[u8]) -> u32 {
+    const GENERATORS: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+    let expanded = hrp
+        .bytes()
+        .map(|c| c >> 5)
+        .chain([0])
+        .chain(hrp.bytes().map(|c| c & 31))
+        .chain(values.iter().copied());
+    expanded.fold(1, |chk, value| {
+        let top = chk >> 25;
+        let chk = ((chk & 0x1ff_ffff) << 5) ^ u32::from(value);
+        GENERATORS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(chk, |chk, (_, generator)| chk ^ generator)
+    })
+}
+
+/// Regroups 5 bit values into bytes, rejecting leftover padding that is not all zero or that
+/// is a whole group long
+fn convert_bits(values: &[u8]) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let mut out = Vec::with_capacity(values.len() * 5 / 8);
+    for &value in values {
+        acc = (acc << 5) | u32::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    if bits >= 5 || (acc << (8 - bits)) & 0xff != 0 {
+        return None;
+    }
+    Some(out)
+}
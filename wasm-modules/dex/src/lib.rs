@@ -1,38 +1,32 @@
+mod address;
+mod registry;
+
+use ain_coin::{dex_stabilization_fee, Amount};
 use primitive_types::U256;
 use std::ops::{Add, Sub};
 
-use crate::dex::{Error, PoolPair, PoolPrice, SwapResult, TokenAmount};
+use crate::dex::{
+    BestPathResult, ClaimResult, CompositeSwapResult, CumulativePrice, DctId, DexFee, Error,
+    InputTokenMismatch, LiquidityResult, OutputLimit, PoolConfigError, PoolEntry, PoolIndex,
+    PoolPair, PoolPairConfig, PoolPrice, PoolType, PriceLimit, Reserves, RewardPerShare,
+    RewardPool, SwapBatchResult, SwapEvent, SwapEventKind, SwapOperation, SwapOutput, SwapResult,
+    SwapRoute, SwapStep, SwapTraceResult, TokenAmount, TwapResult,
+};
+use crate::registry::PoolRegistry;
 
 const COIN: i64 = 100_000_000;
-// const MINIMUM_LIQUIDITY: i64 = 1000;
+const MINIMUM_LIQUIDITY: i64 = 1000;
 const SLOPE_SWAP_RATE: i64 = 1000;
 const PRECISION: u32 = COIN as u32;
+const MAX_AMPLIFICATION: u32 = 1_000_000;
+const STABLE_SWAP_MAX_ITERATIONS: usize = 255;
+const WEIGHT_TOTAL: u32 = 100;
+const FIXED_ONE: i128 = 1_000_000_000_000_000_000;
+const FIXED_LN_2: i128 = 693_147_180_559_945_309;
+const WEIGHTED_POW_ROUNDING: i128 = 100_000_000_000_000;
+const REWARD_PRECISION: u64 = 1_000_000_000_000_000_000;
 
 mod dex {
-    #[repr(u8)]
-    #[derive(Clone, Copy, PartialEq, Eq)]
-    pub enum Error {
-        // Runtime,
-        // NotFound,
-        InvalidInput,
-        LackOfLiquidity,
-        PriceHigherThanIndex,
-        PoolReserveOverflow,
-    }
-    impl std::fmt::Debug for Error {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            match self {
-                // Error::Runtime => f.debug_tuple("Error::Runtime").finish(),
-                // Error::NotFound => f.debug_tuple("Error::NotFound").finish(),
-                Error::InvalidInput => f.debug_tuple("Error::InvalidInput").finish(),
-                Error::LackOfLiquidity => f.debug_tuple("Error::LackOfLiquidity").finish(),
-                Error::PriceHigherThanIndex => {
-                    f.debug_tuple("Error::PriceHigherThanIndex").finish()
-                }
-                Error::PoolReserveOverflow => f.debug_tuple("Error::PoolReserveOverflow").finish(),
-            }
-        }
-    }
     pub type DctId = u32;
     #[repr(C)]
     #[derive(Copy, Clone)]
@@ -64,6 +58,179 @@ mod dex {
     }
     #[repr(C)]
     #[derive(Copy, Clone)]
+    pub struct Reserves {
+        pub reserve_a: i64,
+        pub reserve_b: i64,
+    }
+    impl std::fmt::Debug for Reserves {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Reserves")
+                .field("reserve-a", &self.reserve_a)
+                .field("reserve-b", &self.reserve_b)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct PriceLimit {
+        pub price: PoolPrice,
+        pub max_price: PoolPrice,
+    }
+    impl std::fmt::Debug for PriceLimit {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("PriceLimit")
+                .field("price", &self.price)
+                .field("max-price", &self.max_price)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct OutputLimit {
+        pub amount: i64,
+        pub min_output: i64,
+    }
+    impl std::fmt::Debug for OutputLimit {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("OutputLimit")
+                .field("amount", &self.amount)
+                .field("min-output", &self.min_output)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct PoolIndex {
+        pub index: u32,
+    }
+    impl std::fmt::Debug for PoolIndex {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("PoolIndex")
+                .field("index", &self.index)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct InputTokenMismatch {
+        pub token_id: DctId,
+        pub token_a: DctId,
+        pub token_b: DctId,
+    }
+    impl std::fmt::Debug for InputTokenMismatch {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("InputTokenMismatch")
+                .field("token-id", &self.token_id)
+                .field("token-a", &self.token_a)
+                .field("token-b", &self.token_b)
+                .finish()
+        }
+    }
+    #[repr(u8)]
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum PoolConfigError {
+        SameToken,
+        CommissionOutOfRange,
+        InvalidOwnerAddress,
+        InvalidPoolType,
+    }
+    impl std::fmt::Debug for PoolConfigError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                PoolConfigError::SameToken => f.debug_tuple("PoolConfigError::SameToken").finish(),
+                PoolConfigError::CommissionOutOfRange => f
+                    .debug_tuple("PoolConfigError::CommissionOutOfRange")
+                    .finish(),
+                PoolConfigError::InvalidOwnerAddress => f
+                    .debug_tuple("PoolConfigError::InvalidOwnerAddress")
+                    .finish(),
+                PoolConfigError::InvalidPoolType => {
+                    f.debug_tuple("PoolConfigError::InvalidPoolType").finish()
+                }
+            }
+        }
+    }
+    #[derive(Clone, Copy)]
+    pub enum Error {
+        Runtime,
+        NotFound,
+        InvalidInput,
+        LackOfLiquidity(Reserves),
+        PriceHigherThanIndex(PriceLimit),
+        PoolReserveOverflow(Reserves),
+        OutputBelowMinimum(OutputLimit),
+        TokenMismatch(InputTokenMismatch),
+        ArithmeticOverflow,
+        InvariantViolation(Reserves),
+        InvalidPoolConfig(PoolConfigError),
+        PoolNotFound(PoolIndex),
+        PoolDisabled,
+    }
+    impl std::fmt::Debug for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Error::Runtime => f.debug_tuple("Error::Runtime").finish(),
+                Error::NotFound => f.debug_tuple("Error::NotFound").finish(),
+                Error::InvalidInput => f.debug_tuple("Error::InvalidInput").finish(),
+                Error::LackOfLiquidity(e) => {
+                    f.debug_tuple("Error::LackOfLiquidity").field(e).finish()
+                }
+                Error::PriceHigherThanIndex(e) => f
+                    .debug_tuple("Error::PriceHigherThanIndex")
+                    .field(e)
+                    .finish(),
+                Error::PoolReserveOverflow(e) => f
+                    .debug_tuple("Error::PoolReserveOverflow")
+                    .field(e)
+                    .finish(),
+                Error::OutputBelowMinimum(e) => {
+                    f.debug_tuple("Error::OutputBelowMinimum").field(e).finish()
+                }
+                Error::TokenMismatch(e) => f.debug_tuple("Error::TokenMismatch").field(e).finish(),
+                Error::ArithmeticOverflow => f.debug_tuple("Error::ArithmeticOverflow").finish(),
+                Error::InvariantViolation(e) => {
+                    f.debug_tuple("Error::InvariantViolation").field(e).finish()
+                }
+                Error::InvalidPoolConfig(e) => {
+                    f.debug_tuple("Error::InvalidPoolConfig").field(e).finish()
+                }
+                Error::PoolNotFound(e) => f.debug_tuple("Error::PoolNotFound").field(e).finish(),
+                Error::PoolDisabled => f.debug_tuple("Error::PoolDisabled").finish(),
+            }
+        }
+    }
+    #[repr(u8)]
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum PoolType {
+        ConstantProduct,
+        StableSwap,
+        Weighted,
+    }
+    impl std::fmt::Debug for PoolType {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                PoolType::ConstantProduct => f.debug_tuple("PoolType::ConstantProduct").finish(),
+                PoolType::StableSwap => f.debug_tuple("PoolType::StableSwap").finish(),
+                PoolType::Weighted => f.debug_tuple("PoolType::Weighted").finish(),
+            }
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct CumulativePrice {
+        pub high: u64,
+        pub low: u64,
+    }
+    impl std::fmt::Debug for CumulativePrice {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("CumulativePrice")
+                .field("high", &self.high)
+                .field("low", &self.low)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
     pub struct PoolPair {
         pub token_a: DctId,
         pub token_b: DctId,
@@ -73,6 +240,14 @@ mod dex {
         pub total_liquidity: i64,
         pub block_commission_a: i64,
         pub block_commission_b: i64,
+        pub pool_type: PoolType,
+        pub amplification: u32,
+        pub weight_a: u32,
+        pub weight_b: u32,
+        pub price_ab_cumulative: CumulativePrice,
+        pub price_ba_cumulative: CumulativePrice,
+        pub price_height: u32,
+        pub status: bool,
     }
     impl std::fmt::Debug for PoolPair {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -85,6 +260,72 @@ mod dex {
                 .field("total-liquidity", &self.total_liquidity)
                 .field("block-commission-a", &self.block_commission_a)
                 .field("block-commission-b", &self.block_commission_b)
+                .field("pool-type", &self.pool_type)
+                .field("amplification", &self.amplification)
+                .field("weight-a", &self.weight_a)
+                .field("weight-b", &self.weight_b)
+                .field("price-ab-cumulative", &self.price_ab_cumulative)
+                .field("price-ba-cumulative", &self.price_ba_cumulative)
+                .field("price-height", &self.price_height)
+                .field("status", &self.status)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct PoolPairConfig {
+        pub token_a: DctId,
+        pub token_b: DctId,
+        pub commission: DctId,
+        pub status: bool,
+        pub pool_type: PoolType,
+        pub amplification: u32,
+        pub weight_a: u32,
+        pub weight_b: u32,
+    }
+    impl std::fmt::Debug for PoolPairConfig {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("PoolPairConfig")
+                .field("token-a", &self.token_a)
+                .field("token-b", &self.token_b)
+                .field("commission", &self.commission)
+                .field("status", &self.status)
+                .field("pool-type", &self.pool_type)
+                .field("amplification", &self.amplification)
+                .field("weight-a", &self.weight_a)
+                .field("weight-b", &self.weight_b)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct PoolEntry {
+        pub pool_id: DctId,
+        pub pool_pair: PoolPair,
+    }
+    impl std::fmt::Debug for PoolEntry {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("PoolEntry")
+                .field("pool-id", &self.pool_id)
+                .field("pool-pair", &self.pool_pair)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct DexFee {
+        pub in_pct_ab: i64,
+        pub out_pct_ab: i64,
+        pub in_pct_ba: i64,
+        pub out_pct_ba: i64,
+    }
+    impl std::fmt::Debug for DexFee {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("DexFee")
+                .field("in-pct-ab", &self.in_pct_ab)
+                .field("out-pct-ab", &self.out_pct_ab)
+                .field("in-pct-ba", &self.in_pct_ba)
+                .field("out-pct-ba", &self.out_pct_ba)
                 .finish()
         }
     }
@@ -93,265 +334,7500 @@ mod dex {
     pub struct SwapResult {
         pub pool_pair: PoolPair,
         pub slop_swap_result: i64,
+        pub dex_fee_in: i64,
+        pub dex_fee_out: i64,
     }
     impl std::fmt::Debug for SwapResult {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             f.debug_struct("SwapResult")
                 .field("pool-pair", &self.pool_pair)
                 .field("slop-swap-result", &self.slop_swap_result)
+                .field("dex-fee-in", &self.dex_fee_in)
+                .field("dex-fee-out", &self.dex_fee_out)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct SwapStep {
+        pub step_from: i64,
+        pub step_to: i64,
+        pub reserve_from: i64,
+        pub reserve_to: i64,
+    }
+    impl std::fmt::Debug for SwapStep {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SwapStep")
+                .field("step-from", &self.step_from)
+                .field("step-to", &self.step_to)
+                .field("reserve-from", &self.reserve_from)
+                .field("reserve-to", &self.reserve_to)
+                .finish()
+        }
+    }
+    #[repr(u8)]
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum SwapEventKind {
+        DexFeeIn,
+        Commission,
+        DexFeeOut,
+    }
+    impl std::fmt::Debug for SwapEventKind {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                SwapEventKind::DexFeeIn => f.debug_tuple("SwapEventKind::DexFeeIn").finish(),
+                SwapEventKind::Commission => f.debug_tuple("SwapEventKind::Commission").finish(),
+                SwapEventKind::DexFeeOut => f.debug_tuple("SwapEventKind::DexFeeOut").finish(),
+            }
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct SwapEvent {
+        pub kind: SwapEventKind,
+        pub token_id: DctId,
+        pub amount: i64,
+    }
+    impl std::fmt::Debug for SwapEvent {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SwapEvent")
+                .field("kind", &self.kind)
+                .field("token-id", &self.token_id)
+                .field("amount", &self.amount)
+                .finish()
+        }
+    }
+    #[derive(Clone)]
+    pub struct SwapTraceResult {
+        pub swap_result: SwapResult,
+        pub steps: Vec<SwapStep>,
+        pub events: Vec<SwapEvent>,
+    }
+    impl std::fmt::Debug for SwapTraceResult {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SwapTraceResult")
+                .field("swap-result", &self.swap_result)
+                .field("steps", &self.steps)
+                .field("events", &self.events)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct SwapOperation {
+        pub pool_index: u32,
+        pub token_in: TokenAmount,
+        pub max_price: PoolPrice,
+        pub dex_fee: DexFee,
+    }
+    impl std::fmt::Debug for SwapOperation {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SwapOperation")
+                .field("pool-index", &self.pool_index)
+                .field("token-in", &self.token_in)
+                .field("max-price", &self.max_price)
+                .field("dex-fee", &self.dex_fee)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct SwapOutput {
+        pub amount: i64,
+        pub dex_fee_in: i64,
+        pub dex_fee_out: i64,
+    }
+    impl std::fmt::Debug for SwapOutput {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SwapOutput")
+                .field("amount", &self.amount)
+                .field("dex-fee-in", &self.dex_fee_in)
+                .field("dex-fee-out", &self.dex_fee_out)
+                .finish()
+        }
+    }
+    #[derive(Clone)]
+    pub struct SwapBatchResult {
+        pub pool_pairs: Vec<PoolPair>,
+        pub results: Vec<Result<SwapOutput, Error>>,
+    }
+    impl std::fmt::Debug for SwapBatchResult {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SwapBatchResult")
+                .field("pool-pairs", &self.pool_pairs)
+                .field("results", &self.results)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct LiquidityResult {
+        pub pool_pair: PoolPair,
+        pub amount_a: i64,
+        pub amount_b: i64,
+        pub liquidity: i64,
+    }
+    impl std::fmt::Debug for LiquidityResult {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("LiquidityResult")
+                .field("pool-pair", &self.pool_pair)
+                .field("amount-a", &self.amount_a)
+                .field("amount-b", &self.amount_b)
+                .field("liquidity", &self.liquidity)
+                .finish()
+        }
+    }
+    pub type RewardPerShare = CumulativePrice;
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct RewardPool {
+        pub token_id: DctId,
+        pub reward_per_block: i64,
+        pub reward_per_share: RewardPerShare,
+        pub last_height: u32,
+    }
+    impl std::fmt::Debug for RewardPool {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RewardPool")
+                .field("token-id", &self.token_id)
+                .field("reward-per-block", &self.reward_per_block)
+                .field("reward-per-share", &self.reward_per_share)
+                .field("last-height", &self.last_height)
+                .finish()
+        }
+    }
+    #[derive(Clone)]
+    pub struct ClaimResult {
+        pub amounts: Vec<TokenAmount>,
+        pub paid: Vec<RewardPerShare>,
+    }
+    impl std::fmt::Debug for ClaimResult {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ClaimResult")
+                .field("amounts", &self.amounts)
+                .field("paid", &self.paid)
+                .finish()
+        }
+    }
+    #[repr(C)]
+    #[derive(Copy, Clone)]
+    pub struct TwapResult {
+        pub price_ab: PoolPrice,
+        pub price_ba: PoolPrice,
+    }
+    impl std::fmt::Debug for TwapResult {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("TwapResult")
+                .field("price-ab", &self.price_ab)
+                .field("price-ba", &self.price_ba)
+                .finish()
+        }
+    }
+    #[derive(Clone)]
+    pub struct CompositeSwapResult {
+        pub pool_pairs: Vec<PoolPair>,
+        pub slop_swap_result: i64,
+    }
+    impl std::fmt::Debug for CompositeSwapResult {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("CompositeSwapResult")
+                .field("pool-pairs", &self.pool_pairs)
+                .field("slop-swap-result", &self.slop_swap_result)
+                .finish()
+        }
+    }
+    #[derive(Clone)]
+    pub struct SwapRoute {
+        pub path: Vec<u32>,
+        pub slop_swap_result: i64,
+    }
+    impl std::fmt::Debug for SwapRoute {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SwapRoute")
+                .field("path", &self.path)
+                .field("slop-swap-result", &self.slop_swap_result)
+                .finish()
+        }
+    }
+    #[derive(Clone)]
+    pub struct BestPathResult {
+        pub best: SwapRoute,
+        pub alternatives: Vec<SwapRoute>,
+    }
+    impl std::fmt::Debug for BestPathResult {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("BestPathResult")
+                .field("best", &self.best)
+                .field("alternatives", &self.alternatives)
                 .finish()
         }
     }
     #[export_name = "swap"]
-    unsafe extern "C" fn __wit_bindgen_swap(
-        arg0: i32,
-        arg1: i32,
-        arg2: i32,
-        arg3: i64,
-        arg4: i64,
-        arg5: i64,
-        arg6: i64,
-        arg7: i64,
-        arg8: i32,
-        arg9: i64,
-        arg10: i64,
-        arg11: i64,
-        arg12: i32,
-    ) -> i32 {
-        let result0 = <super::Dex as Dex>::swap(
+    unsafe extern "C" fn __wit_bindgen_swap(arg0: i32) -> i32 {
+        let result1 = <super::Dex as Dex>::swap(
             PoolPair {
-                token_a: arg0 as u32,
-                token_b: arg1 as u32,
-                commission: arg2 as u32,
-                reserve_a: arg3,
-                reserve_b: arg4,
-                total_liquidity: arg5,
-                block_commission_a: arg6,
-                block_commission_b: arg7,
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
             },
             TokenAmount {
-                token_id: arg8 as u32,
-                amount: arg9,
+                token_id: *((arg0 + 112) as *const i32) as u32,
+                amount: *((arg0 + 120) as *const i64),
             },
             PoolPrice {
-                integer: arg10,
-                fraction: arg11,
+                integer: *((arg0 + 128) as *const i64),
+                fraction: *((arg0 + 136) as *const i64),
+            },
+            match i32::from(*((arg0 + 144) as *const u8)) {
+                0 => None,
+                1 => Some(*((arg0 + 152) as *const i64)),
+                _ => panic!("invalid enum discriminant"),
             },
-            match arg12 {
+            DexFee {
+                in_pct_ab: *((arg0 + 160) as *const i64),
+                out_pct_ab: *((arg0 + 168) as *const i64),
+                in_pct_ba: *((arg0 + 176) as *const i64),
+                out_pct_ba: *((arg0 + 184) as *const i64),
+            },
+            match i32::from(*((arg0 + 192) as *const u8)) {
                 0 => false,
                 1 => true,
                 _ => panic!("invalid enum discriminant"),
             },
         );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(200, 8),
+        );
         let (
-            result3_0,
-            result3_1,
-            result3_2,
-            result3_3,
-            result3_4,
-            result3_5,
-            result3_6,
-            result3_7,
-            result3_8,
-            result3_9,
-        ) = match result0 {
+            result2_0,
+            result2_1,
+            result2_2,
+            result2_3,
+            result2_4,
+            result2_5,
+            result2_6,
+            result2_7,
+            result2_8,
+            result2_9,
+            result2_10,
+            result2_11,
+            result2_12,
+            result2_13,
+            result2_14,
+            result2_15,
+            result2_16,
+            result2_17,
+            result2_18,
+            result2_19,
+            result2_20,
+            result2_21,
+        ) = match result1 {
             Ok(e) => {
                 let SwapResult {
-                    pool_pair: pool_pair1,
-                    slop_swap_result: slop_swap_result1,
+                    pool_pair: pool_pair3,
+                    slop_swap_result: slop_swap_result3,
+                    dex_fee_in: dex_fee_in3,
+                    dex_fee_out: dex_fee_out3,
                 } = e;
                 let PoolPair {
-                    token_a: token_a2,
-                    token_b: token_b2,
-                    commission: commission2,
-                    reserve_a: reserve_a2,
-                    reserve_b: reserve_b2,
-                    total_liquidity: total_liquidity2,
-                    block_commission_a: block_commission_a2,
-                    block_commission_b: block_commission_b2,
-                } = pool_pair1;
+                    token_a: token_a4,
+                    token_b: token_b4,
+                    commission: commission4,
+                    reserve_a: reserve_a4,
+                    reserve_b: reserve_b4,
+                    total_liquidity: total_liquidity4,
+                    block_commission_a: block_commission_a4,
+                    block_commission_b: block_commission_b4,
+                    pool_type: pool_type4,
+                    amplification: amplification4,
+                    weight_a: weight_a4,
+                    weight_b: weight_b4,
+                    price_ab_cumulative: price_ab_cumulative4,
+                    price_ba_cumulative: price_ba_cumulative4,
+                    price_height: price_height4,
+                    status: status4,
+                } = pool_pair3;
+                let CumulativePrice {
+                    high: high5,
+                    low: low5,
+                } = price_ab_cumulative4;
+                let CumulativePrice {
+                    high: high6,
+                    low: low6,
+                } = price_ba_cumulative4;
 
                 (
                     0i32,
-                    wit_bindgen_rust::rt::as_i32(token_a2),
-                    wit_bindgen_rust::rt::as_i32(token_b2),
-                    wit_bindgen_rust::rt::as_i32(commission2),
-                    wit_bindgen_rust::rt::as_i64(reserve_a2),
-                    wit_bindgen_rust::rt::as_i64(reserve_b2),
-                    wit_bindgen_rust::rt::as_i64(total_liquidity2),
-                    wit_bindgen_rust::rt::as_i64(block_commission_a2),
-                    wit_bindgen_rust::rt::as_i64(block_commission_b2),
-                    wit_bindgen_rust::rt::as_i64(slop_swap_result1),
+                    wit_bindgen_rust::rt::as_i32(token_a4),
+                    i64::from(wit_bindgen_rust::rt::as_i32(token_b4)),
+                    i64::from(wit_bindgen_rust::rt::as_i32(commission4)),
+                    wit_bindgen_rust::rt::as_i64(reserve_a4),
+                    wit_bindgen_rust::rt::as_i64(reserve_b4),
+                    wit_bindgen_rust::rt::as_i64(total_liquidity4),
+                    wit_bindgen_rust::rt::as_i64(block_commission_a4),
+                    wit_bindgen_rust::rt::as_i64(block_commission_b4),
+                    pool_type4 as i32,
+                    wit_bindgen_rust::rt::as_i32(amplification4),
+                    wit_bindgen_rust::rt::as_i32(weight_a4),
+                    wit_bindgen_rust::rt::as_i32(weight_b4),
+                    wit_bindgen_rust::rt::as_i64(high5),
+                    wit_bindgen_rust::rt::as_i64(low5),
+                    wit_bindgen_rust::rt::as_i64(high6),
+                    wit_bindgen_rust::rt::as_i64(low6),
+                    wit_bindgen_rust::rt::as_i32(price_height4),
+                    match status4 {
+                        false => 0i32,
+                        true => 1i32,
+                    },
+                    wit_bindgen_rust::rt::as_i64(slop_swap_result3),
+                    wit_bindgen_rust::rt::as_i64(dex_fee_in3),
+                    wit_bindgen_rust::rt::as_i64(dex_fee_out3),
                 )
             }
-            Err(e) => (
-                1i32, e as i32, 0i32, 0i32, 0i64, 0i64, 0i64, 0i64, 0i64, 0i64,
+            Err(e) => {
+                let (result7_0, result7_1, result7_2, result7_3, result7_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a8,
+                            reserve_b: reserve_b8,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a8),
+                            wit_bindgen_rust::rt::as_i64(reserve_b8),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price9,
+                            max_price: max_price9,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer10,
+                            fraction: fraction10,
+                        } = price9;
+                        let PoolPrice {
+                            integer: integer11,
+                            fraction: fraction11,
+                        } = max_price9;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer10),
+                            wit_bindgen_rust::rt::as_i64(fraction10),
+                            wit_bindgen_rust::rt::as_i64(integer11),
+                            wit_bindgen_rust::rt::as_i64(fraction11),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a12,
+                            reserve_b: reserve_b12,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a12),
+                            wit_bindgen_rust::rt::as_i64(reserve_b12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount13,
+                            min_output: min_output13,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount13),
+                            wit_bindgen_rust::rt::as_i64(min_output13),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id14,
+                            token_a: token_a14,
+                            token_b: token_b14,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id14)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a14)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b14)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a15,
+                            reserve_b: reserve_b15,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a15),
+                            wit_bindgen_rust::rt::as_i64(reserve_b15),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index16 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index16)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result7_0, result7_1, result7_2, result7_3, result7_4, 0i64, 0i64, 0i64,
+                    0i32, 0i32, 0i32, 0i32, 0i64, 0i64, 0i64, 0i64, 0i32, 0i32, 0i64, 0i64, 0i64,
+                )
+            }
+        };
+        let ptr17 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr17 + 168) as *mut i64) = result2_21;
+        *((ptr17 + 160) as *mut i64) = result2_20;
+        *((ptr17 + 152) as *mut i64) = result2_19;
+        *((ptr17 + 144) as *mut i32) = result2_18;
+        *((ptr17 + 136) as *mut i32) = result2_17;
+        *((ptr17 + 128) as *mut i64) = result2_16;
+        *((ptr17 + 120) as *mut i64) = result2_15;
+        *((ptr17 + 112) as *mut i64) = result2_14;
+        *((ptr17 + 104) as *mut i64) = result2_13;
+        *((ptr17 + 96) as *mut i32) = result2_12;
+        *((ptr17 + 88) as *mut i32) = result2_11;
+        *((ptr17 + 80) as *mut i32) = result2_10;
+        *((ptr17 + 72) as *mut i32) = result2_9;
+        *((ptr17 + 64) as *mut i64) = result2_8;
+        *((ptr17 + 56) as *mut i64) = result2_7;
+        *((ptr17 + 48) as *mut i64) = result2_6;
+        *((ptr17 + 40) as *mut i64) = result2_5;
+        *((ptr17 + 32) as *mut i64) = result2_4;
+        *((ptr17 + 24) as *mut i64) = result2_3;
+        *((ptr17 + 16) as *mut i64) = result2_2;
+        *((ptr17 + 8) as *mut i32) = result2_1;
+        *((ptr17) as *mut i32) = result2_0;
+        ptr17
+    }
+    #[export_name = "swap-trace"]
+    unsafe extern "C" fn __wit_bindgen_swap_trace(arg0: i32) -> i32 {
+        let result1 = <super::Dex as Dex>::swap_trace(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            TokenAmount {
+                token_id: *((arg0 + 112) as *const i32) as u32,
+                amount: *((arg0 + 120) as *const i64),
+            },
+            PoolPrice {
+                integer: *((arg0 + 128) as *const i64),
+                fraction: *((arg0 + 136) as *const i64),
+            },
+            match i32::from(*((arg0 + 144) as *const u8)) {
+                0 => None,
+                1 => Some(*((arg0 + 152) as *const i64)),
+                _ => panic!("invalid enum discriminant"),
+            },
+            DexFee {
+                in_pct_ab: *((arg0 + 160) as *const i64),
+                out_pct_ab: *((arg0 + 168) as *const i64),
+                in_pct_ba: *((arg0 + 176) as *const i64),
+                out_pct_ba: *((arg0 + 184) as *const i64),
+            },
+            match i32::from(*((arg0 + 192) as *const u8)) {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid enum discriminant"),
+            },
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(200, 8),
+        );
+        let (
+            result2_0,
+            result2_1,
+            result2_2,
+            result2_3,
+            result2_4,
+            result2_5,
+            result2_6,
+            result2_7,
+            result2_8,
+            result2_9,
+            result2_10,
+            result2_11,
+            result2_12,
+            result2_13,
+            result2_14,
+            result2_15,
+            result2_16,
+            result2_17,
+            result2_18,
+            result2_19,
+            result2_20,
+            result2_21,
+            result2_22,
+            result2_23,
+            result2_24,
+            result2_25,
+        ) = match result1 {
+            Ok(e) => {
+                let SwapTraceResult {
+                    swap_result: swap_result3,
+                    steps: steps3,
+                    events: events3,
+                } = e;
+                let SwapResult {
+                    pool_pair: pool_pair4,
+                    slop_swap_result: slop_swap_result4,
+                    dex_fee_in: dex_fee_in4,
+                    dex_fee_out: dex_fee_out4,
+                } = swap_result3;
+                let PoolPair {
+                    token_a: token_a5,
+                    token_b: token_b5,
+                    commission: commission5,
+                    reserve_a: reserve_a5,
+                    reserve_b: reserve_b5,
+                    total_liquidity: total_liquidity5,
+                    block_commission_a: block_commission_a5,
+                    block_commission_b: block_commission_b5,
+                    pool_type: pool_type5,
+                    amplification: amplification5,
+                    weight_a: weight_a5,
+                    weight_b: weight_b5,
+                    price_ab_cumulative: price_ab_cumulative5,
+                    price_ba_cumulative: price_ba_cumulative5,
+                    price_height: price_height5,
+                    status: status5,
+                } = pool_pair4;
+                let CumulativePrice {
+                    high: high6,
+                    low: low6,
+                } = price_ab_cumulative5;
+                let CumulativePrice {
+                    high: high7,
+                    low: low7,
+                } = price_ba_cumulative5;
+                let vec8 = (steps3).into_boxed_slice();
+                let ptr8 = vec8.as_ptr() as i32;
+                let len8 = vec8.len() as i32;
+                core::mem::forget(vec8);
+                let vec9 = events3;
+                let len9 = vec9.len() as i32;
+                let layout9 = core::alloc::Layout::from_size_align_unchecked(vec9.len() * 16, 8);
+                let result9 = if layout9.size() != 0 {
+                    let ptr = std::alloc::alloc(layout9);
+                    if ptr.is_null() {
+                        std::alloc::handle_alloc_error(layout9);
+                    }
+                    ptr
+                } else {
+                    std::ptr::null_mut()
+                };
+                for (i, e) in vec9.into_iter().enumerate() {
+                    let base = result9 as i32 + (i as i32) * 16;
+                    {
+                        let SwapEvent {
+                            kind: kind10,
+                            token_id: token_id10,
+                            amount: amount10,
+                        } = e;
+                        *((base) as *mut u8) = (kind10 as i32) as u8;
+                        *((base + 4) as *mut i32) = wit_bindgen_rust::rt::as_i32(token_id10);
+                        *((base + 8) as *mut i64) = wit_bindgen_rust::rt::as_i64(amount10);
+                    }
+                }
+
+                (
+                    0i32,
+                    wit_bindgen_rust::rt::as_i32(token_a5),
+                    i64::from(wit_bindgen_rust::rt::as_i32(token_b5)),
+                    i64::from(wit_bindgen_rust::rt::as_i32(commission5)),
+                    wit_bindgen_rust::rt::as_i64(reserve_a5),
+                    wit_bindgen_rust::rt::as_i64(reserve_b5),
+                    wit_bindgen_rust::rt::as_i64(total_liquidity5),
+                    wit_bindgen_rust::rt::as_i64(block_commission_a5),
+                    wit_bindgen_rust::rt::as_i64(block_commission_b5),
+                    pool_type5 as i32,
+                    wit_bindgen_rust::rt::as_i32(amplification5),
+                    wit_bindgen_rust::rt::as_i32(weight_a5),
+                    wit_bindgen_rust::rt::as_i32(weight_b5),
+                    wit_bindgen_rust::rt::as_i64(high6),
+                    wit_bindgen_rust::rt::as_i64(low6),
+                    wit_bindgen_rust::rt::as_i64(high7),
+                    wit_bindgen_rust::rt::as_i64(low7),
+                    wit_bindgen_rust::rt::as_i32(price_height5),
+                    match status5 {
+                        false => 0i32,
+                        true => 1i32,
+                    },
+                    wit_bindgen_rust::rt::as_i64(slop_swap_result4),
+                    wit_bindgen_rust::rt::as_i64(dex_fee_in4),
+                    wit_bindgen_rust::rt::as_i64(dex_fee_out4),
+                    ptr8,
+                    len8,
+                    result9 as i32,
+                    len9,
+                )
+            }
+            Err(e) => {
+                let (result11_0, result11_1, result11_2, result11_3, result11_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a12,
+                            reserve_b: reserve_b12,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a12),
+                            wit_bindgen_rust::rt::as_i64(reserve_b12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price13,
+                            max_price: max_price13,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer14,
+                            fraction: fraction14,
+                        } = price13;
+                        let PoolPrice {
+                            integer: integer15,
+                            fraction: fraction15,
+                        } = max_price13;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer14),
+                            wit_bindgen_rust::rt::as_i64(fraction14),
+                            wit_bindgen_rust::rt::as_i64(integer15),
+                            wit_bindgen_rust::rt::as_i64(fraction15),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a16,
+                            reserve_b: reserve_b16,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a16),
+                            wit_bindgen_rust::rt::as_i64(reserve_b16),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount17,
+                            min_output: min_output17,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount17),
+                            wit_bindgen_rust::rt::as_i64(min_output17),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id18,
+                            token_a: token_a18,
+                            token_b: token_b18,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id18)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a18)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b18)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a19,
+                            reserve_b: reserve_b19,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a19),
+                            wit_bindgen_rust::rt::as_i64(reserve_b19),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index20 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index20)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result11_0, result11_1, result11_2, result11_3, result11_4, 0i64, 0i64,
+                    0i64, 0i32, 0i32, 0i32, 0i32, 0i64, 0i64, 0i64, 0i64, 0i32, 0i32, 0i64, 0i64,
+                    0i64, 0i32, 0i32, 0i32, 0i32,
+                )
+            }
+        };
+        let ptr21 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr21 + 200) as *mut i32) = result2_25;
+        *((ptr21 + 192) as *mut i32) = result2_24;
+        *((ptr21 + 184) as *mut i32) = result2_23;
+        *((ptr21 + 176) as *mut i32) = result2_22;
+        *((ptr21 + 168) as *mut i64) = result2_21;
+        *((ptr21 + 160) as *mut i64) = result2_20;
+        *((ptr21 + 152) as *mut i64) = result2_19;
+        *((ptr21 + 144) as *mut i32) = result2_18;
+        *((ptr21 + 136) as *mut i32) = result2_17;
+        *((ptr21 + 128) as *mut i64) = result2_16;
+        *((ptr21 + 120) as *mut i64) = result2_15;
+        *((ptr21 + 112) as *mut i64) = result2_14;
+        *((ptr21 + 104) as *mut i64) = result2_13;
+        *((ptr21 + 96) as *mut i32) = result2_12;
+        *((ptr21 + 88) as *mut i32) = result2_11;
+        *((ptr21 + 80) as *mut i32) = result2_10;
+        *((ptr21 + 72) as *mut i32) = result2_9;
+        *((ptr21 + 64) as *mut i64) = result2_8;
+        *((ptr21 + 56) as *mut i64) = result2_7;
+        *((ptr21 + 48) as *mut i64) = result2_6;
+        *((ptr21 + 40) as *mut i64) = result2_5;
+        *((ptr21 + 32) as *mut i64) = result2_4;
+        *((ptr21 + 24) as *mut i64) = result2_3;
+        *((ptr21 + 16) as *mut i64) = result2_2;
+        *((ptr21 + 8) as *mut i32) = result2_1;
+        *((ptr21) as *mut i32) = result2_0;
+        ptr21
+    }
+    #[export_name = "swap-batch"]
+    unsafe extern "C" fn __wit_bindgen_swap_batch(
+        arg0: i32,
+        arg1: i32,
+        arg2: i32,
+        arg3: i32,
+        arg4: i32,
+    ) -> i32 {
+        let result3 = <super::Dex as Dex>::swap_batch(
+            {
+                let base1 = arg0;
+                let len1 = arg1;
+                let mut result1 = Vec::with_capacity(len1 as usize);
+                for i in 0..len1 {
+                    let base = base1 + i * 112;
+                    result1.push(PoolPair {
+                        token_a: *((base) as *const i32) as u32,
+                        token_b: *((base + 4) as *const i32) as u32,
+                        commission: *((base + 8) as *const i32) as u32,
+                        reserve_a: *((base + 16) as *const i64),
+                        reserve_b: *((base + 24) as *const i64),
+                        total_liquidity: *((base + 32) as *const i64),
+                        block_commission_a: *((base + 40) as *const i64),
+                        block_commission_b: *((base + 48) as *const i64),
+                        pool_type: match i32::from(*((base + 56) as *const u8)) {
+                            0 => PoolType::ConstantProduct,
+                            1 => PoolType::StableSwap,
+                            2 => PoolType::Weighted,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                        amplification: *((base + 60) as *const i32) as u32,
+                        weight_a: *((base + 64) as *const i32) as u32,
+                        weight_b: *((base + 68) as *const i32) as u32,
+                        price_ab_cumulative: CumulativePrice {
+                            high: *((base + 72) as *const i64) as u64,
+                            low: *((base + 80) as *const i64) as u64,
+                        },
+                        price_ba_cumulative: CumulativePrice {
+                            high: *((base + 88) as *const i64) as u64,
+                            low: *((base + 96) as *const i64) as u64,
+                        },
+                        price_height: *((base + 104) as *const i32) as u32,
+                        status: match i32::from(*((base + 108) as *const u8)) {
+                            0 => false,
+                            1 => true,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                    });
+                }
+                if len1 != 0 {
+                    std::alloc::dealloc(
+                        base1 as *mut u8,
+                        core::alloc::Layout::from_size_align_unchecked((len1 as usize) * 112, 8),
+                    );
+                }
+                result1
+            },
+            {
+                let len2 = arg3 as usize;
+                Vec::from_raw_parts(arg2 as *mut _, len2, len2)
+            },
+            match arg4 {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid enum discriminant"),
+            },
+        );
+        let (result4_0, result4_1, result4_2, result4_3, result4_4, result4_5) = match result3 {
+            Ok(e) => {
+                let SwapBatchResult {
+                    pool_pairs: pool_pairs5,
+                    results: results5,
+                } = e;
+                let vec6 = pool_pairs5;
+                let len6 = vec6.len() as i32;
+                let layout6 = core::alloc::Layout::from_size_align_unchecked(vec6.len() * 112, 8);
+                let result6 = if layout6.size() != 0 {
+                    let ptr = std::alloc::alloc(layout6);
+                    if ptr.is_null() {
+                        std::alloc::handle_alloc_error(layout6);
+                    }
+                    ptr
+                } else {
+                    std::ptr::null_mut()
+                };
+                for (i, e) in vec6.into_iter().enumerate() {
+                    let base = result6 as i32 + (i as i32) * 112;
+                    {
+                        let PoolPair {
+                            token_a: token_a7,
+                            token_b: token_b7,
+                            commission: commission7,
+                            reserve_a: reserve_a7,
+                            reserve_b: reserve_b7,
+                            total_liquidity: total_liquidity7,
+                            block_commission_a: block_commission_a7,
+                            block_commission_b: block_commission_b7,
+                            pool_type: pool_type7,
+                            amplification: amplification7,
+                            weight_a: weight_a7,
+                            weight_b: weight_b7,
+                            price_ab_cumulative: price_ab_cumulative7,
+                            price_ba_cumulative: price_ba_cumulative7,
+                            price_height: price_height7,
+                            status: status7,
+                        } = e;
+                        *((base) as *mut i32) = wit_bindgen_rust::rt::as_i32(token_a7);
+                        *((base + 4) as *mut i32) = wit_bindgen_rust::rt::as_i32(token_b7);
+                        *((base + 8) as *mut i32) = wit_bindgen_rust::rt::as_i32(commission7);
+                        *((base + 16) as *mut i64) = wit_bindgen_rust::rt::as_i64(reserve_a7);
+                        *((base + 24) as *mut i64) = wit_bindgen_rust::rt::as_i64(reserve_b7);
+                        *((base + 32) as *mut i64) = wit_bindgen_rust::rt::as_i64(total_liquidity7);
+                        *((base + 40) as *mut i64) =
+                            wit_bindgen_rust::rt::as_i64(block_commission_a7);
+                        *((base + 48) as *mut i64) =
+                            wit_bindgen_rust::rt::as_i64(block_commission_b7);
+                        *((base + 56) as *mut u8) = (pool_type7 as i32) as u8;
+                        *((base + 60) as *mut i32) = wit_bindgen_rust::rt::as_i32(amplification7);
+                        *((base + 64) as *mut i32) = wit_bindgen_rust::rt::as_i32(weight_a7);
+                        *((base + 68) as *mut i32) = wit_bindgen_rust::rt::as_i32(weight_b7);
+                        let CumulativePrice {
+                            high: high8,
+                            low: low8,
+                        } = price_ab_cumulative7;
+                        *((base + 72) as *mut i64) = wit_bindgen_rust::rt::as_i64(high8);
+                        *((base + 80) as *mut i64) = wit_bindgen_rust::rt::as_i64(low8);
+                        let CumulativePrice {
+                            high: high9,
+                            low: low9,
+                        } = price_ba_cumulative7;
+                        *((base + 88) as *mut i64) = wit_bindgen_rust::rt::as_i64(high9);
+                        *((base + 96) as *mut i64) = wit_bindgen_rust::rt::as_i64(low9);
+                        *((base + 104) as *mut i32) = wit_bindgen_rust::rt::as_i32(price_height7);
+                        *((base + 108) as *mut u8) = match status7 {
+                            false => 0u8,
+                            true => 1u8,
+                        };
+                    }
+                }
+                let vec10 = results5;
+                let len10 = vec10.len() as i32;
+                let layout10 = core::alloc::Layout::from_size_align_unchecked(vec10.len() * 48, 8);
+                let result10 = if layout10.size() != 0 {
+                    let ptr = std::alloc::alloc(layout10);
+                    if ptr.is_null() {
+                        std::alloc::handle_alloc_error(layout10);
+                    }
+                    ptr
+                } else {
+                    std::ptr::null_mut()
+                };
+                for (i, e) in vec10.into_iter().enumerate() {
+                    let base = result10 as i32 + (i as i32) * 48;
+                    {
+                        match e {
+                            Ok(e) => {
+                                *((base) as *mut u8) = 0u8;
+                                let SwapOutput {
+                                    amount: amount11,
+                                    dex_fee_in: dex_fee_in11,
+                                    dex_fee_out: dex_fee_out11,
+                                } = e;
+                                *((base + 8) as *mut i64) = wit_bindgen_rust::rt::as_i64(amount11);
+                                *((base + 16) as *mut i64) =
+                                    wit_bindgen_rust::rt::as_i64(dex_fee_in11);
+                                *((base + 24) as *mut i64) =
+                                    wit_bindgen_rust::rt::as_i64(dex_fee_out11);
+                            }
+                            Err(e) => {
+                                *((base) as *mut u8) = 1u8;
+                                match e {
+                                    Error::Runtime => {
+                                        *((base + 8) as *mut u8) = 0u8;
+                                    }
+                                    Error::NotFound => {
+                                        *((base + 8) as *mut u8) = 1u8;
+                                    }
+                                    Error::InvalidInput => {
+                                        *((base + 8) as *mut u8) = 2u8;
+                                    }
+                                    Error::LackOfLiquidity(e) => {
+                                        *((base + 8) as *mut u8) = 3u8;
+                                        let Reserves {
+                                            reserve_a: reserve_a12,
+                                            reserve_b: reserve_b12,
+                                        } = e;
+                                        *((base + 16) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(reserve_a12);
+                                        *((base + 24) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(reserve_b12);
+                                    }
+                                    Error::PriceHigherThanIndex(e) => {
+                                        *((base + 8) as *mut u8) = 4u8;
+                                        let PriceLimit {
+                                            price: price13,
+                                            max_price: max_price13,
+                                        } = e;
+                                        let PoolPrice {
+                                            integer: integer14,
+                                            fraction: fraction14,
+                                        } = price13;
+                                        *((base + 16) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(integer14);
+                                        *((base + 24) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(fraction14);
+                                        let PoolPrice {
+                                            integer: integer15,
+                                            fraction: fraction15,
+                                        } = max_price13;
+                                        *((base + 32) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(integer15);
+                                        *((base + 40) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(fraction15);
+                                    }
+                                    Error::PoolReserveOverflow(e) => {
+                                        *((base + 8) as *mut u8) = 5u8;
+                                        let Reserves {
+                                            reserve_a: reserve_a16,
+                                            reserve_b: reserve_b16,
+                                        } = e;
+                                        *((base + 16) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(reserve_a16);
+                                        *((base + 24) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(reserve_b16);
+                                    }
+                                    Error::OutputBelowMinimum(e) => {
+                                        *((base + 8) as *mut u8) = 6u8;
+                                        let OutputLimit {
+                                            amount: amount17,
+                                            min_output: min_output17,
+                                        } = e;
+                                        *((base + 16) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(amount17);
+                                        *((base + 24) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(min_output17);
+                                    }
+                                    Error::TokenMismatch(e) => {
+                                        *((base + 8) as *mut u8) = 7u8;
+                                        let InputTokenMismatch {
+                                            token_id: token_id18,
+                                            token_a: token_a18,
+                                            token_b: token_b18,
+                                        } = e;
+                                        *((base + 16) as *mut i32) =
+                                            wit_bindgen_rust::rt::as_i32(token_id18);
+                                        *((base + 20) as *mut i32) =
+                                            wit_bindgen_rust::rt::as_i32(token_a18);
+                                        *((base + 24) as *mut i32) =
+                                            wit_bindgen_rust::rt::as_i32(token_b18);
+                                    }
+                                    Error::ArithmeticOverflow => {
+                                        *((base + 8) as *mut u8) = 8u8;
+                                    }
+                                    Error::InvariantViolation(e) => {
+                                        *((base + 8) as *mut u8) = 9u8;
+                                        let Reserves {
+                                            reserve_a: reserve_a19,
+                                            reserve_b: reserve_b19,
+                                        } = e;
+                                        *((base + 16) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(reserve_a19);
+                                        *((base + 24) as *mut i64) =
+                                            wit_bindgen_rust::rt::as_i64(reserve_b19);
+                                    }
+                                    Error::InvalidPoolConfig(e) => {
+                                        *((base + 8) as *mut u8) = 10u8;
+                                        *((base + 16) as *mut u8) = (e as i32) as u8;
+                                    }
+                                    Error::PoolNotFound(e) => {
+                                        *((base + 8) as *mut u8) = 11u8;
+                                        let PoolIndex { index: index20 } = e;
+                                        *((base + 16) as *mut i32) =
+                                            wit_bindgen_rust::rt::as_i32(index20);
+                                    }
+                                    Error::PoolDisabled => {
+                                        *((base + 8) as *mut u8) = 12u8;
+                                    }
+                                };
+                            }
+                        };
+                    }
+                }
+
+                (
+                    0i32,
+                    result6 as i32,
+                    i64::from(len6),
+                    i64::from(result10 as i32),
+                    i64::from(len10),
+                    0i64,
+                )
+            }
+            Err(e) => {
+                let (result21_0, result21_1, result21_2, result21_3, result21_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a22,
+                            reserve_b: reserve_b22,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a22),
+                            wit_bindgen_rust::rt::as_i64(reserve_b22),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price23,
+                            max_price: max_price23,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer24,
+                            fraction: fraction24,
+                        } = price23;
+                        let PoolPrice {
+                            integer: integer25,
+                            fraction: fraction25,
+                        } = max_price23;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer24),
+                            wit_bindgen_rust::rt::as_i64(fraction24),
+                            wit_bindgen_rust::rt::as_i64(integer25),
+                            wit_bindgen_rust::rt::as_i64(fraction25),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a26,
+                            reserve_b: reserve_b26,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a26),
+                            wit_bindgen_rust::rt::as_i64(reserve_b26),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount27,
+                            min_output: min_output27,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount27),
+                            wit_bindgen_rust::rt::as_i64(min_output27),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id28,
+                            token_a: token_a28,
+                            token_b: token_b28,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id28)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a28)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b28)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a29,
+                            reserve_b: reserve_b29,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a29),
+                            wit_bindgen_rust::rt::as_i64(reserve_b29),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index30 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index30)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result21_0, result21_1, result21_2, result21_3, result21_4,
+                )
+            }
+        };
+        let ptr31 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr31 + 40) as *mut i64) = result4_5;
+        *((ptr31 + 32) as *mut i64) = result4_4;
+        *((ptr31 + 24) as *mut i64) = result4_3;
+        *((ptr31 + 16) as *mut i64) = result4_2;
+        *((ptr31 + 8) as *mut i32) = result4_1;
+        *((ptr31) as *mut i32) = result4_0;
+        ptr31
+    }
+    #[export_name = "composite-swap"]
+    unsafe extern "C" fn __wit_bindgen_composite_swap(
+        arg0: i32,
+        arg1: i32,
+        arg2: i32,
+        arg3: i64,
+        arg4: i64,
+        arg5: i64,
+        arg6: i32,
+        arg7: i32,
+        arg8: i32,
+    ) -> i32 {
+        let result3 = <super::Dex as Dex>::composite_swap(
+            {
+                let base1 = arg0;
+                let len1 = arg1;
+                let mut result1 = Vec::with_capacity(len1 as usize);
+                for i in 0..len1 {
+                    let base = base1 + i * 112;
+                    result1.push(PoolPair {
+                        token_a: *((base) as *const i32) as u32,
+                        token_b: *((base + 4) as *const i32) as u32,
+                        commission: *((base + 8) as *const i32) as u32,
+                        reserve_a: *((base + 16) as *const i64),
+                        reserve_b: *((base + 24) as *const i64),
+                        total_liquidity: *((base + 32) as *const i64),
+                        block_commission_a: *((base + 40) as *const i64),
+                        block_commission_b: *((base + 48) as *const i64),
+                        pool_type: match i32::from(*((base + 56) as *const u8)) {
+                            0 => PoolType::ConstantProduct,
+                            1 => PoolType::StableSwap,
+                            2 => PoolType::Weighted,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                        amplification: *((base + 60) as *const i32) as u32,
+                        weight_a: *((base + 64) as *const i32) as u32,
+                        weight_b: *((base + 68) as *const i32) as u32,
+                        price_ab_cumulative: CumulativePrice {
+                            high: *((base + 72) as *const i64) as u64,
+                            low: *((base + 80) as *const i64) as u64,
+                        },
+                        price_ba_cumulative: CumulativePrice {
+                            high: *((base + 88) as *const i64) as u64,
+                            low: *((base + 96) as *const i64) as u64,
+                        },
+                        price_height: *((base + 104) as *const i32) as u32,
+                        status: match i32::from(*((base + 108) as *const u8)) {
+                            0 => false,
+                            1 => true,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                    });
+                }
+                if len1 != 0 {
+                    std::alloc::dealloc(
+                        base1 as *mut u8,
+                        core::alloc::Layout::from_size_align_unchecked((len1 as usize) * 112, 8),
+                    );
+                }
+                result1
+            },
+            TokenAmount {
+                token_id: arg2 as u32,
+                amount: arg3,
+            },
+            PoolPrice {
+                integer: arg4,
+                fraction: arg5,
+            },
+            {
+                let len2 = arg7 as usize;
+                Vec::from_raw_parts(arg6 as *mut _, len2, len2)
+            },
+            match arg8 {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid enum discriminant"),
+            },
+        );
+        let (result4_0, result4_1, result4_2, result4_3, result4_4, result4_5) = match result3 {
+            Ok(e) => {
+                let CompositeSwapResult {
+                    pool_pairs: pool_pairs5,
+                    slop_swap_result: slop_swap_result5,
+                } = e;
+                let vec6 = pool_pairs5;
+                let len6 = vec6.len() as i32;
+                let layout6 = core::alloc::Layout::from_size_align_unchecked(vec6.len() * 112, 8);
+                let result6 = if layout6.size() != 0 {
+                    let ptr = std::alloc::alloc(layout6);
+                    if ptr.is_null() {
+                        std::alloc::handle_alloc_error(layout6);
+                    }
+                    ptr
+                } else {
+                    std::ptr::null_mut()
+                };
+                for (i, e) in vec6.into_iter().enumerate() {
+                    let base = result6 as i32 + (i as i32) * 112;
+                    {
+                        let PoolPair {
+                            token_a: token_a7,
+                            token_b: token_b7,
+                            commission: commission7,
+                            reserve_a: reserve_a7,
+                            reserve_b: reserve_b7,
+                            total_liquidity: total_liquidity7,
+                            block_commission_a: block_commission_a7,
+                            block_commission_b: block_commission_b7,
+                            pool_type: pool_type7,
+                            amplification: amplification7,
+                            weight_a: weight_a7,
+                            weight_b: weight_b7,
+                            price_ab_cumulative: price_ab_cumulative7,
+                            price_ba_cumulative: price_ba_cumulative7,
+                            price_height: price_height7,
+                            status: status7,
+                        } = e;
+                        *((base) as *mut i32) = wit_bindgen_rust::rt::as_i32(token_a7);
+                        *((base + 4) as *mut i32) = wit_bindgen_rust::rt::as_i32(token_b7);
+                        *((base + 8) as *mut i32) = wit_bindgen_rust::rt::as_i32(commission7);
+                        *((base + 16) as *mut i64) = wit_bindgen_rust::rt::as_i64(reserve_a7);
+                        *((base + 24) as *mut i64) = wit_bindgen_rust::rt::as_i64(reserve_b7);
+                        *((base + 32) as *mut i64) = wit_bindgen_rust::rt::as_i64(total_liquidity7);
+                        *((base + 40) as *mut i64) =
+                            wit_bindgen_rust::rt::as_i64(block_commission_a7);
+                        *((base + 48) as *mut i64) =
+                            wit_bindgen_rust::rt::as_i64(block_commission_b7);
+                        *((base + 56) as *mut u8) = (pool_type7 as i32) as u8;
+                        *((base + 60) as *mut i32) = wit_bindgen_rust::rt::as_i32(amplification7);
+                        *((base + 64) as *mut i32) = wit_bindgen_rust::rt::as_i32(weight_a7);
+                        *((base + 68) as *mut i32) = wit_bindgen_rust::rt::as_i32(weight_b7);
+                        let CumulativePrice {
+                            high: high8,
+                            low: low8,
+                        } = price_ab_cumulative7;
+                        *((base + 72) as *mut i64) = wit_bindgen_rust::rt::as_i64(high8);
+                        *((base + 80) as *mut i64) = wit_bindgen_rust::rt::as_i64(low8);
+                        let CumulativePrice {
+                            high: high9,
+                            low: low9,
+                        } = price_ba_cumulative7;
+                        *((base + 88) as *mut i64) = wit_bindgen_rust::rt::as_i64(high9);
+                        *((base + 96) as *mut i64) = wit_bindgen_rust::rt::as_i64(low9);
+                        *((base + 104) as *mut i32) = wit_bindgen_rust::rt::as_i32(price_height7);
+                        *((base + 108) as *mut u8) = match status7 {
+                            false => 0u8,
+                            true => 1u8,
+                        };
+                    }
+                }
+
+                (
+                    0i32,
+                    result6 as i32,
+                    i64::from(len6),
+                    wit_bindgen_rust::rt::as_i64(slop_swap_result5),
+                    0i64,
+                    0i64,
+                )
+            }
+            Err(e) => {
+                let (result10_0, result10_1, result10_2, result10_3, result10_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a11,
+                            reserve_b: reserve_b11,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a11),
+                            wit_bindgen_rust::rt::as_i64(reserve_b11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price12,
+                            max_price: max_price12,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer13,
+                            fraction: fraction13,
+                        } = price12;
+                        let PoolPrice {
+                            integer: integer14,
+                            fraction: fraction14,
+                        } = max_price12;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer13),
+                            wit_bindgen_rust::rt::as_i64(fraction13),
+                            wit_bindgen_rust::rt::as_i64(integer14),
+                            wit_bindgen_rust::rt::as_i64(fraction14),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a15,
+                            reserve_b: reserve_b15,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a15),
+                            wit_bindgen_rust::rt::as_i64(reserve_b15),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount16,
+                            min_output: min_output16,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount16),
+                            wit_bindgen_rust::rt::as_i64(min_output16),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id17,
+                            token_a: token_a17,
+                            token_b: token_b17,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id17)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a17)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b17)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a18,
+                            reserve_b: reserve_b18,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a18),
+                            wit_bindgen_rust::rt::as_i64(reserve_b18),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index19 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index19)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result10_0, result10_1, result10_2, result10_3, result10_4,
+                )
+            }
+        };
+        let ptr20 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr20 + 40) as *mut i64) = result4_5;
+        *((ptr20 + 32) as *mut i64) = result4_4;
+        *((ptr20 + 24) as *mut i64) = result4_3;
+        *((ptr20 + 16) as *mut i64) = result4_2;
+        *((ptr20 + 8) as *mut i32) = result4_1;
+        *((ptr20) as *mut i32) = result4_0;
+        ptr20
+    }
+    #[export_name = "best-path"]
+    unsafe extern "C" fn __wit_bindgen_best_path(
+        arg0: i32,
+        arg1: i32,
+        arg2: i32,
+        arg3: i64,
+        arg4: i32,
+        arg5: i32,
+        arg6: i64,
+        arg7: i64,
+        arg8: i32,
+        arg9: i32,
+        arg10: i32,
+    ) -> i32 {
+        let result3 = <super::Dex as Dex>::best_path(
+            {
+                let base1 = arg0;
+                let len1 = arg1;
+                let mut result1 = Vec::with_capacity(len1 as usize);
+                for i in 0..len1 {
+                    let base = base1 + i * 112;
+                    result1.push(PoolPair {
+                        token_a: *((base) as *const i32) as u32,
+                        token_b: *((base + 4) as *const i32) as u32,
+                        commission: *((base + 8) as *const i32) as u32,
+                        reserve_a: *((base + 16) as *const i64),
+                        reserve_b: *((base + 24) as *const i64),
+                        total_liquidity: *((base + 32) as *const i64),
+                        block_commission_a: *((base + 40) as *const i64),
+                        block_commission_b: *((base + 48) as *const i64),
+                        pool_type: match i32::from(*((base + 56) as *const u8)) {
+                            0 => PoolType::ConstantProduct,
+                            1 => PoolType::StableSwap,
+                            2 => PoolType::Weighted,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                        amplification: *((base + 60) as *const i32) as u32,
+                        weight_a: *((base + 64) as *const i32) as u32,
+                        weight_b: *((base + 68) as *const i32) as u32,
+                        price_ab_cumulative: CumulativePrice {
+                            high: *((base + 72) as *const i64) as u64,
+                            low: *((base + 80) as *const i64) as u64,
+                        },
+                        price_ba_cumulative: CumulativePrice {
+                            high: *((base + 88) as *const i64) as u64,
+                            low: *((base + 96) as *const i64) as u64,
+                        },
+                        price_height: *((base + 104) as *const i32) as u32,
+                        status: match i32::from(*((base + 108) as *const u8)) {
+                            0 => false,
+                            1 => true,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                    });
+                }
+                if len1 != 0 {
+                    std::alloc::dealloc(
+                        base1 as *mut u8,
+                        core::alloc::Layout::from_size_align_unchecked((len1 as usize) * 112, 8),
+                    );
+                }
+                result1
+            },
+            TokenAmount {
+                token_id: arg2 as u32,
+                amount: arg3,
+            },
+            arg4 as u32,
+            arg5 as u32,
+            PoolPrice {
+                integer: arg6,
+                fraction: arg7,
+            },
+            {
+                let len2 = arg9 as usize;
+                Vec::from_raw_parts(arg8 as *mut _, len2, len2)
+            },
+            match arg10 {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid enum discriminant"),
+            },
+        );
+        let (result4_0, result4_1, result4_2, result4_3, result4_4, result4_5) = match result3 {
+            Ok(e) => {
+                let BestPathResult {
+                    best: best5,
+                    alternatives: alternatives5,
+                } = e;
+                let SwapRoute {
+                    path: path6,
+                    slop_swap_result: slop_swap_result6,
+                } = best5;
+                let vec7 = (path6).into_boxed_slice();
+                let ptr7 = vec7.as_ptr() as i32;
+                let len7 = vec7.len() as i32;
+                core::mem::forget(vec7);
+                let vec8 = alternatives5;
+                let len8 = vec8.len() as i32;
+                let layout8 = core::alloc::Layout::from_size_align_unchecked(vec8.len() * 16, 8);
+                let result8 = if layout8.size() != 0 {
+                    let ptr = std::alloc::alloc(layout8);
+                    if ptr.is_null() {
+                        std::alloc::handle_alloc_error(layout8);
+                    }
+                    ptr
+                } else {
+                    std::ptr::null_mut()
+                };
+                for (i, e) in vec8.into_iter().enumerate() {
+                    let base = result8 as i32 + (i as i32) * 16;
+                    {
+                        let SwapRoute {
+                            path: path9,
+                            slop_swap_result: slop_swap_result9,
+                        } = e;
+                        let vec10 = (path9).into_boxed_slice();
+                        let ptr10 = vec10.as_ptr() as i32;
+                        let len10 = vec10.len() as i32;
+                        core::mem::forget(vec10);
+                        *((base + 4) as *mut i32) = len10;
+                        *((base) as *mut i32) = ptr10;
+                        *((base + 8) as *mut i64) = wit_bindgen_rust::rt::as_i64(slop_swap_result9);
+                    }
+                }
+
+                (
+                    0i32,
+                    ptr7,
+                    i64::from(len7),
+                    wit_bindgen_rust::rt::as_i64(slop_swap_result6),
+                    i64::from(result8 as i32),
+                    i64::from(len8),
+                )
+            }
+            Err(e) => {
+                let (result11_0, result11_1, result11_2, result11_3, result11_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a12,
+                            reserve_b: reserve_b12,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a12),
+                            wit_bindgen_rust::rt::as_i64(reserve_b12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price13,
+                            max_price: max_price13,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer14,
+                            fraction: fraction14,
+                        } = price13;
+                        let PoolPrice {
+                            integer: integer15,
+                            fraction: fraction15,
+                        } = max_price13;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer14),
+                            wit_bindgen_rust::rt::as_i64(fraction14),
+                            wit_bindgen_rust::rt::as_i64(integer15),
+                            wit_bindgen_rust::rt::as_i64(fraction15),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a16,
+                            reserve_b: reserve_b16,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a16),
+                            wit_bindgen_rust::rt::as_i64(reserve_b16),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount17,
+                            min_output: min_output17,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount17),
+                            wit_bindgen_rust::rt::as_i64(min_output17),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id18,
+                            token_a: token_a18,
+                            token_b: token_b18,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id18)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a18)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b18)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a19,
+                            reserve_b: reserve_b19,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a19),
+                            wit_bindgen_rust::rt::as_i64(reserve_b19),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index20 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index20)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result11_0, result11_1, result11_2, result11_3, result11_4,
+                )
+            }
+        };
+        let ptr21 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr21 + 40) as *mut i64) = result4_5;
+        *((ptr21 + 32) as *mut i64) = result4_4;
+        *((ptr21 + 24) as *mut i64) = result4_3;
+        *((ptr21 + 16) as *mut i64) = result4_2;
+        *((ptr21 + 8) as *mut i32) = result4_1;
+        *((ptr21) as *mut i32) = result4_0;
+        ptr21
+    }
+    #[export_name = "quote"]
+    unsafe extern "C" fn __wit_bindgen_quote(arg0: i32) -> i32 {
+        let result1 = <super::Dex as Dex>::quote(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            TokenAmount {
+                token_id: *((arg0 + 112) as *const i32) as u32,
+                amount: *((arg0 + 120) as *const i64),
+            },
+            DexFee {
+                in_pct_ab: *((arg0 + 128) as *const i64),
+                out_pct_ab: *((arg0 + 136) as *const i64),
+                in_pct_ba: *((arg0 + 144) as *const i64),
+                out_pct_ba: *((arg0 + 152) as *const i64),
+            },
+            match i32::from(*((arg0 + 160) as *const u8)) {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid enum discriminant"),
+            },
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(168, 8),
+        );
+        let (result2_0, result2_1, result2_2, result2_3, result2_4, result2_5) = match result1 {
+            Ok(e) => (
+                0i32,
+                wit_bindgen_rust::rt::as_i64(e),
+                0i64,
+                0i64,
+                0i64,
+                0i64,
             ),
+            Err(e) => {
+                let (result3_0, result3_1, result3_2, result3_3, result3_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a4,
+                            reserve_b: reserve_b4,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a4),
+                            wit_bindgen_rust::rt::as_i64(reserve_b4),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price5,
+                            max_price: max_price5,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer6,
+                            fraction: fraction6,
+                        } = price5;
+                        let PoolPrice {
+                            integer: integer7,
+                            fraction: fraction7,
+                        } = max_price5;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer6),
+                            wit_bindgen_rust::rt::as_i64(fraction6),
+                            wit_bindgen_rust::rt::as_i64(integer7),
+                            wit_bindgen_rust::rt::as_i64(fraction7),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a8,
+                            reserve_b: reserve_b8,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a8),
+                            wit_bindgen_rust::rt::as_i64(reserve_b8),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount9,
+                            min_output: min_output9,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount9),
+                            wit_bindgen_rust::rt::as_i64(min_output9),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id10,
+                            token_a: token_a10,
+                            token_b: token_b10,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id10)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a10)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b10)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a11,
+                            reserve_b: reserve_b11,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a11),
+                            wit_bindgen_rust::rt::as_i64(reserve_b11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index12 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index12)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32,
+                    i64::from(result3_0),
+                    result3_1,
+                    result3_2,
+                    result3_3,
+                    result3_4,
+                )
+            }
+        };
+        let ptr13 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr13 + 40) as *mut i64) = result2_5;
+        *((ptr13 + 32) as *mut i64) = result2_4;
+        *((ptr13 + 24) as *mut i64) = result2_3;
+        *((ptr13 + 16) as *mut i64) = result2_2;
+        *((ptr13 + 8) as *mut i64) = result2_1;
+        *((ptr13) as *mut i32) = result2_0;
+        ptr13
+    }
+    #[export_name = "add-liquidity"]
+    unsafe extern "C" fn __wit_bindgen_add_liquidity(arg0: i32) -> i32 {
+        let result1 = <super::Dex as Dex>::add_liquidity(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            *((arg0 + 112) as *const i64),
+            *((arg0 + 120) as *const i64),
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(128, 8),
+        );
+        let (
+            result2_0,
+            result2_1,
+            result2_2,
+            result2_3,
+            result2_4,
+            result2_5,
+            result2_6,
+            result2_7,
+            result2_8,
+            result2_9,
+            result2_10,
+            result2_11,
+            result2_12,
+            result2_13,
+            result2_14,
+            result2_15,
+            result2_16,
+            result2_17,
+            result2_18,
+            result2_19,
+            result2_20,
+            result2_21,
+        ) = match result1 {
+            Ok(e) => {
+                let LiquidityResult {
+                    pool_pair: pool_pair3,
+                    amount_a: amount_a3,
+                    amount_b: amount_b3,
+                    liquidity: liquidity3,
+                } = e;
+                let PoolPair {
+                    token_a: token_a4,
+                    token_b: token_b4,
+                    commission: commission4,
+                    reserve_a: reserve_a4,
+                    reserve_b: reserve_b4,
+                    total_liquidity: total_liquidity4,
+                    block_commission_a: block_commission_a4,
+                    block_commission_b: block_commission_b4,
+                    pool_type: pool_type4,
+                    amplification: amplification4,
+                    weight_a: weight_a4,
+                    weight_b: weight_b4,
+                    price_ab_cumulative: price_ab_cumulative4,
+                    price_ba_cumulative: price_ba_cumulative4,
+                    price_height: price_height4,
+                    status: status4,
+                } = pool_pair3;
+                let CumulativePrice {
+                    high: high5,
+                    low: low5,
+                } = price_ab_cumulative4;
+                let CumulativePrice {
+                    high: high6,
+                    low: low6,
+                } = price_ba_cumulative4;
+
+                (
+                    0i32,
+                    wit_bindgen_rust::rt::as_i32(token_a4),
+                    i64::from(wit_bindgen_rust::rt::as_i32(token_b4)),
+                    i64::from(wit_bindgen_rust::rt::as_i32(commission4)),
+                    wit_bindgen_rust::rt::as_i64(reserve_a4),
+                    wit_bindgen_rust::rt::as_i64(reserve_b4),
+                    wit_bindgen_rust::rt::as_i64(total_liquidity4),
+                    wit_bindgen_rust::rt::as_i64(block_commission_a4),
+                    wit_bindgen_rust::rt::as_i64(block_commission_b4),
+                    pool_type4 as i32,
+                    wit_bindgen_rust::rt::as_i32(amplification4),
+                    wit_bindgen_rust::rt::as_i32(weight_a4),
+                    wit_bindgen_rust::rt::as_i32(weight_b4),
+                    wit_bindgen_rust::rt::as_i64(high5),
+                    wit_bindgen_rust::rt::as_i64(low5),
+                    wit_bindgen_rust::rt::as_i64(high6),
+                    wit_bindgen_rust::rt::as_i64(low6),
+                    wit_bindgen_rust::rt::as_i32(price_height4),
+                    match status4 {
+                        false => 0i32,
+                        true => 1i32,
+                    },
+                    wit_bindgen_rust::rt::as_i64(amount_a3),
+                    wit_bindgen_rust::rt::as_i64(amount_b3),
+                    wit_bindgen_rust::rt::as_i64(liquidity3),
+                )
+            }
+            Err(e) => {
+                let (result7_0, result7_1, result7_2, result7_3, result7_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a8,
+                            reserve_b: reserve_b8,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a8),
+                            wit_bindgen_rust::rt::as_i64(reserve_b8),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price9,
+                            max_price: max_price9,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer10,
+                            fraction: fraction10,
+                        } = price9;
+                        let PoolPrice {
+                            integer: integer11,
+                            fraction: fraction11,
+                        } = max_price9;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer10),
+                            wit_bindgen_rust::rt::as_i64(fraction10),
+                            wit_bindgen_rust::rt::as_i64(integer11),
+                            wit_bindgen_rust::rt::as_i64(fraction11),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a12,
+                            reserve_b: reserve_b12,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a12),
+                            wit_bindgen_rust::rt::as_i64(reserve_b12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount13,
+                            min_output: min_output13,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount13),
+                            wit_bindgen_rust::rt::as_i64(min_output13),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id14,
+                            token_a: token_a14,
+                            token_b: token_b14,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id14)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a14)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b14)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a15,
+                            reserve_b: reserve_b15,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a15),
+                            wit_bindgen_rust::rt::as_i64(reserve_b15),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index16 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index16)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result7_0, result7_1, result7_2, result7_3, result7_4, 0i64, 0i64, 0i64,
+                    0i32, 0i32, 0i32, 0i32, 0i64, 0i64, 0i64, 0i64, 0i32, 0i32, 0i64, 0i64, 0i64,
+                )
+            }
+        };
+        let ptr17 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr17 + 168) as *mut i64) = result2_21;
+        *((ptr17 + 160) as *mut i64) = result2_20;
+        *((ptr17 + 152) as *mut i64) = result2_19;
+        *((ptr17 + 144) as *mut i32) = result2_18;
+        *((ptr17 + 136) as *mut i32) = result2_17;
+        *((ptr17 + 128) as *mut i64) = result2_16;
+        *((ptr17 + 120) as *mut i64) = result2_15;
+        *((ptr17 + 112) as *mut i64) = result2_14;
+        *((ptr17 + 104) as *mut i64) = result2_13;
+        *((ptr17 + 96) as *mut i32) = result2_12;
+        *((ptr17 + 88) as *mut i32) = result2_11;
+        *((ptr17 + 80) as *mut i32) = result2_10;
+        *((ptr17 + 72) as *mut i32) = result2_9;
+        *((ptr17 + 64) as *mut i64) = result2_8;
+        *((ptr17 + 56) as *mut i64) = result2_7;
+        *((ptr17 + 48) as *mut i64) = result2_6;
+        *((ptr17 + 40) as *mut i64) = result2_5;
+        *((ptr17 + 32) as *mut i64) = result2_4;
+        *((ptr17 + 24) as *mut i64) = result2_3;
+        *((ptr17 + 16) as *mut i64) = result2_2;
+        *((ptr17 + 8) as *mut i32) = result2_1;
+        *((ptr17) as *mut i32) = result2_0;
+        ptr17
+    }
+    #[export_name = "remove-liquidity"]
+    unsafe extern "C" fn __wit_bindgen_remove_liquidity(arg0: i32) -> i32 {
+        let result1 = <super::Dex as Dex>::remove_liquidity(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            *((arg0 + 112) as *const i64),
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(120, 8),
+        );
+        let (
+            result2_0,
+            result2_1,
+            result2_2,
+            result2_3,
+            result2_4,
+            result2_5,
+            result2_6,
+            result2_7,
+            result2_8,
+            result2_9,
+            result2_10,
+            result2_11,
+            result2_12,
+            result2_13,
+            result2_14,
+            result2_15,
+            result2_16,
+            result2_17,
+            result2_18,
+            result2_19,
+            result2_20,
+            result2_21,
+        ) = match result1 {
+            Ok(e) => {
+                let LiquidityResult {
+                    pool_pair: pool_pair3,
+                    amount_a: amount_a3,
+                    amount_b: amount_b3,
+                    liquidity: liquidity3,
+                } = e;
+                let PoolPair {
+                    token_a: token_a4,
+                    token_b: token_b4,
+                    commission: commission4,
+                    reserve_a: reserve_a4,
+                    reserve_b: reserve_b4,
+                    total_liquidity: total_liquidity4,
+                    block_commission_a: block_commission_a4,
+                    block_commission_b: block_commission_b4,
+                    pool_type: pool_type4,
+                    amplification: amplification4,
+                    weight_a: weight_a4,
+                    weight_b: weight_b4,
+                    price_ab_cumulative: price_ab_cumulative4,
+                    price_ba_cumulative: price_ba_cumulative4,
+                    price_height: price_height4,
+                    status: status4,
+                } = pool_pair3;
+                let CumulativePrice {
+                    high: high5,
+                    low: low5,
+                } = price_ab_cumulative4;
+                let CumulativePrice {
+                    high: high6,
+                    low: low6,
+                } = price_ba_cumulative4;
+
+                (
+                    0i32,
+                    wit_bindgen_rust::rt::as_i32(token_a4),
+                    i64::from(wit_bindgen_rust::rt::as_i32(token_b4)),
+                    i64::from(wit_bindgen_rust::rt::as_i32(commission4)),
+                    wit_bindgen_rust::rt::as_i64(reserve_a4),
+                    wit_bindgen_rust::rt::as_i64(reserve_b4),
+                    wit_bindgen_rust::rt::as_i64(total_liquidity4),
+                    wit_bindgen_rust::rt::as_i64(block_commission_a4),
+                    wit_bindgen_rust::rt::as_i64(block_commission_b4),
+                    pool_type4 as i32,
+                    wit_bindgen_rust::rt::as_i32(amplification4),
+                    wit_bindgen_rust::rt::as_i32(weight_a4),
+                    wit_bindgen_rust::rt::as_i32(weight_b4),
+                    wit_bindgen_rust::rt::as_i64(high5),
+                    wit_bindgen_rust::rt::as_i64(low5),
+                    wit_bindgen_rust::rt::as_i64(high6),
+                    wit_bindgen_rust::rt::as_i64(low6),
+                    wit_bindgen_rust::rt::as_i32(price_height4),
+                    match status4 {
+                        false => 0i32,
+                        true => 1i32,
+                    },
+                    wit_bindgen_rust::rt::as_i64(amount_a3),
+                    wit_bindgen_rust::rt::as_i64(amount_b3),
+                    wit_bindgen_rust::rt::as_i64(liquidity3),
+                )
+            }
+            Err(e) => {
+                let (result7_0, result7_1, result7_2, result7_3, result7_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a8,
+                            reserve_b: reserve_b8,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a8),
+                            wit_bindgen_rust::rt::as_i64(reserve_b8),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price9,
+                            max_price: max_price9,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer10,
+                            fraction: fraction10,
+                        } = price9;
+                        let PoolPrice {
+                            integer: integer11,
+                            fraction: fraction11,
+                        } = max_price9;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer10),
+                            wit_bindgen_rust::rt::as_i64(fraction10),
+                            wit_bindgen_rust::rt::as_i64(integer11),
+                            wit_bindgen_rust::rt::as_i64(fraction11),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a12,
+                            reserve_b: reserve_b12,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a12),
+                            wit_bindgen_rust::rt::as_i64(reserve_b12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount13,
+                            min_output: min_output13,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount13),
+                            wit_bindgen_rust::rt::as_i64(min_output13),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id14,
+                            token_a: token_a14,
+                            token_b: token_b14,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id14)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a14)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b14)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a15,
+                            reserve_b: reserve_b15,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a15),
+                            wit_bindgen_rust::rt::as_i64(reserve_b15),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index16 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index16)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result7_0, result7_1, result7_2, result7_3, result7_4, 0i64, 0i64, 0i64,
+                    0i32, 0i32, 0i32, 0i32, 0i64, 0i64, 0i64, 0i64, 0i32, 0i32, 0i64, 0i64, 0i64,
+                )
+            }
+        };
+        let ptr17 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr17 + 168) as *mut i64) = result2_21;
+        *((ptr17 + 160) as *mut i64) = result2_20;
+        *((ptr17 + 152) as *mut i64) = result2_19;
+        *((ptr17 + 144) as *mut i32) = result2_18;
+        *((ptr17 + 136) as *mut i32) = result2_17;
+        *((ptr17 + 128) as *mut i64) = result2_16;
+        *((ptr17 + 120) as *mut i64) = result2_15;
+        *((ptr17 + 112) as *mut i64) = result2_14;
+        *((ptr17 + 104) as *mut i64) = result2_13;
+        *((ptr17 + 96) as *mut i32) = result2_12;
+        *((ptr17 + 88) as *mut i32) = result2_11;
+        *((ptr17 + 80) as *mut i32) = result2_10;
+        *((ptr17 + 72) as *mut i32) = result2_9;
+        *((ptr17 + 64) as *mut i64) = result2_8;
+        *((ptr17 + 56) as *mut i64) = result2_7;
+        *((ptr17 + 48) as *mut i64) = result2_6;
+        *((ptr17 + 40) as *mut i64) = result2_5;
+        *((ptr17 + 32) as *mut i64) = result2_4;
+        *((ptr17 + 24) as *mut i64) = result2_3;
+        *((ptr17 + 16) as *mut i64) = result2_2;
+        *((ptr17 + 8) as *mut i32) = result2_1;
+        *((ptr17) as *mut i32) = result2_0;
+        ptr17
+    }
+    #[export_name = "create-pool-pair"]
+    unsafe extern "C" fn __wit_bindgen_create_pool_pair(
+        arg0: i32,
+        arg1: i32,
+        arg2: i32,
+        arg3: i32,
+        arg4: i32,
+        arg5: i32,
+        arg6: i32,
+        arg7: i32,
+        arg8: i32,
+        arg9: i32,
+    ) -> i32 {
+        let result2 = <super::Dex as Dex>::create_pool_pair(
+            PoolPairConfig {
+                token_a: arg0 as u32,
+                token_b: arg1 as u32,
+                commission: arg2 as u32,
+                status: match arg3 {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                pool_type: match arg4 {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: arg5 as u32,
+                weight_a: arg6 as u32,
+                weight_b: arg7 as u32,
+            },
+            {
+                let len1 = arg9 as usize;
+                String::from_utf8(Vec::from_raw_parts(arg8 as *mut _, len1, len1)).unwrap()
+            },
+        );
+        let (
+            result3_0,
+            result3_1,
+            result3_2,
+            result3_3,
+            result3_4,
+            result3_5,
+            result3_6,
+            result3_7,
+            result3_8,
+            result3_9,
+            result3_10,
+            result3_11,
+            result3_12,
+            result3_13,
+            result3_14,
+            result3_15,
+            result3_16,
+            result3_17,
+            result3_18,
+        ) = match result2 {
+            Ok(e) => {
+                let PoolPair {
+                    token_a: token_a4,
+                    token_b: token_b4,
+                    commission: commission4,
+                    reserve_a: reserve_a4,
+                    reserve_b: reserve_b4,
+                    total_liquidity: total_liquidity4,
+                    block_commission_a: block_commission_a4,
+                    block_commission_b: block_commission_b4,
+                    pool_type: pool_type4,
+                    amplification: amplification4,
+                    weight_a: weight_a4,
+                    weight_b: weight_b4,
+                    price_ab_cumulative: price_ab_cumulative4,
+                    price_ba_cumulative: price_ba_cumulative4,
+                    price_height: price_height4,
+                    status: status4,
+                } = e;
+                let CumulativePrice {
+                    high: high5,
+                    low: low5,
+                } = price_ab_cumulative4;
+                let CumulativePrice {
+                    high: high6,
+                    low: low6,
+                } = price_ba_cumulative4;
+
+                (
+                    0i32,
+                    wit_bindgen_rust::rt::as_i32(token_a4),
+                    i64::from(wit_bindgen_rust::rt::as_i32(token_b4)),
+                    i64::from(wit_bindgen_rust::rt::as_i32(commission4)),
+                    wit_bindgen_rust::rt::as_i64(reserve_a4),
+                    wit_bindgen_rust::rt::as_i64(reserve_b4),
+                    wit_bindgen_rust::rt::as_i64(total_liquidity4),
+                    wit_bindgen_rust::rt::as_i64(block_commission_a4),
+                    wit_bindgen_rust::rt::as_i64(block_commission_b4),
+                    pool_type4 as i32,
+                    wit_bindgen_rust::rt::as_i32(amplification4),
+                    wit_bindgen_rust::rt::as_i32(weight_a4),
+                    wit_bindgen_rust::rt::as_i32(weight_b4),
+                    wit_bindgen_rust::rt::as_i64(high5),
+                    wit_bindgen_rust::rt::as_i64(low5),
+                    wit_bindgen_rust::rt::as_i64(high6),
+                    wit_bindgen_rust::rt::as_i64(low6),
+                    wit_bindgen_rust::rt::as_i32(price_height4),
+                    match status4 {
+                        false => 0i32,
+                        true => 1i32,
+                    },
+                )
+            }
+            Err(e) => {
+                let (result7_0, result7_1, result7_2, result7_3, result7_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a8,
+                            reserve_b: reserve_b8,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a8),
+                            wit_bindgen_rust::rt::as_i64(reserve_b8),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price9,
+                            max_price: max_price9,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer10,
+                            fraction: fraction10,
+                        } = price9;
+                        let PoolPrice {
+                            integer: integer11,
+                            fraction: fraction11,
+                        } = max_price9;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer10),
+                            wit_bindgen_rust::rt::as_i64(fraction10),
+                            wit_bindgen_rust::rt::as_i64(integer11),
+                            wit_bindgen_rust::rt::as_i64(fraction11),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a12,
+                            reserve_b: reserve_b12,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a12),
+                            wit_bindgen_rust::rt::as_i64(reserve_b12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount13,
+                            min_output: min_output13,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount13),
+                            wit_bindgen_rust::rt::as_i64(min_output13),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id14,
+                            token_a: token_a14,
+                            token_b: token_b14,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id14)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a14)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b14)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a15,
+                            reserve_b: reserve_b15,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a15),
+                            wit_bindgen_rust::rt::as_i64(reserve_b15),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index16 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index16)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result7_0, result7_1, result7_2, result7_3, result7_4, 0i64, 0i64, 0i64,
+                    0i32, 0i32, 0i32, 0i32, 0i64, 0i64, 0i64, 0i64, 0i32, 0i32,
+                )
+            }
+        };
+        let ptr17 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr17 + 144) as *mut i32) = result3_18;
+        *((ptr17 + 136) as *mut i32) = result3_17;
+        *((ptr17 + 128) as *mut i64) = result3_16;
+        *((ptr17 + 120) as *mut i64) = result3_15;
+        *((ptr17 + 112) as *mut i64) = result3_14;
+        *((ptr17 + 104) as *mut i64) = result3_13;
+        *((ptr17 + 96) as *mut i32) = result3_12;
+        *((ptr17 + 88) as *mut i32) = result3_11;
+        *((ptr17 + 80) as *mut i32) = result3_10;
+        *((ptr17 + 72) as *mut i32) = result3_9;
+        *((ptr17 + 64) as *mut i64) = result3_8;
+        *((ptr17 + 56) as *mut i64) = result3_7;
+        *((ptr17 + 48) as *mut i64) = result3_6;
+        *((ptr17 + 40) as *mut i64) = result3_5;
+        *((ptr17 + 32) as *mut i64) = result3_4;
+        *((ptr17 + 24) as *mut i64) = result3_3;
+        *((ptr17 + 16) as *mut i64) = result3_2;
+        *((ptr17 + 8) as *mut i32) = result3_1;
+        *((ptr17) as *mut i32) = result3_0;
+        ptr17
+    }
+    #[export_name = "update-pool-pair"]
+    unsafe extern "C" fn __wit_bindgen_update_pool_pair(arg0: i32) -> i32 {
+        let result1 = <super::Dex as Dex>::update_pool_pair(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            match i32::from(*((arg0 + 112) as *const u8)) {
+                0 => None,
+                1 => Some(match i32::from(*((arg0 + 113) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                }),
+                _ => panic!("invalid enum discriminant"),
+            },
+            match i32::from(*((arg0 + 116) as *const u8)) {
+                0 => None,
+                1 => Some(*((arg0 + 120) as *const i32) as u32),
+                _ => panic!("invalid enum discriminant"),
+            },
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(128, 8),
+        );
+        let (
+            result2_0,
+            result2_1,
+            result2_2,
+            result2_3,
+            result2_4,
+            result2_5,
+            result2_6,
+            result2_7,
+            result2_8,
+            result2_9,
+            result2_10,
+            result2_11,
+            result2_12,
+            result2_13,
+            result2_14,
+            result2_15,
+            result2_16,
+            result2_17,
+            result2_18,
+        ) = match result1 {
+            Ok(e) => {
+                let PoolPair {
+                    token_a: token_a3,
+                    token_b: token_b3,
+                    commission: commission3,
+                    reserve_a: reserve_a3,
+                    reserve_b: reserve_b3,
+                    total_liquidity: total_liquidity3,
+                    block_commission_a: block_commission_a3,
+                    block_commission_b: block_commission_b3,
+                    pool_type: pool_type3,
+                    amplification: amplification3,
+                    weight_a: weight_a3,
+                    weight_b: weight_b3,
+                    price_ab_cumulative: price_ab_cumulative3,
+                    price_ba_cumulative: price_ba_cumulative3,
+                    price_height: price_height3,
+                    status: status3,
+                } = e;
+                let CumulativePrice {
+                    high: high4,
+                    low: low4,
+                } = price_ab_cumulative3;
+                let CumulativePrice {
+                    high: high5,
+                    low: low5,
+                } = price_ba_cumulative3;
+
+                (
+                    0i32,
+                    wit_bindgen_rust::rt::as_i32(token_a3),
+                    i64::from(wit_bindgen_rust::rt::as_i32(token_b3)),
+                    i64::from(wit_bindgen_rust::rt::as_i32(commission3)),
+                    wit_bindgen_rust::rt::as_i64(reserve_a3),
+                    wit_bindgen_rust::rt::as_i64(reserve_b3),
+                    wit_bindgen_rust::rt::as_i64(total_liquidity3),
+                    wit_bindgen_rust::rt::as_i64(block_commission_a3),
+                    wit_bindgen_rust::rt::as_i64(block_commission_b3),
+                    pool_type3 as i32,
+                    wit_bindgen_rust::rt::as_i32(amplification3),
+                    wit_bindgen_rust::rt::as_i32(weight_a3),
+                    wit_bindgen_rust::rt::as_i32(weight_b3),
+                    wit_bindgen_rust::rt::as_i64(high4),
+                    wit_bindgen_rust::rt::as_i64(low4),
+                    wit_bindgen_rust::rt::as_i64(high5),
+                    wit_bindgen_rust::rt::as_i64(low5),
+                    wit_bindgen_rust::rt::as_i32(price_height3),
+                    match status3 {
+                        false => 0i32,
+                        true => 1i32,
+                    },
+                )
+            }
+            Err(e) => {
+                let (result6_0, result6_1, result6_2, result6_3, result6_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a7,
+                            reserve_b: reserve_b7,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a7),
+                            wit_bindgen_rust::rt::as_i64(reserve_b7),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price8,
+                            max_price: max_price8,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer9,
+                            fraction: fraction9,
+                        } = price8;
+                        let PoolPrice {
+                            integer: integer10,
+                            fraction: fraction10,
+                        } = max_price8;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer9),
+                            wit_bindgen_rust::rt::as_i64(fraction9),
+                            wit_bindgen_rust::rt::as_i64(integer10),
+                            wit_bindgen_rust::rt::as_i64(fraction10),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a11,
+                            reserve_b: reserve_b11,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a11),
+                            wit_bindgen_rust::rt::as_i64(reserve_b11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount12,
+                            min_output: min_output12,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount12),
+                            wit_bindgen_rust::rt::as_i64(min_output12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id13,
+                            token_a: token_a13,
+                            token_b: token_b13,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id13)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a13)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b13)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a14,
+                            reserve_b: reserve_b14,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a14),
+                            wit_bindgen_rust::rt::as_i64(reserve_b14),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index15 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index15)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result6_0, result6_1, result6_2, result6_3, result6_4, 0i64, 0i64, 0i64,
+                    0i32, 0i32, 0i32, 0i32, 0i64, 0i64, 0i64, 0i64, 0i32, 0i32,
+                )
+            }
+        };
+        let ptr16 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr16 + 144) as *mut i32) = result2_18;
+        *((ptr16 + 136) as *mut i32) = result2_17;
+        *((ptr16 + 128) as *mut i64) = result2_16;
+        *((ptr16 + 120) as *mut i64) = result2_15;
+        *((ptr16 + 112) as *mut i64) = result2_14;
+        *((ptr16 + 104) as *mut i64) = result2_13;
+        *((ptr16 + 96) as *mut i32) = result2_12;
+        *((ptr16 + 88) as *mut i32) = result2_11;
+        *((ptr16 + 80) as *mut i32) = result2_10;
+        *((ptr16 + 72) as *mut i32) = result2_9;
+        *((ptr16 + 64) as *mut i64) = result2_8;
+        *((ptr16 + 56) as *mut i64) = result2_7;
+        *((ptr16 + 48) as *mut i64) = result2_6;
+        *((ptr16 + 40) as *mut i64) = result2_5;
+        *((ptr16 + 32) as *mut i64) = result2_4;
+        *((ptr16 + 24) as *mut i64) = result2_3;
+        *((ptr16 + 16) as *mut i64) = result2_2;
+        *((ptr16 + 8) as *mut i32) = result2_1;
+        *((ptr16) as *mut i32) = result2_0;
+        ptr16
+    }
+    #[export_name = "accumulate-price"]
+    unsafe extern "C" fn __wit_bindgen_accumulate_price(arg0: i32) -> i32 {
+        let result1 = <super::Dex as Dex>::accumulate_price(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            *((arg0 + 112) as *const i32) as u32,
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(120, 8),
+        );
+        let (
+            result2_0,
+            result2_1,
+            result2_2,
+            result2_3,
+            result2_4,
+            result2_5,
+            result2_6,
+            result2_7,
+            result2_8,
+            result2_9,
+            result2_10,
+            result2_11,
+            result2_12,
+            result2_13,
+            result2_14,
+            result2_15,
+            result2_16,
+            result2_17,
+            result2_18,
+        ) = match result1 {
+            Ok(e) => {
+                let PoolPair {
+                    token_a: token_a3,
+                    token_b: token_b3,
+                    commission: commission3,
+                    reserve_a: reserve_a3,
+                    reserve_b: reserve_b3,
+                    total_liquidity: total_liquidity3,
+                    block_commission_a: block_commission_a3,
+                    block_commission_b: block_commission_b3,
+                    pool_type: pool_type3,
+                    amplification: amplification3,
+                    weight_a: weight_a3,
+                    weight_b: weight_b3,
+                    price_ab_cumulative: price_ab_cumulative3,
+                    price_ba_cumulative: price_ba_cumulative3,
+                    price_height: price_height3,
+                    status: status3,
+                } = e;
+                let CumulativePrice {
+                    high: high4,
+                    low: low4,
+                } = price_ab_cumulative3;
+                let CumulativePrice {
+                    high: high5,
+                    low: low5,
+                } = price_ba_cumulative3;
+
+                (
+                    0i32,
+                    wit_bindgen_rust::rt::as_i32(token_a3),
+                    i64::from(wit_bindgen_rust::rt::as_i32(token_b3)),
+                    i64::from(wit_bindgen_rust::rt::as_i32(commission3)),
+                    wit_bindgen_rust::rt::as_i64(reserve_a3),
+                    wit_bindgen_rust::rt::as_i64(reserve_b3),
+                    wit_bindgen_rust::rt::as_i64(total_liquidity3),
+                    wit_bindgen_rust::rt::as_i64(block_commission_a3),
+                    wit_bindgen_rust::rt::as_i64(block_commission_b3),
+                    pool_type3 as i32,
+                    wit_bindgen_rust::rt::as_i32(amplification3),
+                    wit_bindgen_rust::rt::as_i32(weight_a3),
+                    wit_bindgen_rust::rt::as_i32(weight_b3),
+                    wit_bindgen_rust::rt::as_i64(high4),
+                    wit_bindgen_rust::rt::as_i64(low4),
+                    wit_bindgen_rust::rt::as_i64(high5),
+                    wit_bindgen_rust::rt::as_i64(low5),
+                    wit_bindgen_rust::rt::as_i32(price_height3),
+                    match status3 {
+                        false => 0i32,
+                        true => 1i32,
+                    },
+                )
+            }
+            Err(e) => {
+                let (result6_0, result6_1, result6_2, result6_3, result6_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a7,
+                            reserve_b: reserve_b7,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a7),
+                            wit_bindgen_rust::rt::as_i64(reserve_b7),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price8,
+                            max_price: max_price8,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer9,
+                            fraction: fraction9,
+                        } = price8;
+                        let PoolPrice {
+                            integer: integer10,
+                            fraction: fraction10,
+                        } = max_price8;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer9),
+                            wit_bindgen_rust::rt::as_i64(fraction9),
+                            wit_bindgen_rust::rt::as_i64(integer10),
+                            wit_bindgen_rust::rt::as_i64(fraction10),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a11,
+                            reserve_b: reserve_b11,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a11),
+                            wit_bindgen_rust::rt::as_i64(reserve_b11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount12,
+                            min_output: min_output12,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount12),
+                            wit_bindgen_rust::rt::as_i64(min_output12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id13,
+                            token_a: token_a13,
+                            token_b: token_b13,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id13)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a13)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b13)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a14,
+                            reserve_b: reserve_b14,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a14),
+                            wit_bindgen_rust::rt::as_i64(reserve_b14),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index15 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index15)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result6_0, result6_1, result6_2, result6_3, result6_4, 0i64, 0i64, 0i64,
+                    0i32, 0i32, 0i32, 0i32, 0i64, 0i64, 0i64, 0i64, 0i32, 0i32,
+                )
+            }
+        };
+        let ptr16 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr16 + 144) as *mut i32) = result2_18;
+        *((ptr16 + 136) as *mut i32) = result2_17;
+        *((ptr16 + 128) as *mut i64) = result2_16;
+        *((ptr16 + 120) as *mut i64) = result2_15;
+        *((ptr16 + 112) as *mut i64) = result2_14;
+        *((ptr16 + 104) as *mut i64) = result2_13;
+        *((ptr16 + 96) as *mut i32) = result2_12;
+        *((ptr16 + 88) as *mut i32) = result2_11;
+        *((ptr16 + 80) as *mut i32) = result2_10;
+        *((ptr16 + 72) as *mut i32) = result2_9;
+        *((ptr16 + 64) as *mut i64) = result2_8;
+        *((ptr16 + 56) as *mut i64) = result2_7;
+        *((ptr16 + 48) as *mut i64) = result2_6;
+        *((ptr16 + 40) as *mut i64) = result2_5;
+        *((ptr16 + 32) as *mut i64) = result2_4;
+        *((ptr16 + 24) as *mut i64) = result2_3;
+        *((ptr16 + 16) as *mut i64) = result2_2;
+        *((ptr16 + 8) as *mut i32) = result2_1;
+        *((ptr16) as *mut i32) = result2_0;
+        ptr16
+    }
+    #[export_name = "twap"]
+    unsafe extern "C" fn __wit_bindgen_twap(arg0: i32) -> i32 {
+        let result1 = <super::Dex as Dex>::twap(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            PoolPair {
+                token_a: *((arg0 + 112) as *const i32) as u32,
+                token_b: *((arg0 + 116) as *const i32) as u32,
+                commission: *((arg0 + 120) as *const i32) as u32,
+                reserve_a: *((arg0 + 128) as *const i64),
+                reserve_b: *((arg0 + 136) as *const i64),
+                total_liquidity: *((arg0 + 144) as *const i64),
+                block_commission_a: *((arg0 + 152) as *const i64),
+                block_commission_b: *((arg0 + 160) as *const i64),
+                pool_type: match i32::from(*((arg0 + 168) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 172) as *const i32) as u32,
+                weight_a: *((arg0 + 176) as *const i32) as u32,
+                weight_b: *((arg0 + 180) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 184) as *const i64) as u64,
+                    low: *((arg0 + 192) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 200) as *const i64) as u64,
+                    low: *((arg0 + 208) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 216) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 220) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(224, 8),
+        );
+        let (result2_0, result2_1, result2_2, result2_3, result2_4, result2_5) = match result1 {
+            Ok(e) => {
+                let TwapResult {
+                    price_ab: price_ab3,
+                    price_ba: price_ba3,
+                } = e;
+                let PoolPrice {
+                    integer: integer4,
+                    fraction: fraction4,
+                } = price_ab3;
+                let PoolPrice {
+                    integer: integer5,
+                    fraction: fraction5,
+                } = price_ba3;
+
+                (
+                    0i32,
+                    wit_bindgen_rust::rt::as_i64(integer4),
+                    wit_bindgen_rust::rt::as_i64(fraction4),
+                    wit_bindgen_rust::rt::as_i64(integer5),
+                    wit_bindgen_rust::rt::as_i64(fraction5),
+                    0i64,
+                )
+            }
+            Err(e) => {
+                let (result6_0, result6_1, result6_2, result6_3, result6_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a7,
+                            reserve_b: reserve_b7,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a7),
+                            wit_bindgen_rust::rt::as_i64(reserve_b7),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price8,
+                            max_price: max_price8,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer9,
+                            fraction: fraction9,
+                        } = price8;
+                        let PoolPrice {
+                            integer: integer10,
+                            fraction: fraction10,
+                        } = max_price8;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer9),
+                            wit_bindgen_rust::rt::as_i64(fraction9),
+                            wit_bindgen_rust::rt::as_i64(integer10),
+                            wit_bindgen_rust::rt::as_i64(fraction10),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a11,
+                            reserve_b: reserve_b11,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a11),
+                            wit_bindgen_rust::rt::as_i64(reserve_b11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount12,
+                            min_output: min_output12,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount12),
+                            wit_bindgen_rust::rt::as_i64(min_output12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id13,
+                            token_a: token_a13,
+                            token_b: token_b13,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id13)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a13)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b13)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a14,
+                            reserve_b: reserve_b14,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a14),
+                            wit_bindgen_rust::rt::as_i64(reserve_b14),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index15 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index15)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32,
+                    i64::from(result6_0),
+                    result6_1,
+                    result6_2,
+                    result6_3,
+                    result6_4,
+                )
+            }
+        };
+        let ptr16 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr16 + 40) as *mut i64) = result2_5;
+        *((ptr16 + 32) as *mut i64) = result2_4;
+        *((ptr16 + 24) as *mut i64) = result2_3;
+        *((ptr16 + 16) as *mut i64) = result2_2;
+        *((ptr16 + 8) as *mut i64) = result2_1;
+        *((ptr16) as *mut i32) = result2_0;
+        ptr16
+    }
+    #[export_name = "distribute"]
+    unsafe extern "C" fn __wit_bindgen_distribute(arg0: i32) -> i32 {
+        let result3 = <super::Dex as Dex>::distribute(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            {
+                let ptr1 = *((arg0 + 112) as *const i32);
+                let len1 = *((arg0 + 116) as *const i32);
+                {
+                    let len2 = len1 as usize;
+                    Vec::from_raw_parts(ptr1 as *mut _, len2, len2)
+                }
+            },
+            *((arg0 + 120) as *const i32) as u32,
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(128, 8),
+        );
+        let (result4_0, result4_1, result4_2, result4_3, result4_4, result4_5) = match result3 {
+            Ok(e) => {
+                let vec5 = (e).into_boxed_slice();
+                let ptr5 = vec5.as_ptr() as i32;
+                let len5 = vec5.len() as i32;
+                core::mem::forget(vec5);
+
+                (0i32, ptr5, i64::from(len5), 0i64, 0i64, 0i64)
+            }
+            Err(e) => {
+                let (result6_0, result6_1, result6_2, result6_3, result6_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a7,
+                            reserve_b: reserve_b7,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a7),
+                            wit_bindgen_rust::rt::as_i64(reserve_b7),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price8,
+                            max_price: max_price8,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer9,
+                            fraction: fraction9,
+                        } = price8;
+                        let PoolPrice {
+                            integer: integer10,
+                            fraction: fraction10,
+                        } = max_price8;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer9),
+                            wit_bindgen_rust::rt::as_i64(fraction9),
+                            wit_bindgen_rust::rt::as_i64(integer10),
+                            wit_bindgen_rust::rt::as_i64(fraction10),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a11,
+                            reserve_b: reserve_b11,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a11),
+                            wit_bindgen_rust::rt::as_i64(reserve_b11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount12,
+                            min_output: min_output12,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount12),
+                            wit_bindgen_rust::rt::as_i64(min_output12),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id13,
+                            token_a: token_a13,
+                            token_b: token_b13,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id13)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a13)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b13)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a14,
+                            reserve_b: reserve_b14,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a14),
+                            wit_bindgen_rust::rt::as_i64(reserve_b14),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index15 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index15)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (1i32, result6_0, result6_1, result6_2, result6_3, result6_4)
+            }
+        };
+        let ptr16 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr16 + 40) as *mut i64) = result4_5;
+        *((ptr16 + 32) as *mut i64) = result4_4;
+        *((ptr16 + 24) as *mut i64) = result4_3;
+        *((ptr16 + 16) as *mut i64) = result4_2;
+        *((ptr16 + 8) as *mut i32) = result4_1;
+        *((ptr16) as *mut i32) = result4_0;
+        ptr16
+    }
+    #[export_name = "claim"]
+    unsafe extern "C" fn __wit_bindgen_claim(arg0: i32) -> i32 {
+        let result5 = <super::Dex as Dex>::claim(
+            PoolPair {
+                token_a: *((arg0) as *const i32) as u32,
+                token_b: *((arg0 + 4) as *const i32) as u32,
+                commission: *((arg0 + 8) as *const i32) as u32,
+                reserve_a: *((arg0 + 16) as *const i64),
+                reserve_b: *((arg0 + 24) as *const i64),
+                total_liquidity: *((arg0 + 32) as *const i64),
+                block_commission_a: *((arg0 + 40) as *const i64),
+                block_commission_b: *((arg0 + 48) as *const i64),
+                pool_type: match i32::from(*((arg0 + 56) as *const u8)) {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => panic!("invalid enum discriminant"),
+                },
+                amplification: *((arg0 + 60) as *const i32) as u32,
+                weight_a: *((arg0 + 64) as *const i32) as u32,
+                weight_b: *((arg0 + 68) as *const i32) as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: *((arg0 + 72) as *const i64) as u64,
+                    low: *((arg0 + 80) as *const i64) as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: *((arg0 + 88) as *const i64) as u64,
+                    low: *((arg0 + 96) as *const i64) as u64,
+                },
+                price_height: *((arg0 + 104) as *const i32) as u32,
+                status: match i32::from(*((arg0 + 108) as *const u8)) {
+                    0 => false,
+                    1 => true,
+                    _ => panic!("invalid enum discriminant"),
+                },
+            },
+            {
+                let ptr1 = *((arg0 + 112) as *const i32);
+                let len1 = *((arg0 + 116) as *const i32);
+                {
+                    let len2 = len1 as usize;
+                    Vec::from_raw_parts(ptr1 as *mut _, len2, len2)
+                }
+            },
+            *((arg0 + 120) as *const i64),
+            {
+                let ptr3 = *((arg0 + 128) as *const i32);
+                let len3 = *((arg0 + 132) as *const i32);
+                {
+                    let len4 = len3 as usize;
+                    Vec::from_raw_parts(ptr3 as *mut _, len4, len4)
+                }
+            },
+        );
+        std::alloc::dealloc(
+            arg0 as *mut u8,
+            core::alloc::Layout::from_size_align_unchecked(136, 8),
+        );
+        let (result6_0, result6_1, result6_2, result6_3, result6_4, result6_5) = match result5 {
+            Ok(e) => {
+                let ClaimResult {
+                    amounts: amounts7,
+                    paid: paid7,
+                } = e;
+                let vec8 = (amounts7).into_boxed_slice();
+                let ptr8 = vec8.as_ptr() as i32;
+                let len8 = vec8.len() as i32;
+                core::mem::forget(vec8);
+                let vec9 = (paid7).into_boxed_slice();
+                let ptr9 = vec9.as_ptr() as i32;
+                let len9 = vec9.len() as i32;
+                core::mem::forget(vec9);
+
+                (
+                    0i32,
+                    ptr8,
+                    i64::from(len8),
+                    i64::from(ptr9),
+                    i64::from(len9),
+                    0i64,
+                )
+            }
+            Err(e) => {
+                let (result10_0, result10_1, result10_2, result10_3, result10_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a11,
+                            reserve_b: reserve_b11,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a11),
+                            wit_bindgen_rust::rt::as_i64(reserve_b11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price12,
+                            max_price: max_price12,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer13,
+                            fraction: fraction13,
+                        } = price12;
+                        let PoolPrice {
+                            integer: integer14,
+                            fraction: fraction14,
+                        } = max_price12;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer13),
+                            wit_bindgen_rust::rt::as_i64(fraction13),
+                            wit_bindgen_rust::rt::as_i64(integer14),
+                            wit_bindgen_rust::rt::as_i64(fraction14),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a15,
+                            reserve_b: reserve_b15,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a15),
+                            wit_bindgen_rust::rt::as_i64(reserve_b15),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount16,
+                            min_output: min_output16,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount16),
+                            wit_bindgen_rust::rt::as_i64(min_output16),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id17,
+                            token_a: token_a17,
+                            token_b: token_b17,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id17)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a17)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b17)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a18,
+                            reserve_b: reserve_b18,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a18),
+                            wit_bindgen_rust::rt::as_i64(reserve_b18),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index19 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index19)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32, result10_0, result10_1, result10_2, result10_3, result10_4,
+                )
+            }
+        };
+        let ptr20 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr20 + 40) as *mut i64) = result6_5;
+        *((ptr20 + 32) as *mut i64) = result6_4;
+        *((ptr20 + 24) as *mut i64) = result6_3;
+        *((ptr20 + 16) as *mut i64) = result6_2;
+        *((ptr20 + 8) as *mut i32) = result6_1;
+        *((ptr20) as *mut i32) = result6_0;
+        ptr20
+    }
+    #[export_name = "serialize-pools"]
+    unsafe extern "C" fn __wit_bindgen_serialize_pools(arg0: i32, arg1: i32) -> i32 {
+        let result2 = <super::Dex as Dex>::serialize_pools({
+            let base1 = arg0;
+            let len1 = arg1;
+            let mut result1 = Vec::with_capacity(len1 as usize);
+            for i in 0..len1 {
+                let base = base1 + i * 120;
+                result1.push(PoolEntry {
+                    pool_id: *((base) as *const i32) as u32,
+                    pool_pair: PoolPair {
+                        token_a: *((base + 8) as *const i32) as u32,
+                        token_b: *((base + 12) as *const i32) as u32,
+                        commission: *((base + 16) as *const i32) as u32,
+                        reserve_a: *((base + 24) as *const i64),
+                        reserve_b: *((base + 32) as *const i64),
+                        total_liquidity: *((base + 40) as *const i64),
+                        block_commission_a: *((base + 48) as *const i64),
+                        block_commission_b: *((base + 56) as *const i64),
+                        pool_type: match i32::from(*((base + 64) as *const u8)) {
+                            0 => PoolType::ConstantProduct,
+                            1 => PoolType::StableSwap,
+                            2 => PoolType::Weighted,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                        amplification: *((base + 68) as *const i32) as u32,
+                        weight_a: *((base + 72) as *const i32) as u32,
+                        weight_b: *((base + 76) as *const i32) as u32,
+                        price_ab_cumulative: CumulativePrice {
+                            high: *((base + 80) as *const i64) as u64,
+                            low: *((base + 88) as *const i64) as u64,
+                        },
+                        price_ba_cumulative: CumulativePrice {
+                            high: *((base + 96) as *const i64) as u64,
+                            low: *((base + 104) as *const i64) as u64,
+                        },
+                        price_height: *((base + 112) as *const i32) as u32,
+                        status: match i32::from(*((base + 116) as *const u8)) {
+                            0 => false,
+                            1 => true,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                    },
+                });
+            }
+            if len1 != 0 {
+                std::alloc::dealloc(
+                    base1 as *mut u8,
+                    core::alloc::Layout::from_size_align_unchecked((len1 as usize) * 120, 8),
+                );
+            }
+            result1
+        });
+        let (result3_0, result3_1, result3_2, result3_3, result3_4, result3_5) = match result2 {
+            Ok(e) => {
+                let vec4 = (e).into_boxed_slice();
+                let ptr4 = vec4.as_ptr() as i32;
+                let len4 = vec4.len() as i32;
+                core::mem::forget(vec4);
+
+                (0i32, ptr4, i64::from(len4), 0i64, 0i64, 0i64)
+            }
+            Err(e) => {
+                let (result5_0, result5_1, result5_2, result5_3, result5_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a6,
+                            reserve_b: reserve_b6,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a6),
+                            wit_bindgen_rust::rt::as_i64(reserve_b6),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price7,
+                            max_price: max_price7,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer8,
+                            fraction: fraction8,
+                        } = price7;
+                        let PoolPrice {
+                            integer: integer9,
+                            fraction: fraction9,
+                        } = max_price7;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer8),
+                            wit_bindgen_rust::rt::as_i64(fraction8),
+                            wit_bindgen_rust::rt::as_i64(integer9),
+                            wit_bindgen_rust::rt::as_i64(fraction9),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a10,
+                            reserve_b: reserve_b10,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a10),
+                            wit_bindgen_rust::rt::as_i64(reserve_b10),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount11,
+                            min_output: min_output11,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount11),
+                            wit_bindgen_rust::rt::as_i64(min_output11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id12,
+                            token_a: token_a12,
+                            token_b: token_b12,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id12)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a12)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b12)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a13,
+                            reserve_b: reserve_b13,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a13),
+                            wit_bindgen_rust::rt::as_i64(reserve_b13),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index14 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index14)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (1i32, result5_0, result5_1, result5_2, result5_3, result5_4)
+            }
+        };
+        let ptr15 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr15 + 40) as *mut i64) = result3_5;
+        *((ptr15 + 32) as *mut i64) = result3_4;
+        *((ptr15 + 24) as *mut i64) = result3_3;
+        *((ptr15 + 16) as *mut i64) = result3_2;
+        *((ptr15 + 8) as *mut i32) = result3_1;
+        *((ptr15) as *mut i32) = result3_0;
+        ptr15
+    }
+    #[export_name = "deserialize-pools"]
+    unsafe extern "C" fn __wit_bindgen_deserialize_pools(arg0: i32, arg1: i32) -> i32 {
+        let result2 = <super::Dex as Dex>::deserialize_pools({
+            let len1 = arg1 as usize;
+            Vec::from_raw_parts(arg0 as *mut _, len1, len1)
+        });
+        let (result3_0, result3_1, result3_2, result3_3, result3_4, result3_5) = match result2 {
+            Ok(e) => {
+                let vec4 = e;
+                let len4 = vec4.len() as i32;
+                let layout4 = core::alloc::Layout::from_size_align_unchecked(vec4.len() * 120, 8);
+                let result4 = if layout4.size() != 0 {
+                    let ptr = std::alloc::alloc(layout4);
+                    if ptr.is_null() {
+                        std::alloc::handle_alloc_error(layout4);
+                    }
+                    ptr
+                } else {
+                    std::ptr::null_mut()
+                };
+                for (i, e) in vec4.into_iter().enumerate() {
+                    let base = result4 as i32 + (i as i32) * 120;
+                    {
+                        let PoolEntry {
+                            pool_id: pool_id5,
+                            pool_pair: pool_pair5,
+                        } = e;
+                        *((base) as *mut i32) = wit_bindgen_rust::rt::as_i32(pool_id5);
+                        let PoolPair {
+                            token_a: token_a6,
+                            token_b: token_b6,
+                            commission: commission6,
+                            reserve_a: reserve_a6,
+                            reserve_b: reserve_b6,
+                            total_liquidity: total_liquidity6,
+                            block_commission_a: block_commission_a6,
+                            block_commission_b: block_commission_b6,
+                            pool_type: pool_type6,
+                            amplification: amplification6,
+                            weight_a: weight_a6,
+                            weight_b: weight_b6,
+                            price_ab_cumulative: price_ab_cumulative6,
+                            price_ba_cumulative: price_ba_cumulative6,
+                            price_height: price_height6,
+                            status: status6,
+                        } = pool_pair5;
+                        *((base + 8) as *mut i32) = wit_bindgen_rust::rt::as_i32(token_a6);
+                        *((base + 12) as *mut i32) = wit_bindgen_rust::rt::as_i32(token_b6);
+                        *((base + 16) as *mut i32) = wit_bindgen_rust::rt::as_i32(commission6);
+                        *((base + 24) as *mut i64) = wit_bindgen_rust::rt::as_i64(reserve_a6);
+                        *((base + 32) as *mut i64) = wit_bindgen_rust::rt::as_i64(reserve_b6);
+                        *((base + 40) as *mut i64) = wit_bindgen_rust::rt::as_i64(total_liquidity6);
+                        *((base + 48) as *mut i64) =
+                            wit_bindgen_rust::rt::as_i64(block_commission_a6);
+                        *((base + 56) as *mut i64) =
+                            wit_bindgen_rust::rt::as_i64(block_commission_b6);
+                        *((base + 64) as *mut u8) = (pool_type6 as i32) as u8;
+                        *((base + 68) as *mut i32) = wit_bindgen_rust::rt::as_i32(amplification6);
+                        *((base + 72) as *mut i32) = wit_bindgen_rust::rt::as_i32(weight_a6);
+                        *((base + 76) as *mut i32) = wit_bindgen_rust::rt::as_i32(weight_b6);
+                        let CumulativePrice {
+                            high: high7,
+                            low: low7,
+                        } = price_ab_cumulative6;
+                        *((base + 80) as *mut i64) = wit_bindgen_rust::rt::as_i64(high7);
+                        *((base + 88) as *mut i64) = wit_bindgen_rust::rt::as_i64(low7);
+                        let CumulativePrice {
+                            high: high8,
+                            low: low8,
+                        } = price_ba_cumulative6;
+                        *((base + 96) as *mut i64) = wit_bindgen_rust::rt::as_i64(high8);
+                        *((base + 104) as *mut i64) = wit_bindgen_rust::rt::as_i64(low8);
+                        *((base + 112) as *mut i32) = wit_bindgen_rust::rt::as_i32(price_height6);
+                        *((base + 116) as *mut u8) = match status6 {
+                            false => 0u8,
+                            true => 1u8,
+                        };
+                    }
+                }
+
+                (0i32, result4 as i32, i64::from(len4), 0i64, 0i64, 0i64)
+            }
+            Err(e) => {
+                let (result9_0, result9_1, result9_2, result9_3, result9_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a10,
+                            reserve_b: reserve_b10,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a10),
+                            wit_bindgen_rust::rt::as_i64(reserve_b10),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price11,
+                            max_price: max_price11,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer12,
+                            fraction: fraction12,
+                        } = price11;
+                        let PoolPrice {
+                            integer: integer13,
+                            fraction: fraction13,
+                        } = max_price11;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer12),
+                            wit_bindgen_rust::rt::as_i64(fraction12),
+                            wit_bindgen_rust::rt::as_i64(integer13),
+                            wit_bindgen_rust::rt::as_i64(fraction13),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a14,
+                            reserve_b: reserve_b14,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a14),
+                            wit_bindgen_rust::rt::as_i64(reserve_b14),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount15,
+                            min_output: min_output15,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount15),
+                            wit_bindgen_rust::rt::as_i64(min_output15),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id16,
+                            token_a: token_a16,
+                            token_b: token_b16,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id16)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a16)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b16)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a17,
+                            reserve_b: reserve_b17,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a17),
+                            wit_bindgen_rust::rt::as_i64(reserve_b17),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index18 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index18)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (1i32, result9_0, result9_1, result9_2, result9_3, result9_4)
+            }
+        };
+        let ptr19 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr19 + 40) as *mut i64) = result3_5;
+        *((ptr19 + 32) as *mut i64) = result3_4;
+        *((ptr19 + 24) as *mut i64) = result3_3;
+        *((ptr19 + 16) as *mut i64) = result3_2;
+        *((ptr19 + 8) as *mut i32) = result3_1;
+        *((ptr19) as *mut i32) = result3_0;
+        ptr19
+    }
+    #[export_name = "pools-hash"]
+    unsafe extern "C" fn __wit_bindgen_pools_hash(arg0: i32, arg1: i32) -> i32 {
+        let result2 = <super::Dex as Dex>::pools_hash({
+            let base1 = arg0;
+            let len1 = arg1;
+            let mut result1 = Vec::with_capacity(len1 as usize);
+            for i in 0..len1 {
+                let base = base1 + i * 120;
+                result1.push(PoolEntry {
+                    pool_id: *((base) as *const i32) as u32,
+                    pool_pair: PoolPair {
+                        token_a: *((base + 8) as *const i32) as u32,
+                        token_b: *((base + 12) as *const i32) as u32,
+                        commission: *((base + 16) as *const i32) as u32,
+                        reserve_a: *((base + 24) as *const i64),
+                        reserve_b: *((base + 32) as *const i64),
+                        total_liquidity: *((base + 40) as *const i64),
+                        block_commission_a: *((base + 48) as *const i64),
+                        block_commission_b: *((base + 56) as *const i64),
+                        pool_type: match i32::from(*((base + 64) as *const u8)) {
+                            0 => PoolType::ConstantProduct,
+                            1 => PoolType::StableSwap,
+                            2 => PoolType::Weighted,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                        amplification: *((base + 68) as *const i32) as u32,
+                        weight_a: *((base + 72) as *const i32) as u32,
+                        weight_b: *((base + 76) as *const i32) as u32,
+                        price_ab_cumulative: CumulativePrice {
+                            high: *((base + 80) as *const i64) as u64,
+                            low: *((base + 88) as *const i64) as u64,
+                        },
+                        price_ba_cumulative: CumulativePrice {
+                            high: *((base + 96) as *const i64) as u64,
+                            low: *((base + 104) as *const i64) as u64,
+                        },
+                        price_height: *((base + 112) as *const i32) as u32,
+                        status: match i32::from(*((base + 116) as *const u8)) {
+                            0 => false,
+                            1 => true,
+                            _ => panic!("invalid enum discriminant"),
+                        },
+                    },
+                });
+            }
+            if len1 != 0 {
+                std::alloc::dealloc(
+                    base1 as *mut u8,
+                    core::alloc::Layout::from_size_align_unchecked((len1 as usize) * 120, 8),
+                );
+            }
+            result1
+        });
+        let (result3_0, result3_1, result3_2, result3_3, result3_4, result3_5) = match result2 {
+            Ok(e) => {
+                let vec4 = (e).into_boxed_slice();
+                let ptr4 = vec4.as_ptr() as i32;
+                let len4 = vec4.len() as i32;
+                core::mem::forget(vec4);
+
+                (0i32, ptr4, i64::from(len4), 0i64, 0i64, 0i64)
+            }
+            Err(e) => {
+                let (result5_0, result5_1, result5_2, result5_3, result5_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a6,
+                            reserve_b: reserve_b6,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a6),
+                            wit_bindgen_rust::rt::as_i64(reserve_b6),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price7,
+                            max_price: max_price7,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer8,
+                            fraction: fraction8,
+                        } = price7;
+                        let PoolPrice {
+                            integer: integer9,
+                            fraction: fraction9,
+                        } = max_price7;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer8),
+                            wit_bindgen_rust::rt::as_i64(fraction8),
+                            wit_bindgen_rust::rt::as_i64(integer9),
+                            wit_bindgen_rust::rt::as_i64(fraction9),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a10,
+                            reserve_b: reserve_b10,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a10),
+                            wit_bindgen_rust::rt::as_i64(reserve_b10),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount11,
+                            min_output: min_output11,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount11),
+                            wit_bindgen_rust::rt::as_i64(min_output11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id12,
+                            token_a: token_a12,
+                            token_b: token_b12,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id12)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a12)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b12)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a13,
+                            reserve_b: reserve_b13,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a13),
+                            wit_bindgen_rust::rt::as_i64(reserve_b13),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index14 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index14)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (1i32, result5_0, result5_1, result5_2, result5_3, result5_4)
+            }
+        };
+        let ptr15 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr15 + 40) as *mut i64) = result3_5;
+        *((ptr15 + 32) as *mut i64) = result3_4;
+        *((ptr15 + 24) as *mut i64) = result3_3;
+        *((ptr15 + 16) as *mut i64) = result3_2;
+        *((ptr15 + 8) as *mut i32) = result3_1;
+        *((ptr15) as *mut i32) = result3_0;
+        ptr15
+    }
+    #[export_name = "dex-stabilization-fee"]
+    unsafe extern "C" fn __wit_bindgen_dex_stabilization_fee(
+        arg0: i64,
+        arg1: i64,
+        arg2: i64,
+    ) -> i32 {
+        let result1 = <super::Dex as Dex>::dex_stabilization_fee(arg0, arg1, arg2);
+        let (result2_0, result2_1, result2_2, result2_3, result2_4, result2_5) = match result1 {
+            Ok(e) => (
+                0i32,
+                wit_bindgen_rust::rt::as_i64(e),
+                0i64,
+                0i64,
+                0i64,
+                0i64,
+            ),
+            Err(e) => {
+                let (result3_0, result3_1, result3_2, result3_3, result3_4) = match e {
+                    Error::Runtime => (0i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::NotFound => (1i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvalidInput => (2i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::LackOfLiquidity(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a4,
+                            reserve_b: reserve_b4,
+                        } = e;
+
+                        (
+                            3i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a4),
+                            wit_bindgen_rust::rt::as_i64(reserve_b4),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PriceHigherThanIndex(e) => {
+                        let PriceLimit {
+                            price: price5,
+                            max_price: max_price5,
+                        } = e;
+                        let PoolPrice {
+                            integer: integer6,
+                            fraction: fraction6,
+                        } = price5;
+                        let PoolPrice {
+                            integer: integer7,
+                            fraction: fraction7,
+                        } = max_price5;
+
+                        (
+                            4i32,
+                            wit_bindgen_rust::rt::as_i64(integer6),
+                            wit_bindgen_rust::rt::as_i64(fraction6),
+                            wit_bindgen_rust::rt::as_i64(integer7),
+                            wit_bindgen_rust::rt::as_i64(fraction7),
+                        )
+                    }
+                    Error::PoolReserveOverflow(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a8,
+                            reserve_b: reserve_b8,
+                        } = e;
+
+                        (
+                            5i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a8),
+                            wit_bindgen_rust::rt::as_i64(reserve_b8),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::OutputBelowMinimum(e) => {
+                        let OutputLimit {
+                            amount: amount9,
+                            min_output: min_output9,
+                        } = e;
+
+                        (
+                            6i32,
+                            wit_bindgen_rust::rt::as_i64(amount9),
+                            wit_bindgen_rust::rt::as_i64(min_output9),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::TokenMismatch(e) => {
+                        let InputTokenMismatch {
+                            token_id: token_id10,
+                            token_a: token_a10,
+                            token_b: token_b10,
+                        } = e;
+
+                        (
+                            7i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_id10)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_a10)),
+                            i64::from(wit_bindgen_rust::rt::as_i32(token_b10)),
+                            0i64,
+                        )
+                    }
+                    Error::ArithmeticOverflow => (8i32, 0i64, 0i64, 0i64, 0i64),
+                    Error::InvariantViolation(e) => {
+                        let Reserves {
+                            reserve_a: reserve_a11,
+                            reserve_b: reserve_b11,
+                        } = e;
+
+                        (
+                            9i32,
+                            wit_bindgen_rust::rt::as_i64(reserve_a11),
+                            wit_bindgen_rust::rt::as_i64(reserve_b11),
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::InvalidPoolConfig(e) => (10i32, i64::from(e as i32), 0i64, 0i64, 0i64),
+                    Error::PoolNotFound(e) => {
+                        let PoolIndex { index: index12 } = e;
+
+                        (
+                            11i32,
+                            i64::from(wit_bindgen_rust::rt::as_i32(index12)),
+                            0i64,
+                            0i64,
+                            0i64,
+                        )
+                    }
+                    Error::PoolDisabled => (12i32, 0i64, 0i64, 0i64, 0i64),
+                };
+
+                (
+                    1i32,
+                    i64::from(result3_0),
+                    result3_1,
+                    result3_2,
+                    result3_3,
+                    result3_4,
+                )
+            }
+        };
+        let ptr13 = RET_AREA.as_mut_ptr() as i32;
+        *((ptr13 + 40) as *mut i64) = result2_5;
+        *((ptr13 + 32) as *mut i64) = result2_4;
+        *((ptr13 + 24) as *mut i64) = result2_3;
+        *((ptr13 + 16) as *mut i64) = result2_2;
+        *((ptr13 + 8) as *mut i64) = result2_1;
+        *((ptr13) as *mut i32) = result2_0;
+        ptr13
+    }
+    pub trait Dex {
+        fn swap(
+            poolpair: PoolPair,
+            token_in: TokenAmount,
+            max_price: PoolPrice,
+            min_output: Option<i64>,
+            dex_fee: DexFee,
+            post_bayfront_gardens: bool,
+        ) -> Result<SwapResult, Error>;
+        fn swap_trace(
+            poolpair: PoolPair,
+            token_in: TokenAmount,
+            max_price: PoolPrice,
+            min_output: Option<i64>,
+            dex_fee: DexFee,
+            post_bayfront_gardens: bool,
+        ) -> Result<SwapTraceResult, Error>;
+        fn swap_batch(
+            poolpairs: Vec<PoolPair>,
+            operations: Vec<SwapOperation>,
+            post_bayfront_gardens: bool,
+        ) -> Result<SwapBatchResult, Error>;
+        fn composite_swap(
+            poolpairs: Vec<PoolPair>,
+            token_in: TokenAmount,
+            max_price: PoolPrice,
+            dex_fees: Vec<DexFee>,
+            post_bayfront_gardens: bool,
+        ) -> Result<CompositeSwapResult, Error>;
+        fn best_path(
+            poolpairs: Vec<PoolPair>,
+            token_in: TokenAmount,
+            token_to: DctId,
+            max_hops: u32,
+            max_price: PoolPrice,
+            dex_fees: Vec<DexFee>,
+            post_bayfront_gardens: bool,
+        ) -> Result<BestPathResult, Error>;
+        fn quote(
+            poolpair: PoolPair,
+            token_in: TokenAmount,
+            dex_fee: DexFee,
+            post_bayfront_gardens: bool,
+        ) -> Result<i64, Error>;
+        fn add_liquidity(
+            poolpair: PoolPair,
+            amount_a: i64,
+            amount_b: i64,
+        ) -> Result<LiquidityResult, Error>;
+        fn remove_liquidity(poolpair: PoolPair, liquidity: i64) -> Result<LiquidityResult, Error>;
+        fn create_pool_pair(
+            config: PoolPairConfig,
+            owner_address: String,
+        ) -> Result<PoolPair, Error>;
+        fn update_pool_pair(
+            poolpair: PoolPair,
+            status: Option<bool>,
+            commission: Option<DctId>,
+        ) -> Result<PoolPair, Error>;
+        fn accumulate_price(poolpair: PoolPair, height: u32) -> Result<PoolPair, Error>;
+        fn twap(start: PoolPair, end: PoolPair) -> Result<TwapResult, Error>;
+        fn distribute(
+            poolpair: PoolPair,
+            rewards: Vec<RewardPool>,
+            height: u32,
+        ) -> Result<Vec<RewardPool>, Error>;
+        fn claim(
+            poolpair: PoolPair,
+            rewards: Vec<RewardPool>,
+            liquidity: i64,
+            paid: Vec<RewardPerShare>,
+        ) -> Result<ClaimResult, Error>;
+        fn serialize_pools(pools: Vec<PoolEntry>) -> Result<Vec<u8>, Error>;
+        fn deserialize_pools(bytes: Vec<u8>) -> Result<Vec<PoolEntry>, Error>;
+        fn pools_hash(pools: Vec<PoolEntry>) -> Result<Vec<u8>, Error>;
+        fn dex_stabilization_fee(
+            algo_dusd: i64,
+            dusd_supply: i64,
+            coefficient: i64,
+        ) -> Result<i64, Error>;
+    }
+    static mut RET_AREA: [i64; 26] = [0; 26];
+}
+
+impl Add for TokenAmount {
+    type Output = TokenAmount;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        TokenAmount {
+            token_id: self.token_id,
+            amount: self.amount.saturating_add(rhs.amount),
+        }
+    }
+}
+
+impl Sub for TokenAmount {
+    type Output = TokenAmount;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        TokenAmount {
+            token_id: self.token_id,
+            amount: self.amount.saturating_sub(rhs.amount),
+        }
+    }
+}
+
+impl PartialEq for TokenAmount {
+    fn eq(&self, other: &Self) -> bool {
+        self.token_id.eq(&other.token_id)
+    }
+}
+
+impl From<u128> for CumulativePrice {
+    fn from(value: u128) -> Self {
+        CumulativePrice {
+            high: (value >> 64) as u64,
+            low: value as u64,
+        }
+    }
+}
+
+impl From<CumulativePrice> for u128 {
+    fn from(value: CumulativePrice) -> Self {
+        (u128::from(value.high) << 64) | u128::from(value.low)
+    }
+}
+
+struct Dex {}
+
+/// Steps and fee events recorded by a traced swap.
+#[derive(Default)]
+struct SwapTrace {
+    steps: Vec<SwapStep>,
+    events: Vec<SwapEvent>,
+}
+
+impl dex::Dex for Dex {
+    fn swap(
+        poolpair: PoolPair,
+        token_in: TokenAmount,
+        max_price: PoolPrice,
+        min_output: Option<i64>,
+        dex_fee: DexFee,
+        post_bayfront_gardens: bool,
+    ) -> Result<SwapResult, Error> {
+        Dex::swap_traced(
+            poolpair,
+            token_in,
+            max_price,
+            min_output,
+            dex_fee,
+            post_bayfront_gardens,
+            None,
+        )
+    }
+
+    fn swap_trace(
+        poolpair: PoolPair,
+        token_in: TokenAmount,
+        max_price: PoolPrice,
+        min_output: Option<i64>,
+        dex_fee: DexFee,
+        post_bayfront_gardens: bool,
+    ) -> Result<SwapTraceResult, Error> {
+        let mut trace = SwapTrace::default();
+        let swap_result = Dex::swap_traced(
+            poolpair,
+            token_in,
+            max_price,
+            min_output,
+            dex_fee,
+            post_bayfront_gardens,
+            Some(&mut trace),
+        )?;
+        Ok(SwapTraceResult {
+            swap_result,
+            steps: trace.steps,
+            events: trace.events,
+        })
+    }
+
+    fn swap_batch(
+        poolpairs: Vec<PoolPair>,
+        operations: Vec<SwapOperation>,
+        post_bayfront_gardens: bool,
+    ) -> Result<SwapBatchResult, Error> {
+        let mut poolpairs = poolpairs;
+
+        // Each operation sees the pools as left by the ones before it; a failed operation leaves
+        // them untouched and does not stop the rest of the batch
+        let results = operations
+            .into_iter()
+            .map(|operation| {
+                let poolpair =
+                    poolpairs
+                        .get_mut(operation.pool_index as usize)
+                        .ok_or(Error::PoolNotFound(PoolIndex {
+                            index: operation.pool_index,
+                        }))?;
+                let result = Dex::swap_traced(
+                    *poolpair,
+                    operation.token_in,
+                    operation.max_price,
+                    None,
+                    operation.dex_fee,
+                    post_bayfront_gardens,
+                    None,
+                )?;
+                *poolpair = result.pool_pair;
+                Ok(SwapOutput {
+                    amount: result.slop_swap_result,
+                    dex_fee_in: result.dex_fee_in,
+                    dex_fee_out: result.dex_fee_out,
+                })
+            })
+            .collect();
+
+        Ok(SwapBatchResult {
+            pool_pairs: poolpairs,
+            results,
+        })
+    }
+
+    fn composite_swap(
+        poolpairs: Vec<PoolPair>,
+        token_in: TokenAmount,
+        max_price: PoolPrice,
+        dex_fees: Vec<DexFee>,
+        post_bayfront_gardens: bool,
+    ) -> Result<CompositeSwapResult, Error> {
+        if poolpairs.is_empty() || dex_fees.len() != poolpairs.len() {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut poolpairs = poolpairs;
+        let result = Dex::swap_path(
+            &mut poolpairs,
+            token_in,
+            max_price,
+            &dex_fees,
+            post_bayfront_gardens,
+        )?;
+
+        Ok(CompositeSwapResult {
+            pool_pairs: poolpairs,
+            slop_swap_result: result,
+        })
+    }
+
+    fn best_path(
+        poolpairs: Vec<PoolPair>,
+        token_in: TokenAmount,
+        token_to: DctId,
+        max_hops: u32,
+        max_price: PoolPrice,
+        dex_fees: Vec<DexFee>,
+        post_bayfront_gardens: bool,
+    ) -> Result<BestPathResult, Error> {
+        if max_hops == 0 || token_in.token_id == token_to || dex_fees.len() != poolpairs.len() {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut paths = Vec::new();
+        Dex::find_paths(
+            &poolpairs,
+            token_in.token_id,
+            token_to,
+            max_hops as usize,
+            &mut vec![],
+            &mut vec![token_in.token_id],
+            &mut paths,
+        );
+
+        // Paths that fail to swap (lack of liquidity, price limits, ...) are not viable routes
+        let mut routes = paths
+            .into_iter()
+            .filter_map(|path| {
+                let mut hops = path
+                    .iter()
+                    .map(|&i| poolpairs[i as usize])
+                    .collect::<Vec<_>>();
+                let fees = path
+                    .iter()
+                    .map(|&i| dex_fees[i as usize])
+                    .collect::<Vec<_>>();
+                Dex::swap_path(&mut hops, token_in, max_price, &fees, post_bayfront_gardens)
+                    .ok()
+                    .map(|slop_swap_result| SwapRoute {
+                        path,
+                        slop_swap_result,
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        // Stable sort keeps discovery order between routes of equal output, so the choice is
+        // deterministic for a given pool list
+        routes.sort_by(|a, b| b.slop_swap_result.cmp(&a.slop_swap_result));
+        if routes.is_empty() {
+            return Err(Error::NotFound);
+        }
+        let best = routes.remove(0);
+
+        Ok(BestPathResult {
+            best,
+            alternatives: routes,
+        })
+    }
+
+    fn quote(
+        poolpair: PoolPair,
+        token_in: TokenAmount,
+        dex_fee: DexFee,
+        post_bayfront_gardens: bool,
+    ) -> Result<i64, Error> {
+        // Same computation as a swap, fees and commission included, without any price limit
+        let no_limit = PoolPrice {
+            integer: i64::MAX,
+            fraction: 0,
+        };
+        let result = Dex::swap_traced(
+            poolpair,
+            token_in,
+            no_limit,
+            None,
+            dex_fee,
+            post_bayfront_gardens,
+            None,
+        )?;
+        Ok(result.slop_swap_result)
+    }
+
+    fn add_liquidity(
+        poolpair: PoolPair,
+        amount_a: i64,
+        amount_b: i64,
+    ) -> Result<LiquidityResult, Error> {
+        if amount_a <= 0 || amount_b <= 0 || !Dex::valid_pool_type(&poolpair) {
+            return Err(Error::InvalidInput);
+        }
+        let mut poolpair = poolpair;
+        let (reserve_a, reserve_b) = match (
+            poolpair.reserve_a.checked_add(amount_a),
+            poolpair.reserve_b.checked_add(amount_b),
+        ) {
+            (Some(reserve_a), Some(reserve_b)) => (reserve_a, reserve_b),
+            _ => return Err(Error::PoolReserveOverflow(Dex::reserves(&poolpair))),
+        };
+
+        let liquidity = if poolpair.total_liquidity == 0 {
+            let liquidity = Dex::to_amount(Dex::pool_value(&poolpair, amount_a, amount_b)?)?;
+            if liquidity <= MINIMUM_LIQUIDITY {
+                return Err(Error::LackOfLiquidity(Reserves {
+                    reserve_a,
+                    reserve_b,
+                }));
+            }
+            // The first MINIMUM_LIQUIDITY tokens are locked in the pool forever
+            poolpair.total_liquidity = MINIMUM_LIQUIDITY;
+            liquidity - MINIMUM_LIQUIDITY
+        } else {
+            if poolpair.reserve_a <= 0 || poolpair.reserve_b <= 0 {
+                return Err(Error::LackOfLiquidity(Dex::reserves(&poolpair)));
+            }
+            let total = U256::from(poolpair.total_liquidity);
+            let liquidity = match poolpair.pool_type {
+                PoolType::ConstantProduct | PoolType::Weighted => {
+                    let liq_a = U256::from(amount_a) * total / U256::from(poolpair.reserve_a);
+                    let liq_b = U256::from(amount_b) * total / U256::from(poolpair.reserve_b);
+                    liq_a.min(liq_b)
+                }
+                PoolType::StableSwap => {
+                    let old = Dex::pool_value(&poolpair, poolpair.reserve_a, poolpair.reserve_b)?;
+                    let new = Dex::pool_value(&poolpair, reserve_a, reserve_b)?;
+                    total * new.saturating_sub(old) / old
+                }
+            };
+            let liquidity = Dex::to_amount(liquidity)?;
+            if liquidity == 0 {
+                return Err(Error::InvalidInput);
+            }
+            liquidity
+        };
+
+        poolpair.total_liquidity = poolpair
+            .total_liquidity
+            .checked_add(liquidity)
+            .ok_or(Error::ArithmeticOverflow)?;
+        poolpair.reserve_a = reserve_a;
+        poolpair.reserve_b = reserve_b;
+
+        Ok(LiquidityResult {
+            pool_pair: poolpair,
+            amount_a,
+            amount_b,
+            liquidity,
+        })
+    }
+
+    fn remove_liquidity(poolpair: PoolPair, liquidity: i64) -> Result<LiquidityResult, Error> {
+        if liquidity <= 0 || liquidity >= poolpair.total_liquidity {
+            return Err(Error::InvalidInput);
+        }
+        let mut poolpair = poolpair;
+
+        // All pool types pay out in proportion to the current reserves
+        let total = U256::from(poolpair.total_liquidity);
+        let amount_a =
+            Dex::to_amount(U256::from(liquidity) * U256::from(poolpair.reserve_a.max(0)) / total)?;
+        let amount_b =
+            Dex::to_amount(U256::from(liquidity) * U256::from(poolpair.reserve_b.max(0)) / total)?;
+
+        poolpair.reserve_a -= amount_a;
+        poolpair.reserve_b -= amount_b;
+        poolpair.total_liquidity -= liquidity;
+
+        Ok(LiquidityResult {
+            pool_pair: poolpair,
+            amount_a,
+            amount_b,
+            liquidity,
+        })
+    }
+
+    fn create_pool_pair(config: PoolPairConfig, owner_address: String) -> Result<PoolPair, Error> {
+        if config.token_a == config.token_b {
+            return Err(Error::InvalidPoolConfig(PoolConfigError::SameToken));
+        }
+        Dex::validate_pool_config(config.commission, Some(&owner_address))?;
+
+        let poolpair = PoolPair {
+            token_a: config.token_a,
+            token_b: config.token_b,
+            commission: config.commission,
+            reserve_a: 0,
+            reserve_b: 0,
+            total_liquidity: 0,
+            block_commission_a: 0,
+            block_commission_b: 0,
+            pool_type: config.pool_type,
+            amplification: config.amplification,
+            weight_a: config.weight_a,
+            weight_b: config.weight_b,
+            price_ab_cumulative: 0.into(),
+            price_ba_cumulative: 0.into(),
+            price_height: 0,
+            status: config.status,
+        };
+        if !Dex::valid_pool_type(&poolpair) {
+            return Err(Error::InvalidPoolConfig(PoolConfigError::InvalidPoolType));
+        }
+        Ok(poolpair)
+    }
+
+    fn update_pool_pair(
+        poolpair: PoolPair,
+        status: Option<bool>,
+        commission: Option<u32>,
+    ) -> Result<PoolPair, Error> {
+        let commission = commission.unwrap_or(poolpair.commission);
+        Dex::validate_pool_config(commission, None)?;
+
+        Ok(PoolPair {
+            commission,
+            status: status.unwrap_or(poolpair.status),
+            ..poolpair
+        })
+    }
+
+    fn accumulate_price(poolpair: PoolPair, height: u32) -> Result<PoolPair, Error> {
+        if height < poolpair.price_height || !Dex::valid_pool_type(&poolpair) {
+            return Err(Error::InvalidInput);
+        }
+        let mut poolpair = poolpair;
+
+        // The spot price held for every block since the last update, as the node accumulates
+        // before any change to the reserves at a new height. Nothing accumulates until the pool
+        // holds both tokens.
+        let elapsed = height - poolpair.price_height;
+        if elapsed > 0 && poolpair.reserve_a > 0 && poolpair.reserve_b > 0 {
+            let price_ab = Dex::spot_price(&poolpair, true)?;
+            let price_ba = Dex::spot_price(&poolpair, false)?;
+            poolpair.price_ab_cumulative =
+                Dex::accumulate(poolpair.price_ab_cumulative, price_ab, elapsed);
+            poolpair.price_ba_cumulative =
+                Dex::accumulate(poolpair.price_ba_cumulative, price_ba, elapsed);
+        }
+        poolpair.price_height = height;
+
+        Ok(poolpair)
+    }
+
+    fn twap(start: PoolPair, end: PoolPair) -> Result<TwapResult, Error> {
+        if start.token_a != end.token_a
+            || start.token_b != end.token_b
+            || end.price_height <= start.price_height
+        {
+            return Err(Error::InvalidInput);
+        }
+
+        // Accumulators wrap around, so the difference is taken modulo 2^128
+        let elapsed = u128::from(end.price_height - start.price_height);
+        let average = |start: CumulativePrice, end: CumulativePrice| {
+            let sum = u128::from(end).wrapping_sub(u128::from(start));
+            Dex::u256_to_price(U256::from(sum / elapsed))
+        };
+
+        Ok(TwapResult {
+            price_ab: average(start.price_ab_cumulative, end.price_ab_cumulative),
+            price_ba: average(start.price_ba_cumulative, end.price_ba_cumulative),
+        })
+    }
+
+    fn distribute(
+        poolpair: PoolPair,
+        rewards: Vec<RewardPool>,
+        height: u32,
+    ) -> Result<Vec<RewardPool>, Error> {
+        let mut rewards = rewards;
+        for reward in rewards.iter_mut() {
+            if height < reward.last_height || reward.reward_per_block < 0 {
+                return Err(Error::InvalidInput);
+            }
+
+            // Rewards for blocks without any liquidity are not paid out to anyone
+            let elapsed = height - reward.last_height;
+            if poolpair.total_liquidity > 0 {
+                let increment = U256::from(reward.reward_per_block) * elapsed * REWARD_PRECISION
+                    / U256::from(poolpair.total_liquidity);
+                // The accumulator wraps around, but a single increment must fit in it whole or
+                // owners would be underpaid
+                if increment > U256::from(u128::MAX) {
+                    return Err(Error::ArithmeticOverflow);
+                }
+                reward.reward_per_share = u128::from(reward.reward_per_share)
+                    .wrapping_add(increment.low_u128())
+                    .into();
+            }
+            reward.last_height = height;
+        }
+        Ok(rewards)
+    }
+
+    fn claim(
+        poolpair: PoolPair,
+        rewards: Vec<RewardPool>,
+        liquidity: i64,
+        paid: Vec<RewardPerShare>,
+    ) -> Result<ClaimResult, Error> {
+        if liquidity < 0 || liquidity > poolpair.total_liquidity || paid.len() > rewards.len() {
+            return Err(Error::InvalidInput);
+        }
+
+        // Reward pools missing from `paid` were added after the owner last claimed, and start
+        // from a reward per share of zero
+        let mut amounts = Vec::with_capacity(rewards.len());
+        for (i, reward) in rewards.iter().enumerate() {
+            let paid = paid.get(i).map_or(0, |&paid| u128::from(paid));
+            let owed = u128::from(reward.reward_per_share).wrapping_sub(paid);
+            let amount = U256::from(liquidity) * U256::from(owed) / REWARD_PRECISION;
+            amounts.push(TokenAmount {
+                token_id: reward.token_id,
+                amount: Dex::to_amount(amount)?,
+            });
+        }
+
+        Ok(ClaimResult {
+            amounts,
+            paid: rewards
+                .iter()
+                .map(|reward| reward.reward_per_share)
+                .collect(),
+        })
+    }
+
+    fn serialize_pools(pools: Vec<PoolEntry>) -> Result<Vec<u8>, Error> {
+        Ok(PoolRegistry::from_entries(pools)?.to_bytes())
+    }
+
+    fn deserialize_pools(bytes: Vec<u8>) -> Result<Vec<PoolEntry>, Error> {
+        Ok(PoolRegistry::from_bytes(&bytes)?.entries())
+    }
+
+    fn pools_hash(pools: Vec<PoolEntry>) -> Result<Vec<u8>, Error> {
+        Ok(PoolRegistry::from_entries(pools)?.hash().to_vec())
+    }
+
+    /// DFIP-2206-D fee for DUSD swaps, computed by the same `ain-coin` function as the node's
+    /// `calc_dex_fee`, so it can be passed back in as a `DexFee` percentage.
+    fn dex_stabilization_fee(
+        algo_dusd: i64,
+        dusd_supply: i64,
+        coefficient: i64,
+    ) -> Result<i64, Error> {
+        if algo_dusd < 0 || dusd_supply <= 0 || coefficient <= 0 {
+            return Err(Error::InvalidInput);
+        }
+        dex_stabilization_fee(Amount(algo_dusd), Amount(dusd_supply), Amount(coefficient))
+            .map(|fee| fee.0)
+            .ok_or(Error::InvalidInput)
+    }
+}
+
+impl Dex {
+    /// Swaps `token_in` against a single pool pair the way the node does: the dex fee in and the
+    /// pool commission are taken from the input before it reaches the pool, and the dex fee out
+    /// from what the pool pays out. Every step and fee is recorded into `trace` when given.
+    fn swap_traced(
+        poolpair: PoolPair,
+        token_in: TokenAmount,
+        max_price: PoolPrice,
+        min_output: Option<i64>,
+        dex_fee: DexFee,
+        post_bayfront_gardens: bool,
+        mut trace: Option<&mut SwapTrace>,
+    ) -> Result<SwapResult, Error> {
+        Dex::check_input(&poolpair, token_in)?;
+        let forward = token_in.token_id == poolpair.token_a;
+        let (in_pct, out_pct) = if forward {
+            (dex_fee.in_pct_ab, dex_fee.out_pct_ab)
+        } else {
+            (dex_fee.in_pct_ba, dex_fee.out_pct_ba)
+        };
+        let commission = i64::from(poolpair.commission);
+        if !(0..=COIN).contains(&in_pct) || !(0..=COIN).contains(&out_pct) || commission > COIN {
+            return Err(Error::InvalidInput);
+        }
+        let token_out = if forward {
+            poolpair.token_b
+        } else {
+            poolpair.token_a
+        };
+
+        let mut poolpair = poolpair;
+        let dex_fee_in = Dex::fee(token_in.amount, in_pct);
+        let trade_fee = Dex::fee(token_in.amount - dex_fee_in, commission);
+        let block_commission = if forward {
+            &mut poolpair.block_commission_a
+        } else {
+            &mut poolpair.block_commission_b
+        };
+        *block_commission = block_commission
+            .checked_add(trade_fee)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let swap_in = TokenAmount {
+            token_id: token_in.token_id,
+            amount: token_in.amount - dex_fee_in - trade_fee,
+        };
+        let result = Dex::swap_pool(
+            &mut poolpair,
+            swap_in,
+            max_price,
+            post_bayfront_gardens,
+            trace.as_deref_mut().map(|trace| &mut trace.steps),
+        )?;
+        let dex_fee_out = Dex::fee(result, out_pct);
+        let result = result - dex_fee_out;
+
+        // A minimum output opts into slippage protection, which also holds the realized price,
+        // fees and slippage included, to `max_price`. Without one only the pre-trade spot price
+        // is checked, as the node does.
+        if let Some(min_output) = min_output {
+            Dex::check_realized_price(token_in.amount, result, max_price)?;
+            if result < min_output {
+                return Err(Error::OutputBelowMinimum(OutputLimit {
+                    amount: result,
+                    min_output,
+                }));
+            }
+        }
+
+        if let Some(trace) = trace {
+            let events = [
+                (
+                    SwapEventKind::DexFeeIn,
+                    in_pct,
+                    token_in.token_id,
+                    dex_fee_in,
+                ),
+                (
+                    SwapEventKind::Commission,
+                    commission,
+                    token_in.token_id,
+                    trade_fee,
+                ),
+                (SwapEventKind::DexFeeOut, out_pct, token_out, dex_fee_out),
+            ];
+            for (kind, pct, token_id, amount) in events {
+                if pct > 0 {
+                    trace.events.push(SwapEvent {
+                        kind,
+                        token_id,
+                        amount,
+                    });
+                }
+            }
+        }
+
+        Ok(SwapResult {
+            pool_pair: poolpair,
+            slop_swap_result: result,
+            dex_fee_in,
+            dex_fee_out,
+        })
+    }
+
+    /// Swaps `token_in` through every pool pair of `path` in order, feeding the output of each
+    /// hop into the next one, and returns the amount received from the last hop. Each hop is a
+    /// full swap with the dex fee of the same index in `dex_fees` and the pool's commission.
+    /// Like the node's composite swap, `max_price` bounds the spot price of each pool before its
+    /// hop and the price paid for the whole route, the original input per unit of the final
+    /// output.
+    fn swap_path(
+        path: &mut [PoolPair],
+        token_in: TokenAmount,
+        max_price: PoolPrice,
+        dex_fees: &[DexFee],
+        post_bayfront_gardens: bool,
+    ) -> Result<i64, Error> {
+        let mut swap_amount = token_in;
+        for (poolpair, &dex_fee) in path.iter_mut().zip(dex_fees) {
+            // Output of each hop is the other token of the pool, which feeds the next hop
+            let token_out = if swap_amount.token_id == poolpair.token_a {
+                poolpair.token_b
+            } else {
+                poolpair.token_a
+            };
+            let result = Dex::swap_traced(
+                *poolpair,
+                swap_amount,
+                max_price,
+                None,
+                dex_fee,
+                post_bayfront_gardens,
+                None,
+            )?;
+            *poolpair = result.pool_pair;
+            swap_amount = TokenAmount {
+                token_id: token_out,
+                amount: result.slop_swap_result,
+            };
+        }
+        Dex::check_realized_price(token_in.amount, swap_amount.amount, max_price)?;
+        Ok(swap_amount.amount)
+    }
+
+    /// Depth-first enumeration of every path from `token_from` to `token_to` of at most
+    /// `max_hops` pools, visiting pools in list order. A path never goes through the same token
+    /// twice, which also rules out reusing a pool.
+    fn find_paths(
+        poolpairs: &[PoolPair],
+        token_from: DctId,
+        token_to: DctId,
+        max_hops: usize,
+        path: &mut Vec<u32>,
+        visited: &mut Vec<DctId>,
+        paths: &mut Vec<Vec<u32>>,
+    ) {
+        if path.len() == max_hops {
+            return;
+        }
+        for (i, poolpair) in poolpairs.iter().enumerate() {
+            let next = if poolpair.token_a == token_from {
+                poolpair.token_b
+            } else if poolpair.token_b == token_from {
+                poolpair.token_a
+            } else {
+                continue;
+            };
+            if visited.contains(&next) {
+                continue;
+            }
+
+            path.push(i as u32);
+            if next == token_to {
+                paths.push(path.clone());
+            } else {
+                visited.push(next);
+                Dex::find_paths(poolpairs, next, token_to, max_hops, path, visited, paths);
+                visited.pop();
+            }
+            path.pop();
+        }
+    }
+
+    /// Rejects an input token that is not one of the pool's tokens, or a non-positive amount.
+    /// Runs before any fee is taken from the amount.
+    fn check_input(poolpair: &PoolPair, token_in: TokenAmount) -> Result<(), Error> {
+        if token_in.token_id != poolpair.token_a && token_in.token_id != poolpair.token_b {
+            return Err(Error::TokenMismatch(InputTokenMismatch {
+                token_id: token_in.token_id,
+                token_a: poolpair.token_a,
+                token_b: poolpair.token_b,
+            }));
+        }
+        if token_in.amount <= 0 {
+            return Err(Error::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// Swaps `token_in` against a single pool pair, updating its reserves in place and returning
+    /// the amount of the opposite token received. The steps of the trade are recorded into
+    /// `steps` when given.
+    fn swap_pool(
+        poolpair: &mut PoolPair,
+        token_in: TokenAmount,
+        max_price: PoolPrice,
+        post_bayfront_gardens: bool,
+        mut steps: Option<&mut Vec<SwapStep>>,
+    ) -> Result<i64, Error> {
+        Dex::check_input(poolpair, token_in)?;
+        if !Dex::valid_pool_type(poolpair) {
+            return Err(Error::InvalidInput);
+        }
+        if !poolpair.status {
+            return Err(Error::PoolDisabled);
+        }
+
+        let forward = token_in.token_id == poolpair.token_a;
+        let pool_type = poolpair.pool_type;
+        let amplification = poolpair.amplification;
+        let (weight_from, weight_to) = if forward {
+            (poolpair.weight_a, poolpair.weight_b)
+        } else {
+            (poolpair.weight_b, poolpair.weight_a)
+        };
+
+        if poolpair.reserve_a < SLOPE_SWAP_RATE || poolpair.reserve_b < SLOPE_SWAP_RATE {
+            return Err(Error::LackOfLiquidity(Dex::reserves(poolpair)));
+        }
+
+        let max_prince_256 = Dex::price_to_u256(max_price);
+
+        // A stable pool does not trade at its spot price, so its price is checked on the
+        // realized price below instead
+        if pool_type != PoolType::StableSwap {
+            let cur_price = Dex::spot_price(poolpair, forward)?;
+            if cur_price > max_prince_256 {
+                return Err(Error::PriceHigherThanIndex(PriceLimit {
+                    price: Dex::u256_to_price(cur_price),
+                    max_price,
+                }));
+            }
+        }
+
+        let check_res = if forward {
+            poolpair.reserve_a.checked_add(token_in.amount)
+        } else {
+            poolpair.reserve_b.checked_add(token_in.amount)
+        };
+        if check_res.is_none() {
+            return Err(Error::PoolReserveOverflow(Dex::reserves(poolpair)));
+        }
+
+        let (pool_from, pool_to) = if forward {
+            (&mut poolpair.reserve_a, &mut poolpair.reserve_b)
+        } else {
+            (&mut poolpair.reserve_b, &mut poolpair.reserve_a)
+        };
+        let (old_from, old_to) = (*pool_from, *pool_to);
+        let (result, invariants_hold) = match pool_type {
+            PoolType::ConstantProduct => {
+                let result = Dex::slop_swap(
+                    token_in.amount,
+                    pool_from,
+                    pool_to,
+                    post_bayfront_gardens,
+                    steps.as_deref_mut(),
+                )?;
+                let invariants_hold = Dex::invariants_hold(
+                    token_in.amount,
+                    result,
+                    (old_from, old_to),
+                    (*pool_from, *pool_to),
+                );
+                (result, invariants_hold)
+            }
+            PoolType::StableSwap => {
+                let result = Dex::stable_swap(token_in.amount, pool_from, pool_to, amplification)?;
+                let invariants_hold = Dex::stable_invariants_hold(
+                    amplification,
+                    token_in.amount,
+                    result,
+                    (old_from, old_to),
+                    (*pool_from, *pool_to),
+                )?;
+                (result, invariants_hold)
+            }
+            PoolType::Weighted => {
+                let result = Dex::weighted_swap(
+                    token_in.amount,
+                    pool_from,
+                    pool_to,
+                    weight_from,
+                    weight_to,
+                )?;
+                let invariants_hold = Dex::weighted_invariants_hold(
+                    (weight_from, weight_to),
+                    token_in.amount,
+                    result,
+                    (old_from, old_to),
+                    (*pool_from, *pool_to),
+                );
+                (result, invariants_hold)
+            }
+        };
+        if !invariants_hold {
+            return Err(Error::InvariantViolation(Dex::reserves(poolpair)));
+        }
+
+        // Stable and weighted pools trade in a single step
+        if pool_type != PoolType::ConstantProduct {
+            if let Some(steps) = steps {
+                let (reserve_from, reserve_to) = if forward {
+                    (poolpair.reserve_a, poolpair.reserve_b)
+                } else {
+                    (poolpair.reserve_b, poolpair.reserve_a)
+                };
+                steps.push(SwapStep {
+                    step_from: token_in.amount,
+                    step_to: result,
+                    reserve_from,
+                    reserve_to,
+                });
+            }
+        }
+
+        if pool_type == PoolType::StableSwap {
+            Dex::check_realized_price(token_in.amount, result, max_price)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Rejects a trade whose realized price, `amount_in` paid per unit of `amount_out`, is above
+    /// `max_price`
+    fn check_realized_price(
+        amount_in: i64,
+        amount_out: i64,
+        max_price: PoolPrice,
+    ) -> Result<(), Error> {
+        if amount_out > 0 {
+            let realized_price = U256::from(amount_in) * PRECISION / U256::from(amount_out);
+            if realized_price > Dex::price_to_u256(max_price) {
+                return Err(Error::PriceHigherThanIndex(PriceLimit {
+                    price: Dex::u256_to_price(realized_price),
+                    max_price,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rejects swap results that a constant-product pool cannot produce: reserves must move by
+    /// exactly the traded amounts, the pool can never be drained, and the output can never exceed
+    /// what the pre-trade spot price pays for the input (rounded up).
+    fn invariants_hold(
+        amount_in: i64,
+        amount_out: i64,
+        (old_from, old_to): (i64, i64),
+        (new_from, new_to): (i64, i64),
+    ) -> bool {
+        if amount_out < 0
+            || old_from.checked_add(amount_in) != Some(new_from)
+            || old_to.checked_sub(amount_out) != Some(new_to)
+            || new_to <= 0
+        {
+            return false;
+        }
+
+        amount_out == 0
+            || U256::from(amount_out - 1) * U256::from(old_from)
+                < U256::from(old_to) * U256::from(amount_in)
+    }
+
+    /// Stable-swap counterpart of `invariants_hold`: reserves must move by exactly the traded
+    /// amounts, the pool can never be drained, and the invariant `D` can never decrease.
+    fn stable_invariants_hold(
+        amplification: u32,
+        amount_in: i64,
+        amount_out: i64,
+        (old_from, old_to): (i64, i64),
+        (new_from, new_to): (i64, i64),
+    ) -> Result<bool, Error> {
+        if amount_out < 0
+            || old_from.checked_add(amount_in) != Some(new_from)
+            || old_to.checked_sub(amount_out) != Some(new_to)
+            || new_to <= 0
+        {
+            return Ok(false);
+        }
+
+        let old_d = Dex::stable_swap_d(U256::from(old_from), U256::from(old_to), amplification)?;
+        let new_d = Dex::stable_swap_d(U256::from(new_from), U256::from(new_to), amplification)?;
+        Ok(new_d >= old_d)
+    }
+
+    /// Weighted counterpart of `invariants_hold`: reserves must move by exactly the traded
+    /// amounts, the pool can never be drained, and the output can never exceed what the
+    /// pre-trade weighted spot price pays for the input.
+    fn weighted_invariants_hold(
+        (weight_from, weight_to): (u32, u32),
+        amount_in: i64,
+        amount_out: i64,
+        (old_from, old_to): (i64, i64),
+        (new_from, new_to): (i64, i64),
+    ) -> bool {
+        if amount_out < 0
+            || old_from.checked_add(amount_in) != Some(new_from)
+            || old_to.checked_sub(amount_out) != Some(new_to)
+            || new_to <= 0
+        {
+            return false;
+        }
+
+        U256::from(amount_out) * U256::from(old_from) * U256::from(weight_to)
+            <= U256::from(old_to) * U256::from(amount_in) * U256::from(weight_from)
+    }
+
+    /// Checks the commission of a pool, and the owner address it is created with. The node keeps
+    /// the owner address next to the pool pair, so it is only validated here.
+    fn validate_pool_config(commission: u32, owner_address: Option<&str>) -> Result<(), Error> {
+        if i64::from(commission) > COIN {
+            return Err(Error::InvalidPoolConfig(
+                PoolConfigError::CommissionOutOfRange,
+            ));
+        }
+        if matches!(owner_address, Some(owner_address) if !address::is_valid(owner_address)) {
+            return Err(Error::InvalidPoolConfig(
+                PoolConfigError::InvalidOwnerAddress,
+            ));
+        }
+        Ok(())
+    }
+
+    fn valid_pool_type(poolpair: &PoolPair) -> bool {
+        match poolpair.pool_type {
+            PoolType::ConstantProduct => true,
+            PoolType::StableSwap => {
+                poolpair.amplification > 0 && poolpair.amplification <= MAX_AMPLIFICATION
+            }
+            PoolType::Weighted => {
+                poolpair.weight_a > 0
+                    && poolpair.weight_b > 0
+                    && poolpair.weight_a.checked_add(poolpair.weight_b) == Some(WEIGHT_TOTAL)
+            }
+        }
+    }
+
+    /// Value of a pool holding `amount_a` and `amount_b` that liquidity tokens are minted
+    /// against: the geometric mean of the amounts for constant-product pools, the invariant `D`
+    /// for stable pools and the weighted geometric mean for weighted pools.
+    fn pool_value(poolpair: &PoolPair, amount_a: i64, amount_b: i64) -> Result<U256, Error> {
+        if poolpair.pool_type == PoolType::Weighted {
+            let ln_a = Dex::fixed_ln(i128::from(amount_a) * FIXED_ONE)?;
+            let ln_b = Dex::fixed_ln(i128::from(amount_b) * FIXED_ONE)?;
+            let ln_value = (ln_a * i128::from(poolpair.weight_a)
+                + ln_b * i128::from(poolpair.weight_b))
+                / i128::from(WEIGHT_TOTAL);
+            let value = Dex::fixed_exp(ln_value)? / FIXED_ONE;
+            return Ok(U256::from(value as u128));
+        }
+        let (amount_a, amount_b) = (U256::from(amount_a), U256::from(amount_b));
+        match poolpair.pool_type {
+            PoolType::StableSwap => Dex::stable_swap_d(amount_a, amount_b, poolpair.amplification),
+            _ => Ok((amount_a * amount_b).integer_sqrt()),
+        }
+    }
+
+    /// Marginal price of the token paid out in units of the token paid in, scaled by
+    /// `PRECISION`. This is the reserve ratio for constant-product pools, the reserve ratio
+    /// scaled by the weights for weighted pools, and the ratio of the partial derivatives of the
+    /// invariant for stable pools.
+    fn spot_price(poolpair: &PoolPair, forward: bool) -> Result<U256, Error> {
+        let (reserve_from, reserve_to, weight_from, weight_to) = if forward {
+            (
+                poolpair.reserve_a,
+                poolpair.reserve_b,
+                poolpair.weight_a,
+                poolpair.weight_b,
+            )
+        } else {
+            (
+                poolpair.reserve_b,
+                poolpair.reserve_a,
+                poolpair.weight_b,
+                poolpair.weight_a,
+            )
+        };
+        let (reserve_from, reserve_to) = (U256::from(reserve_from), U256::from(reserve_to));
+        match poolpair.pool_type {
+            PoolType::ConstantProduct => Ok(reserve_from * PRECISION / reserve_to),
+            PoolType::StableSwap => {
+                // Both derivatives are divided through by from * to to stay within 256 bits
+                let amplification = poolpair.amplification;
+                let d = Dex::stable_swap_d(reserve_from, reserve_to, amplification)?;
+                let d_3 = d * d * d;
+                let ann_xy = U256::from(amplification) * 16 * reserve_from * reserve_to;
+                Ok((ann_xy + d_3 / reserve_to) * PRECISION / (ann_xy + d_3 / reserve_from))
+            }
+            PoolType::Weighted => {
+                Ok(reserve_from * weight_to * PRECISION / (reserve_to * weight_from))
+            }
+        }
+    }
+
+    /// Adds `price` held for `blocks` blocks to a cumulative price, wrapping around on overflow.
+    fn accumulate(cumulative: CumulativePrice, price: U256, blocks: u32) -> CumulativePrice {
+        let increment = (price * blocks).low_u128();
+        u128::from(cumulative).wrapping_add(increment).into()
+    }
+
+    /// Fee of `pct` on `amount`, rounded down. This is the node's `MultiplyAmounts`, shared with
+    /// ain-core so the fees taken here cannot drift from the ones the node computes.
+    fn fee(amount: i64, pct: i64) -> i64 {
+        Amount(amount).checked_price_multiply(Amount(pct)).0
+    }
+
+    fn reserves(poolpair: &PoolPair) -> Reserves {
+        Reserves {
+            reserve_a: poolpair.reserve_a,
+            reserve_b: poolpair.reserve_b,
+        }
+    }
+
+    /// Scales a pool price to the `PRECISION` fixed point used for reserve ratios, keeping the
+    /// fractional part. Negative components are treated as zero.
+    fn price_to_u256(price: PoolPrice) -> U256 {
+        U256::from(price.integer.max(0)) * PRECISION + U256::from(price.fraction.max(0))
+    }
+
+    /// Inverse of `price_to_u256`, saturating the integer part for error reporting.
+    fn u256_to_price(price: U256) -> PoolPrice {
+        PoolPrice {
+            integer: Dex::to_amount(price / PRECISION).unwrap_or(i64::MAX),
+            fraction: (price % PRECISION).low_u64() as i64,
+        }
+    }
+
+    /// Converts a `U256` back into an amount, failing instead of truncating when it does not fit.
+    fn to_amount(value: U256) -> Result<i64, Error> {
+        if value > U256::from(i64::MAX) {
+            return Err(Error::ArithmeticOverflow);
+        }
+        Ok(value.low_u64() as i64)
+    }
+
+    pub fn slop_swap(
+        unswapped: i64,
+        pool_from: &mut i64,
+        pool_to: &mut i64,
+        post_bayfront_gardens: bool,
+        steps: Option<&mut Vec<SwapStep>>,
+    ) -> Result<i64, Error> {
+        if unswapped < 0 || *pool_from <= 0 || *pool_to < 0 {
+            return Err(Error::InvalidInput);
+        }
+        if unswapped.checked_add(*pool_from).is_none() {
+            return Err(Error::ArithmeticOverflow);
+        }
+
+        if post_bayfront_gardens {
+            // Chunks are a thousandth of the from reserve, so a smaller pool would never finish
+            if *pool_from < SLOPE_SWAP_RATE && unswapped > 0 {
+                return Err(Error::LackOfLiquidity(Reserves {
+                    reserve_a: *pool_from,
+                    reserve_b: *pool_to,
+                }));
+            }
+            #[cfg(not(feature = "reference-slop-swap"))]
+            return Dex::slop_swap_chunked_u128(unswapped, pool_from, pool_to, steps);
+            #[cfg(feature = "reference-slop-swap")]
+            return Dex::slop_swap_chunked_reference(unswapped, pool_from, pool_to, steps);
+        }
+
+        let mut pool_f = U256::from(*pool_from);
+        let mut pool_t = U256::from(*pool_to);
+
+        // The node's `CPoolPair::Swap` before Bayfront Gardens: `poolT - poolT * poolF /
+        // (poolF + unswapped)` in `arith_uint256`, which keeps `poolF * poolT` from falling
+        let unswapped_a = U256::from(unswapped);
+        let swapped = pool_t - (pool_t * pool_f / (pool_f + unswapped_a));
+        pool_f += unswapped_a;
+        pool_t = pool_t
+            .checked_sub(swapped)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        if let Some(steps) = steps {
+            steps.push(SwapStep {
+                step_from: unswapped,
+                step_to: Dex::to_amount(swapped)?,
+                reserve_from: Dex::to_amount(pool_f)?,
+                reserve_to: Dex::to_amount(pool_t)?,
+            });
+        }
+        *pool_from = Dex::to_amount(pool_f)?;
+        *pool_to = Dex::to_amount(pool_t)?;
+        Dex::to_amount(swapped)
+    }
+
+    /// Post-Bayfront Gardens swap of `unswapped` in chunks of a thousandth of the from reserve,
+    /// each priced at the reserves left by the previous one. The same loop as
+    /// `slop_swap_chunked_reference`, with the same results, in `u128` instead of `U256`: every
+    /// product of a reserve and a chunk fits.
+    ///
+    /// Consecutive full chunks that pay out the same amount are applied together. A chunk `c`
+    /// paying `q` from reserves `f`/`t` leaves `f + c`/`t - q`, and the `j`th chunk after it
+    /// still pays `q` as long as `2jqc <= tc - qf`, so a run takes one division however long it
+    /// is. Runs grow as the payout per chunk shrinks, which is the tail of swaps many times the
+    /// size of the from reserve; once a chunk pays out nothing so do all later ones.
+    #[cfg_attr(feature = "reference-slop-swap", allow(dead_code))]
+    fn slop_swap_chunked_u128(
+        unswapped: i64,
+        pool_from: &mut i64,
+        pool_to: &mut i64,
+        mut steps: Option<&mut Vec<SwapStep>>,
+    ) -> Result<i64, Error> {
+        let mut unswapped = unswapped;
+        let chunk = (*pool_from / SLOPE_SWAP_RATE).min(unswapped);
+
+        let mut pool_f = *pool_from as u128;
+        let mut pool_t = *pool_to as u128;
+        let mut swapped = 0u128;
+
+        while unswapped > 0 {
+            let step_from = chunk.min(unswapped);
+            let step_to = pool_t * step_from as u128 / pool_f;
+            if step_to == 0 && steps.is_none() {
+                pool_f += unswapped as u128;
+                break;
+            }
+
+            // Further full chunks that pay out `step_to` as well. The slack `tc - qf` is below
+            // the from reserve, so most chunks of a large pool are ruled out by one product.
+            let per_chunk = (step_to as u64 as u128) * (2 * chunk as u64 as u128);
+            let run = if step_from == chunk && per_chunk < pool_f {
+                Dex::slop_swap_run(unswapped, chunk, pool_f, pool_t, step_to, per_chunk)
+            } else {
+                1
+            };
+
+            if run > 1 && steps.is_none() {
+                pool_f += run * step_from as u128;
+                pool_t -= run * step_to;
+                unswapped -= run as i64 * step_from;
+                swapped += run * step_to;
+                continue;
+            }
+
+            for _ in 0..run {
+                pool_f += step_from as u128;
+                pool_t -= step_to;
+
+                unswapped -= step_from;
+                swapped += step_to;
+
+                if let Some(steps) = steps.as_deref_mut() {
+                    steps.push(SwapStep {
+                        step_from,
+                        step_to: step_to as i64,
+                        reserve_from: pool_f as i64,
+                        reserve_to: pool_t as i64,
+                    });
+                }
+            }
+        }
+        // Reserves only move between the inputs and their sum, which fit in `i64`
+        *pool_from = pool_f as i64;
+        *pool_to = pool_t as i64;
+        Ok(swapped as i64)
+    }
+
+    /// Number of full chunks from `unswapped`, starting with the current one, that each pay
+    /// out `step_to`. The `j`th of them still does while `2j * step_to * chunk` stays within
+    /// the slack `pool_t * chunk - step_to * pool_f`.
+    #[cold]
+    fn slop_swap_run(
+        unswapped: i64,
+        chunk: i64,
+        pool_f: u128,
+        pool_t: u128,
+        step_to: u128,
+        per_chunk: u128,
+    ) -> u128 {
+        let full_chunks = (unswapped / chunk) as u128;
+        if step_to == 0 {
+            return full_chunks;
+        }
+        let slack = pool_t * chunk as u128 - step_to * pool_f;
+        (slack / per_chunk + 1).min(full_chunks)
+    }
+
+    /// Reference implementation of `slop_swap_chunked_u128`, one `U256` step per chunk. Enable the
+    /// `reference-slop-swap` feature to swap with it instead.
+    #[cfg(any(test, feature = "reference-slop-swap"))]
+    fn slop_swap_chunked_reference(
+        unswapped: i64,
+        pool_from: &mut i64,
+        pool_to: &mut i64,
+        mut steps: Option<&mut Vec<SwapStep>>,
+    ) -> Result<i64, Error> {
+        let mut unswapped = unswapped;
+
+        let mut pool_f = U256::from(*pool_from);
+        let mut pool_t = U256::from(*pool_to);
+
+        let mut swapped: U256 = 0.into();
+
+        let chunk = if *pool_from / SLOPE_SWAP_RATE < unswapped {
+            *pool_from / SLOPE_SWAP_RATE
+        } else {
+            unswapped
+        };
+        while unswapped > 0 {
+            let step_from = (chunk).min(unswapped);
+            let step_from_256 = U256::from(step_from);
+            let step_to = pool_t * step_from_256 / pool_f;
+            pool_f += step_from_256;
+            pool_t = pool_t
+                .checked_sub(step_to)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            unswapped -= step_from;
+            swapped += step_to;
+
+            if let Some(steps) = steps.as_deref_mut() {
+                steps.push(SwapStep {
+                    step_from,
+                    step_to: Dex::to_amount(step_to)?,
+                    reserve_from: Dex::to_amount(pool_f)?,
+                    reserve_to: Dex::to_amount(pool_t)?,
+                });
+            }
+        }
+        *pool_from = Dex::to_amount(pool_f)?;
+        *pool_to = Dex::to_amount(pool_t)?;
+        Dex::to_amount(swapped)
+    }
+
+    /// Stable-swap counterpart of `slop_swap` for a two token pool with amplification `A`. The
+    /// output keeps the invariant `4A(x + y) + D = 4AD + D^3 / 4xy` at the `D` of the reserves
+    /// before the swap. It is rounded down and one unit is kept back, so rounding always favours
+    /// the pool.
+    pub fn stable_swap(
+        unswapped: i64,
+        pool_from: &mut i64,
+        pool_to: &mut i64,
+        amplification: u32,
+    ) -> Result<i64, Error> {
+        if unswapped < 0 || *pool_from <= 0 || *pool_to <= 0 {
+            return Err(Error::InvalidInput);
+        }
+        if amplification == 0 || amplification > MAX_AMPLIFICATION {
+            return Err(Error::InvalidInput);
+        }
+        let new_from = pool_from
+            .checked_add(unswapped)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let pool_t = U256::from(*pool_to);
+        let d = Dex::stable_swap_d(U256::from(*pool_from), pool_t, amplification)?;
+        let new_to = Dex::stable_swap_y(U256::from(new_from), d, amplification)?;
+        let swapped = pool_t.saturating_sub(new_to).saturating_sub(U256::one());
+
+        *pool_from = new_from;
+        *pool_to = Dex::to_amount(pool_t - swapped)?;
+        Dex::to_amount(swapped)
+    }
+
+    /// Solves the stable-swap invariant for `D` by Newton iteration, starting from `x + y`.
+    fn stable_swap_d(x: U256, y: U256, amplification: u32) -> Result<U256, Error> {
+        let sum = x + y;
+        if sum.is_zero() {
+            return Ok(U256::zero());
+        }
+        let ann = U256::from(amplification) * 4;
+
+        let mut d = sum;
+        for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+            // D^3 / 4xy in a single division, as truncating in between stops the iteration from
+            // converging on very unbalanced pools
+            let d_p = d * d * d / (x * y * 4);
+            let prev = d;
+            d = (ann * sum + d_p * 2) * d / ((ann - 1) * d + d_p * 3);
+            if Dex::converged(d, prev) {
+                return Ok(d);
+            }
+        }
+        Err(Error::Runtime)
+    }
+
+    /// Solves the stable-swap invariant at `d` for the reserve opposite to `x` by Newton
+    /// iteration, starting from `d`.
+    fn stable_swap_y(x: U256, d: U256, amplification: u32) -> Result<U256, Error> {
+        let ann = U256::from(amplification) * 4;
+        let c = d * d * d / (x * ann * 4);
+        let b = x + d / ann;
+
+        let mut y = d;
+        for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+            let prev = y;
+            let denominator = (y + y + b)
+                .checked_sub(d)
+                .ok_or(Error::ArithmeticOverflow)?;
+            y = (y * y + c) / denominator;
+            if Dex::converged(y, prev) {
+                return Ok(y);
+            }
+        }
+        Err(Error::Runtime)
+    }
+
+    fn converged(value: U256, prev: U256) -> bool {
+        if value > prev {
+            value - prev <= U256::one()
+        } else {
+            prev - value <= U256::one()
+        }
+    }
+
+    /// Weighted counterpart of `slop_swap` for a pool with token weights `weight_from` and
+    /// `weight_to`. The output keeps `from^weight_from * to^weight_to` constant:
+    /// `to * (1 - (from / (from + in))^(weight_from / weight_to))`. The power is computed in
+    /// `FIXED_ONE` fixed point and rounded up, so rounding always favours the pool.
+    pub fn weighted_swap(
+        unswapped: i64,
+        pool_from: &mut i64,
+        pool_to: &mut i64,
+        weight_from: u32,
+        weight_to: u32,
+    ) -> Result<i64, Error> {
+        if unswapped < 0 || *pool_from <= 0 || *pool_to <= 0 {
+            return Err(Error::InvalidInput);
+        }
+        if weight_from == 0 || weight_to == 0 {
+            return Err(Error::InvalidInput);
+        }
+        let new_from = pool_from
+            .checked_add(unswapped)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let new_from_128 = i128::from(new_from);
+        let base = (i128::from(*pool_from) * FIXED_ONE + new_from_128 - 1) / new_from_128;
+        let power =
+            Dex::fixed_exp(Dex::fixed_ln(base)? * i128::from(weight_from) / i128::from(weight_to))?;
+        let power = (power + power / WEIGHTED_POW_ROUNDING + 1).min(FIXED_ONE);
+        let swapped = i128::from(*pool_to) * (FIXED_ONE - power) / FIXED_ONE;
+
+        *pool_from = new_from;
+        *pool_to -= swapped as i64;
+        Ok(swapped as i64)
+    }
+
+    /// Natural logarithm of a positive `FIXED_ONE` fixed point number. The argument is scaled by
+    /// a power of two into `[1, 2)` and the remainder is summed as `2 atanh((m - 1) / (m + 1))`.
+    fn fixed_ln(x: i128) -> Result<i128, Error> {
+        if x <= 0 {
+            return Err(Error::InvalidInput);
+        }
+        let mut m = x;
+        let mut k = 0i128;
+        while m >= 2 * FIXED_ONE {
+            m >>= 1;
+            k += 1;
+        }
+        while m < FIXED_ONE {
+            m <<= 1;
+            k -= 1;
+        }
+
+        let z = (m - FIXED_ONE) * FIXED_ONE / (m + FIXED_ONE);
+        let z2 = z * z / FIXED_ONE;
+        let mut term = z;
+        let mut sum = 0;
+        let mut n = 1;
+        while term != 0 {
+            sum += term / n;
+            term = term * z2 / FIXED_ONE;
+            n += 2;
+        }
+        Ok(2 * sum + k * FIXED_LN_2)
+    }
+
+    /// Exponential of a `FIXED_ONE` fixed point number, as `2^k e^r` with `r` in `[0, ln 2)`
+    /// summed as a Taylor series. Results below one unit of precision round to zero.
+    fn fixed_exp(x: i128) -> Result<i128, Error> {
+        let k = x.div_euclid(FIXED_LN_2);
+        let r = x - k * FIXED_LN_2;
+
+        let mut term = FIXED_ONE;
+        let mut sum = FIXED_ONE;
+        let mut n = 1;
+        while term != 0 {
+            term = term * r / FIXED_ONE / n;
+            sum += term;
+            n += 1;
+        }
+
+        if k >= 0 {
+            if k >= 64 {
+                return Err(Error::ArithmeticOverflow);
+            }
+            sum.checked_mul(1 << k).ok_or(Error::ArithmeticOverflow)
+        } else if k > -127 {
+            Ok(sum >> -k)
+        } else {
+            Ok(0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dex::{
+        Dex as _, DexFee, Error, PoolConfigError, PoolEntry, PoolIndex, PoolPair, PoolPairConfig,
+        PoolPrice, PoolType, RewardPool, SwapEventKind, SwapOperation, TokenAmount,
+    };
+    use crate::{Dex, COIN, MINIMUM_LIQUIDITY};
+
+    fn pool(token_a: u32, token_b: u32, reserve_a: i64, reserve_b: i64) -> PoolPair {
+        PoolPair {
+            token_a,
+            token_b,
+            commission: 0,
+            reserve_a,
+            reserve_b,
+            total_liquidity: 1000 * COIN,
+            block_commission_a: 0,
+            block_commission_b: 0,
+            pool_type: PoolType::ConstantProduct,
+            amplification: 0,
+            weight_a: 0,
+            weight_b: 0,
+            price_ab_cumulative: 0.into(),
+            price_ba_cumulative: 0.into(),
+            price_height: 0,
+            status: true,
+        }
+    }
+
+    fn stable_pool(token_a: u32, token_b: u32, reserve_a: i64, reserve_b: i64) -> PoolPair {
+        PoolPair {
+            pool_type: PoolType::StableSwap,
+            amplification: 100,
+            ..pool(token_a, token_b, reserve_a, reserve_b)
+        }
+    }
+
+    fn weighted_pool(
+        token_a: u32,
+        token_b: u32,
+        reserve_a: i64,
+        reserve_b: i64,
+        weight_a: u32,
+    ) -> PoolPair {
+        PoolPair {
+            pool_type: PoolType::Weighted,
+            weight_a,
+            weight_b: 100 - weight_a,
+            ..pool(token_a, token_b, reserve_a, reserve_b)
+        }
+    }
+
+    const MAX_PRICE: PoolPrice = PoolPrice {
+        integer: 100 * COIN,
+        fraction: 0,
+    };
+
+    const NO_FEE: DexFee = DexFee {
+        in_pct_ab: 0,
+        out_pct_ab: 0,
+        in_pct_ba: 0,
+        out_pct_ba: 0,
+    };
+
+    #[test]
+    fn test_composite_swap_matches_sequential_swaps() {
+        // Every hop takes its dex fees and pool commission like a single swap does
+        let pools = vec![
+            PoolPair {
+                commission: 2_000_000,
+                ..pool(1, 2, 200 * COIN, 1000 * COIN)
+            },
+            pool(3, 2, 5000 * COIN, 1000 * COIN),
+        ];
+        let fees = vec![
+            DexFee {
+                in_pct_ab: 1_000_000,
+                ..NO_FEE
+            },
+            DexFee {
+                out_pct_ba: 3_000_000,
+                ..NO_FEE
+            },
+        ];
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
+
+        let result =
+            Dex::composite_swap(pools.clone(), token_in, MAX_PRICE, fees.clone(), true).unwrap();
+
+        let first = Dex::swap(pools[0], token_in, MAX_PRICE, None, fees[0], true).unwrap();
+        let second_in = TokenAmount {
+            token_id: 2,
+            amount: first.slop_swap_result,
+        };
+        let second = Dex::swap(pools[1], second_in, MAX_PRICE, None, fees[1], true).unwrap();
+        assert!(first.dex_fee_in > 0 && second.dex_fee_out > 0);
+        assert_eq!(first.pool_pair.block_commission_a, 1_980_000);
+
+        assert_eq!(result.slop_swap_result, second.slop_swap_result);
+        for (pool_pair, swapped) in result.pool_pairs.iter().zip([first, second]) {
+            let swapped = swapped.pool_pair;
+            assert_eq!(pool_pair.reserve_a, swapped.reserve_a);
+            assert_eq!(pool_pair.reserve_b, swapped.reserve_b);
+            assert_eq!(pool_pair.block_commission_a, swapped.block_commission_a);
+            assert_eq!(pool_pair.block_commission_b, swapped.block_commission_b);
+        }
+
+        // A quote is the output of the same swap
+        let quote = Dex::quote(pools[0], token_in, fees[0], true).unwrap();
+        assert_eq!(quote, first.slop_swap_result);
+    }
+
+    #[test]
+    fn test_composite_swap_max_price() {
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
+        let at_parity = PoolPrice {
+            integer: 1,
+            fraction: 0,
+        };
+
+        // The second hop slips past parity on its own, but the route as a whole pays about 0.1
+        let pools = vec![
+            pool(1, 2, 100 * COIN, 1000 * COIN),
+            pool(2, 3, 1000 * COIN, 1000 * COIN),
+        ];
+        let result = Dex::composite_swap(pools, token_in, at_parity, vec![NO_FEE; 2], true);
+        assert!(result.unwrap().slop_swap_result > 9 * COIN);
+
+        // Each hop pays about 2, which the limit allows, but the route pays about 4
+        let limit = PoolPrice {
+            integer: 3,
+            fraction: 0,
+        };
+        let pools = vec![
+            pool(1, 2, 2000 * COIN, 1000 * COIN),
+            pool(2, 3, 2000 * COIN, 1000 * COIN),
+        ];
+        let result = Dex::composite_swap(pools.clone(), token_in, limit, vec![NO_FEE; 2], true);
+        match result {
+            Err(Error::PriceHigherThanIndex(limit)) => assert_eq!(limit.price.integer, 4),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        // A pool whose spot price is above the limit fails before its hop
+        let limit = PoolPrice {
+            integer: 1,
+            fraction: 50_000_000,
         };
-        let ptr4 = RET_AREA.as_mut_ptr() as i32;
-        *((ptr4 + 72) as *mut i64) = result3_9;
-        *((ptr4 + 64) as *mut i64) = result3_8;
-        *((ptr4 + 56) as *mut i64) = result3_7;
-        *((ptr4 + 48) as *mut i64) = result3_6;
-        *((ptr4 + 40) as *mut i64) = result3_5;
-        *((ptr4 + 32) as *mut i64) = result3_4;
-        *((ptr4 + 24) as *mut i32) = result3_3;
-        *((ptr4 + 16) as *mut i32) = result3_2;
-        *((ptr4 + 8) as *mut i32) = result3_1;
-        *((ptr4) as *mut i32) = result3_0;
-        ptr4
+        let result = Dex::composite_swap(pools, token_in, limit, vec![NO_FEE; 2], true);
+        match result {
+            Err(Error::PriceHigherThanIndex(limit)) => assert_eq!(limit.price.integer, 2),
+            other => panic!("unexpected result {:?}", other),
+        }
     }
-    pub trait Dex {
-        fn swap(
-            poolpair: PoolPair,
-            token_in: TokenAmount,
-            max_price: PoolPrice,
-            post_bayfront_gardens: bool,
-        ) -> Result<SwapResult, Error>;
+
+    #[test]
+    fn test_composite_swap_empty_path() {
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
+        let result = Dex::composite_swap(vec![], token_in, MAX_PRICE, vec![], true);
+        assert!(matches!(result, Err(Error::InvalidInput)));
     }
-    static mut RET_AREA: [i64; 10] = [0; 10];
-}
 
-impl Add for TokenAmount {
-    type Output = TokenAmount;
+    #[test]
+    fn test_best_path_picks_highest_output() {
+        // Direct 1 -> 3 pool is shallow, routing through token 2 yields more
+        let pools = vec![
+            pool(1, 3, 10 * COIN, 10 * COIN),
+            pool(1, 2, 200 * COIN, 1000 * COIN),
+            pool(2, 3, 1000 * COIN, 1000 * COIN),
+            pool(4, 5, 1000 * COIN, 1000 * COIN),
+        ];
+        let fees = vec![NO_FEE; pools.len()];
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
 
-    fn add(self, rhs: Self) -> Self::Output {
-        TokenAmount {
-            token_id: self.token_id,
-            amount: self.amount.saturating_add(rhs.amount),
-        }
+        let result =
+            Dex::best_path(pools.clone(), token_in, 3, 3, MAX_PRICE, fees.clone(), true).unwrap();
+        assert_eq!(result.best.path, vec![1, 2]);
+        assert_eq!(result.alternatives.len(), 1);
+        assert_eq!(result.alternatives[0].path, vec![0]);
+        assert!(result.best.slop_swap_result > result.alternatives[0].slop_swap_result);
+
+        let composite = Dex::composite_swap(
+            vec![pools[1], pools[2]],
+            token_in,
+            MAX_PRICE,
+            vec![NO_FEE; 2],
+            true,
+        );
+        assert_eq!(
+            composite.unwrap().slop_swap_result,
+            result.best.slop_swap_result
+        );
+
+        let direct_only =
+            Dex::best_path(pools.clone(), token_in, 3, 1, MAX_PRICE, fees.clone(), true).unwrap();
+        assert_eq!(direct_only.best.path, vec![0]);
+        assert!(direct_only.alternatives.is_empty());
+
+        let unreachable = Dex::best_path(pools, token_in, 5, 3, MAX_PRICE, fees, true);
+        assert!(matches!(unreachable, Err(Error::NotFound)));
     }
-}
 
-impl Sub for TokenAmount {
-    type Output = TokenAmount;
+    #[test]
+    fn test_swap_min_output() {
+        let poolpair = pool(1, 2, 200 * COIN, 1000 * COIN);
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        TokenAmount {
-            token_id: self.token_id,
-            amount: self.amount.saturating_sub(rhs.amount),
-        }
+        let result = Dex::swap(poolpair, token_in, MAX_PRICE, None, NO_FEE, true).unwrap();
+        let exact = Dex::swap(
+            poolpair,
+            token_in,
+            MAX_PRICE,
+            Some(result.slop_swap_result),
+            NO_FEE,
+            true,
+        );
+        assert_eq!(exact.unwrap().slop_swap_result, result.slop_swap_result);
+
+        let above = Dex::swap(
+            poolpair,
+            token_in,
+            MAX_PRICE,
+            Some(result.slop_swap_result + 1),
+            NO_FEE,
+            true,
+        );
+        assert!(matches!(above, Err(Error::OutputBelowMinimum(_))));
     }
-}
 
-impl PartialEq for TokenAmount {
-    fn eq(&self, other: &Self) -> bool {
-        self.token_id.eq(&other.token_id)
+    #[test]
+    fn test_swap_max_price_fraction() {
+        // Spot price of token 1 is 0.2 and the trade slips it slightly above that
+        let poolpair = pool(1, 2, 200 * COIN, 1000 * COIN);
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
+
+        // Only the pre-trade spot price is held to the limit by default, like the node does
+        let at_spot = PoolPrice {
+            integer: 0,
+            fraction: 20_000_000,
+        };
+        assert!(Dex::swap(poolpair, token_in, at_spot, None, NO_FEE, true).is_ok());
+
+        // A minimum output also holds the realized price to it
+        let result = Dex::swap(poolpair, token_in, at_spot, Some(1), NO_FEE, true);
+        assert!(matches!(result, Err(Error::PriceHigherThanIndex(_))));
+
+        let with_slippage = PoolPrice {
+            integer: 0,
+            fraction: 21_000_000,
+        };
+        assert!(Dex::swap(poolpair, token_in, with_slippage, Some(1), NO_FEE, true).is_ok());
+
+        let below_spot = PoolPrice {
+            integer: 0,
+            fraction: 19_000_000,
+        };
+        let result = Dex::composite_swap(vec![poolpair], token_in, below_spot, vec![NO_FEE], true);
+        assert!(matches!(result, Err(Error::PriceHigherThanIndex(_))));
     }
-}
 
-struct Dex {}
+    #[test]
+    fn test_swap_token_mismatch() {
+        let pools = vec![
+            pool(1, 2, 200 * COIN, 1000 * COIN),
+            pool(3, 4, 1000 * COIN, 1000 * COIN),
+        ];
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
 
-impl dex::Dex for Dex {
-    fn swap(
-        poolpair: PoolPair,
-        token_in: TokenAmount,
-        max_price: PoolPrice,
-        post_bayfront_gardens: bool,
-    ) -> Result<SwapResult, Error> {
-        let mut poolpair = poolpair;
-        assert!(
-            !(token_in.token_id != poolpair.token_a && token_in.token_id != poolpair.token_b),
-            "Error, input token ID ({}) doesn't match pool tokens ({})",
-            poolpair.token_a,
-            poolpair.token_b
+        let result = Dex::composite_swap(
+            pools.clone(),
+            token_in,
+            MAX_PRICE,
+            vec![NO_FEE; pools.len()],
+            true,
         );
-        if token_in.amount <= 0 {
-            return Err(Error::InvalidInput);
+        assert!(matches!(result, Err(Error::TokenMismatch(_))));
+
+        let token_in = TokenAmount {
+            token_id: 3,
+            amount: COIN,
+        };
+        let result = Dex::swap(pools[0], token_in, MAX_PRICE, None, NO_FEE, true);
+        assert!(matches!(result, Err(Error::TokenMismatch(_))));
+    }
+
+    #[test]
+    fn test_slop_swap() {
+        let (mut pool_from, mut pool_to) = (200 * COIN, 1000 * COIN);
+        let swapped = Dex::slop_swap(COIN, &mut pool_from, &mut pool_to, false, None).unwrap();
+        assert_eq!(swapped, 497_512_438);
+        assert_eq!(pool_from, 201 * COIN);
+        assert_eq!(pool_to, 1000 * COIN - swapped);
+
+        let (mut pool_from, mut pool_to) = (200 * COIN, 1000 * COIN);
+        let result = Dex::slop_swap(-1, &mut pool_from, &mut pool_to, true, None);
+        assert!(matches!(result, Err(Error::InvalidInput)));
+
+        let (mut pool_from, mut pool_to) = (i64::MAX - 1, 1000 * COIN);
+        let result = Dex::slop_swap(2, &mut pool_from, &mut pool_to, true, None);
+        assert!(matches!(result, Err(Error::ArithmeticOverflow)));
+
+        // Chunks of nothing would never finish
+        let (mut pool_from, mut pool_to) = (999, 1000 * COIN);
+        let result = Dex::slop_swap(1, &mut pool_from, &mut pool_to, true, None);
+        assert!(matches!(result, Err(Error::LackOfLiquidity(_))));
+    }
+
+    #[test]
+    fn test_slop_swap_pre_bayfront_matches_node() {
+        // (from reserve, to reserve, input, output) as computed by the node's `CPoolPair::Swap`
+        let vectors = [
+            (1_000 * COIN, 500 * COIN, 10 * COIN, 495_049_505),
+            (200 * COIN, 1_000 * COIN, COIN, 497_512_438),
+            (123_456_789_012, 987_654_321, 55_555_555, 444_245),
+        ];
+        for (from, to, input, output) in vectors {
+            let (mut pool_from, mut pool_to) = (from, to);
+            let swapped = Dex::slop_swap(input, &mut pool_from, &mut pool_to, false, None);
+            assert_eq!(swapped.unwrap(), output);
+            assert_eq!((pool_from, pool_to), (from + input, to - output));
         }
+    }
 
-        let forward = token_in.token_id == poolpair.token_a;
+    #[test]
+    fn test_slop_swap_chunked_u128_matches_reference() {
+        let check = |unswapped: i64, pool_from: i64, pool_to: i64| {
+            let (mut fast_from, mut fast_to) = (pool_from, pool_to);
+            let (mut ref_from, mut ref_to) = (pool_from, pool_to);
+            let fast = Dex::slop_swap_chunked_u128(unswapped, &mut fast_from, &mut fast_to, None);
+            let reference =
+                Dex::slop_swap_chunked_reference(unswapped, &mut ref_from, &mut ref_to, None);
+            assert_eq!(
+                (fast.unwrap(), fast_from, fast_to),
+                (reference.unwrap(), ref_from, ref_to),
+                "{} into {}/{}",
+                unswapped,
+                pool_from,
+                pool_to
+            );
 
-        if poolpair.reserve_a < SLOPE_SWAP_RATE || poolpair.reserve_b < SLOPE_SWAP_RATE {
-            return Err(Error::LackOfLiquidity);
+            // Recorded steps match one for one, including chunks that pay nothing
+            let (mut fast_steps, mut ref_steps) = (vec![], vec![]);
+            let (mut fast_from, mut fast_to) = (pool_from, pool_to);
+            let (mut ref_from, mut ref_to) = (pool_from, pool_to);
+            Dex::slop_swap_chunked_u128(
+                unswapped,
+                &mut fast_from,
+                &mut fast_to,
+                Some(&mut fast_steps),
+            )
+            .unwrap();
+            Dex::slop_swap_chunked_reference(
+                unswapped,
+                &mut ref_from,
+                &mut ref_to,
+                Some(&mut ref_steps),
+            )
+            .unwrap();
+            let key = |steps: &[crate::dex::SwapStep]| {
+                steps
+                    .iter()
+                    .map(|s| (s.step_from, s.step_to, s.reserve_from, s.reserve_to))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(key(&fast_steps), key(&ref_steps));
+        };
+
+        // Every input around the chunk boundaries of the smallest pools
+        for pool_from in [1000, 1001, 1999, 2000, 2001, 12_345] {
+            for pool_to in [0, 1, 2, 999, 1000, 1001, 54_321, 7 * COIN] {
+                for unswapped in (0..=30).chain([999, 1000, 1001, 1999, 2000, 2001, 3 * pool_from])
+                {
+                    check(unswapped, pool_from, pool_to);
+                }
+            }
+        }
+
+        // Chunks that stop paying out part way, and from the start
+        check(5 * COIN, COIN, 1_000);
+        check(COIN, i64::MAX / 4, 5);
+
+        // Swaps far larger than the from reserve, where runs of equal payouts get long
+        for pool_to in [1, 999, 1000, 123_456, 10 * COIN, 1_000 * COIN] {
+            for pool_from in [1000, 7_919, COIN] {
+                for times in [10, 997] {
+                    check(times * pool_from + times, pool_from, pool_to);
+                }
+            }
         }
 
-        let reserve_a = U256::from(poolpair.reserve_a);
-        let reserve_b = U256::from(poolpair.reserve_b);
+        // Random pools and amounts across the whole range of reserves
+        let mut x: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        };
+        for _ in 0..300 {
+            let pool_from = (next() % (1 << (10 + next() % 52))) as i64 + 1000;
+            let pool_to = (next() % (1 << (next() % 62))) as i64;
+            let max_in = (pool_from as u64 * 3).min((i64::MAX - pool_from) as u64);
+            let unswapped = (next() % (max_in + 1)) as i64;
+            check(unswapped, pool_from, pool_to);
+        }
+    }
 
-        let max_prince_256 = U256::from(max_price.integer);
-        let price_ab = reserve_a * PRECISION / reserve_b;
-        let price_ba = reserve_b * PRECISION / reserve_a;
+    /// Times the `u128` loop against the `U256` reference on thousand chunk swaps, and on swaps
+    /// a hundred times the from reserve where runs of equal payouts form. Run with
+    /// `cargo test --release -- --ignored --nocapture bench_slop_swap_chunked`.
+    #[test]
+    #[ignore]
+    fn bench_slop_swap_chunked() {
+        use std::time::Instant;
 
-        let cur_price = if forward { price_ab } else { price_ba };
+        type ChunkedSwap = fn(
+            i64,
+            &mut i64,
+            &mut i64,
+            Option<&mut Vec<crate::dex::SwapStep>>,
+        ) -> Result<i64, Error>;
+        let run = |swap: ChunkedSwap, swaps: i64, unswapped: i64, pool_to: i64| {
+            let instant = Instant::now();
+            let mut total = 0;
+            for i in 0..swaps {
+                let (mut pool_from, mut pool_to) = (1_000 * COIN + i, pool_to);
+                total += swap(unswapped, &mut pool_from, &mut pool_to, None).unwrap();
+            }
+            (total, instant.elapsed())
+        };
 
-        if cur_price > max_prince_256 {
-            return Err(Error::PriceHigherThanIndex);
+        for (name, swaps, unswapped, pool_to) in [
+            (
+                "1000 swaps of 1000 chunks",
+                1_000,
+                1_000 * COIN,
+                5_000 * COIN,
+            ),
+            ("10 swaps of 100000 chunks", 10, 100_000 * COIN, 10 * COIN),
+        ] {
+            let (fast, fast_time) = run(Dex::slop_swap_chunked_u128, swaps, unswapped, pool_to);
+            let (reference, reference_time) =
+                run(Dex::slop_swap_chunked_reference, swaps, unswapped, pool_to);
+            assert_eq!(fast, reference);
+            println!(
+                "{}: u128 {:?}, U256 {:?} ({:.1}x)",
+                name,
+                fast_time,
+                reference_time,
+                reference_time.as_secs_f64() / fast_time.as_secs_f64()
+            );
         }
+    }
 
-        let check_res = if forward {
-            poolpair.reserve_a.checked_add(token_in.amount)
-        } else {
-            poolpair.reserve_b.checked_add(token_in.amount)
+    #[test]
+    fn test_invariants_hold() {
+        let old = (200 * COIN, 1000 * COIN);
+        assert!(Dex::invariants_hold(
+            COIN,
+            497_512_438,
+            old,
+            (201 * COIN, 99_502_487_562)
+        ));
+
+        // Paying out at a better rate than the spot price
+        assert!(!Dex::invariants_hold(
+            COIN,
+            5 * COIN + 1,
+            old,
+            (201 * COIN, 995 * COIN - 1)
+        ));
+
+        // Reserves not moved by the traded amounts
+        assert!(!Dex::invariants_hold(
+            COIN,
+            COIN,
+            old,
+            (201 * COIN, 1000 * COIN)
+        ));
+
+        // Draining the pool
+        assert!(!Dex::invariants_hold(
+            COIN,
+            1000 * COIN,
+            old,
+            (201 * COIN, 0)
+        ));
+    }
+
+    #[test]
+    fn test_error_details() {
+        let poolpair = pool(1, 2, 200 * COIN, 1000 * COIN);
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
         };
-        if check_res.is_none() {
-            return Err(Error::PoolReserveOverflow);
+        let max_price = PoolPrice {
+            integer: 0,
+            fraction: 10_000_000,
+        };
+
+        match Dex::swap(poolpair, token_in, max_price, None, NO_FEE, true) {
+            Err(Error::PriceHigherThanIndex(limit)) => {
+                assert_eq!(limit.price.integer, 0);
+                assert_eq!(limit.price.fraction, 20_000_000);
+                assert_eq!(limit.max_price.fraction, 10_000_000);
+            }
+            other => panic!("unexpected result {:?}", other),
         }
 
-        let result = if forward {
-            Dex::slop_swap(
-                token_in.amount,
-                &mut poolpair.reserve_a,
-                &mut poolpair.reserve_b,
-                post_bayfront_gardens,
-            )
-        } else {
-            Dex::slop_swap(
-                token_in.amount,
-                &mut poolpair.reserve_b,
-                &mut poolpair.reserve_a,
-                post_bayfront_gardens,
-            )
+        let token_in = TokenAmount {
+            token_id: 3,
+            amount: COIN,
         };
+        match Dex::swap(poolpair, token_in, MAX_PRICE, None, NO_FEE, true) {
+            Err(Error::TokenMismatch(mismatch)) => {
+                assert_eq!(mismatch.token_id, 3);
+                assert_eq!((mismatch.token_a, mismatch.token_b), (1, 2));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
 
-        Ok(SwapResult {
-            pool_pair: poolpair,
-            slop_swap_result: result,
-        })
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
+        match Dex::swap(
+            pool(1, 2, 999, COIN),
+            token_in,
+            MAX_PRICE,
+            None,
+            NO_FEE,
+            true,
+        ) {
+            Err(Error::LackOfLiquidity(reserves)) => {
+                assert_eq!((reserves.reserve_a, reserves.reserve_b), (999, COIN));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
     }
-}
 
-impl Dex {
-    pub fn slop_swap(
-        unswapped: i64,
-        pool_from: &mut i64,
-        pool_to: &mut i64,
-        post_bayfront_gardens: bool,
-    ) -> i64 {
-        let mut unswapped = unswapped;
+    #[test]
+    fn test_stable_swap_slippage() {
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: 10_000 * COIN,
+        };
+        let stable = Dex::quote(
+            stable_pool(1, 2, 100_000 * COIN, 100_000 * COIN),
+            token_in,
+            NO_FEE,
+            true,
+        )
+        .unwrap();
+        let constant = Dex::quote(
+            pool(1, 2, 100_000 * COIN, 100_000 * COIN),
+            token_in,
+            NO_FEE,
+            true,
+        )
+        .unwrap();
 
-        assert!(unswapped >= 0);
-        assert!(unswapped.checked_add(*pool_from).is_some());
+        // Close to one to one at balance, and never more than was put in
+        assert!(stable > constant);
+        assert!(stable < token_in.amount);
+        assert!(stable > token_in.amount * 999 / 1000);
 
-        let mut pool_f = U256::from(*pool_from);
-        let mut pool_t = U256::from(*pool_to);
+        // Quote does not need a price limit, but matches the swap
+        let swapped = Dex::swap(
+            stable_pool(1, 2, 100_000 * COIN, 100_000 * COIN),
+            token_in,
+            MAX_PRICE,
+            None,
+            NO_FEE,
+            true,
+        )
+        .unwrap();
+        assert_eq!(swapped.slop_swap_result, stable);
+        assert_eq!(swapped.pool_pair.reserve_b, 100_000 * COIN - stable);
+    }
 
-        let mut swapped: U256 = 0.into();
+    #[test]
+    fn test_stable_swap_invariant() {
+        let mut pool_from = 3_000 * COIN;
+        let mut pool_to = 1_000 * COIN;
+        let result = Dex::stable_swap(500 * COIN, &mut pool_from, &mut pool_to, 50).unwrap();
+        assert_eq!(pool_from, 3_500 * COIN);
+        assert_eq!(pool_to, 1_000 * COIN - result);
+        assert!(Dex::stable_invariants_hold(
+            50,
+            500 * COIN,
+            result,
+            (3_000 * COIN, 1_000 * COIN),
+            (pool_from, pool_to)
+        )
+        .unwrap());
 
-        if post_bayfront_gardens {
-            let chunk = if *pool_from / SLOPE_SWAP_RATE < unswapped {
-                *pool_from / SLOPE_SWAP_RATE
-            } else {
-                unswapped
+        // A swap of nothing pays nothing
+        let mut pool_from = 3_000 * COIN;
+        let mut pool_to = 1_000 * COIN;
+        assert_eq!(
+            Dex::stable_swap(0, &mut pool_from, &mut pool_to, 50).unwrap(),
+            0
+        );
+
+        let mut invalid = stable_pool(1, 2, 1_000 * COIN, 1_000 * COIN);
+        invalid.amplification = 0;
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: COIN,
+        };
+        assert!(matches!(
+            Dex::quote(invalid, token_in, NO_FEE, true),
+            Err(Error::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_add_remove_liquidity() {
+        for poolpair in [
+            pool(1, 2, 0, 0),
+            stable_pool(1, 2, 0, 0),
+            weighted_pool(1, 2, 0, 0, 50),
+        ] {
+            let empty = PoolPair {
+                total_liquidity: 0,
+                ..poolpair
             };
-            while unswapped > 0 {
-                let step_from = (chunk).min(unswapped);
-                let step_from_256 = U256::from(step_from);
-                let step_to = pool_t * step_from_256 / pool_f;
-                pool_f += step_from_256;
-                pool_t -= step_to;
+            let added = Dex::add_liquidity(empty, 1_000 * COIN, 1_000 * COIN).unwrap();
+            // Geometric mean of the amounts, or their sum for a balanced stable pool. The
+            // weighted mean is computed in fixed point and may round down by a unit.
+            let value = match poolpair.pool_type {
+                PoolType::StableSwap => 2_000 * COIN,
+                _ => 1_000 * COIN,
+            };
+            assert!(added.liquidity <= value - MINIMUM_LIQUIDITY);
+            assert!(added.liquidity >= value - MINIMUM_LIQUIDITY - 1);
+            assert_eq!(
+                added.pool_pair.total_liquidity,
+                added.liquidity + MINIMUM_LIQUIDITY
+            );
 
-                unswapped -= step_from;
-                swapped += step_to;
-            }
-        } else {
-            let unswapped_a = U256::from(unswapped);
-            swapped = pool_t - (pool_t * pool_f / (pool_f * unswapped));
-            pool_f += unswapped_a;
-            pool_t -= swapped;
-        }
-        *pool_from = pool_f.as_u64() as i64;
-        *pool_to = pool_t.as_u64() as i64;
-        swapped.as_u64() as i64
+            let more = Dex::add_liquidity(added.pool_pair, 500 * COIN, 500 * COIN).unwrap();
+            assert_eq!(more.liquidity, added.pool_pair.total_liquidity / 2);
+
+            let removed = Dex::remove_liquidity(more.pool_pair, more.liquidity).unwrap();
+            assert!(removed.amount_a <= 500 * COIN && removed.amount_a >= 500 * COIN - 1);
+            assert_eq!(
+                removed.pool_pair.total_liquidity,
+                added.pool_pair.total_liquidity
+            );
+
+            assert!(matches!(
+                Dex::remove_liquidity(removed.pool_pair, removed.pool_pair.total_liquidity),
+                Err(Error::InvalidInput)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_weighted_swap() {
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: 100 * COIN,
+        };
+
+        // A 50/50 weighted pool trades like a constant-product pool, give or take rounding
+        let weighted = Dex::quote(
+            weighted_pool(1, 2, 1_000 * COIN, 1_000 * COIN, 50),
+            token_in,
+            NO_FEE,
+            false,
+        )
+        .unwrap();
+        let constant = Dex::quote(
+            pool(1, 2, 1_000 * COIN, 1_000 * COIN),
+            token_in,
+            NO_FEE,
+            false,
+        )
+        .unwrap();
+        assert!(weighted <= constant);
+        assert!(weighted >= constant - 10);
+
+        // Selling the heavy token of an 80/20 pool pays out four times as much at the margin
+        let swapped = Dex::swap(
+            weighted_pool(1, 2, 4_000 * COIN, 1_000 * COIN, 80),
+            token_in,
+            MAX_PRICE,
+            None,
+            NO_FEE,
+            true,
+        )
+        .unwrap();
+        let result = swapped.slop_swap_result;
+        // 1000 * (1 - (4000 / 4100)^4)
+        assert!((9_404_935_520 - 10..=9_404_935_520).contains(&result));
+        assert_eq!(swapped.pool_pair.reserve_a, 4_100 * COIN);
+        assert_eq!(swapped.pool_pair.reserve_b, 1_000 * COIN - result);
+        assert!(Dex::weighted_invariants_hold(
+            (80, 20),
+            token_in.amount,
+            result,
+            (4_000 * COIN, 1_000 * COIN),
+            (4_100 * COIN, 1_000 * COIN - result)
+        ));
+
+        // The spot price of an 80/20 pool at 4000/1000 is one to one
+        let limit = PoolPrice {
+            integer: 0,
+            fraction: COIN / 2,
+        };
+        assert!(matches!(
+            Dex::swap(
+                weighted_pool(1, 2, 4_000 * COIN, 1_000 * COIN, 80),
+                token_in,
+                limit,
+                None,
+                NO_FEE,
+                true
+            ),
+            Err(Error::PriceHigherThanIndex(_))
+        ));
+
+        let invalid = weighted_pool(1, 2, 1_000 * COIN, 1_000 * COIN, 100);
+        assert!(matches!(
+            Dex::quote(invalid, token_in, NO_FEE, true),
+            Err(Error::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_twap() {
+        let start = Dex::accumulate_price(pool(1, 2, 1_000 * COIN, 2_000 * COIN), 100).unwrap();
+        // 100 blocks since height 0 at a price of 0.5
+        assert_eq!(
+            u128::from(start.price_ab_cumulative),
+            100 * COIN as u128 / 2
+        );
+        assert_eq!(start.price_height, 100);
+
+        // 10 blocks at 0.5, then a swap moves the price for another 30 blocks
+        let before_swap = Dex::accumulate_price(start, 110).unwrap();
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: 1_000 * COIN,
+        };
+        let swapped = Dex::swap(before_swap, token_in, MAX_PRICE, None, NO_FEE, false).unwrap();
+        let end = Dex::accumulate_price(swapped.pool_pair, 140).unwrap();
+        assert_eq!(end.reserve_a, 2_000 * COIN);
+        assert_eq!(end.reserve_b, 1_000 * COIN);
+
+        let twap = Dex::twap(start, end).unwrap();
+        // (10 * 0.5 + 30 * 2) / 40
+        assert_eq!(twap.price_ab.integer, 1);
+        assert_eq!(twap.price_ab.fraction, 62_500_000);
+        // (10 * 2 + 30 * 0.5) / 40
+        assert_eq!(twap.price_ba.integer, 0);
+        assert_eq!(twap.price_ba.fraction, 87_500_000);
+
+        // Accumulators that wrapped around between the snapshots still average correctly
+        let wrapped_start = PoolPair {
+            price_ab_cumulative: (u128::MAX - 4 * COIN as u128).into(),
+            ..start
+        };
+        let wrapped_end = Dex::accumulate_price(wrapped_start, 110).unwrap();
+        let twap = Dex::twap(wrapped_start, wrapped_end).unwrap();
+        assert_eq!(twap.price_ab.fraction, 50_000_000);
+
+        assert!(matches!(
+            Dex::accumulate_price(end, 139),
+            Err(Error::InvalidInput)
+        ));
+        assert!(matches!(Dex::twap(end, start), Err(Error::InvalidInput)));
+
+        // A balanced stable pool trades one to one at the margin
+        let stable =
+            Dex::accumulate_price(stable_pool(1, 2, 1_000 * COIN, 1_000 * COIN), 1).unwrap();
+        let stable_end = Dex::accumulate_price(stable, 11).unwrap();
+        let twap = Dex::twap(stable, stable_end).unwrap();
+        assert_eq!(twap.price_ab.integer, 1);
+        assert_eq!(twap.price_ab.fraction, 0);
+    }
+
+    #[test]
+    fn test_distribute_claim() {
+        let poolpair = pool(1, 2, 1_000 * COIN, 1_000 * COIN);
+        let reward = |token_id, reward_per_block| RewardPool {
+            token_id,
+            reward_per_block,
+            reward_per_share: 0.into(),
+            last_height: 100,
+        };
+        let rewards = vec![reward(0, 10 * COIN), reward(3, 3)];
+
+        let rewards = Dex::distribute(poolpair, rewards, 110).unwrap();
+        assert!(rewards.iter().all(|reward| reward.last_height == 110));
+
+        // An owner of a quarter of the pool gets a quarter of 10 blocks of rewards, rounded down
+        let owner = poolpair.total_liquidity / 4;
+        let claimed = Dex::claim(poolpair, rewards.clone(), owner, vec![]).unwrap();
+        assert_eq!(claimed.amounts[0].token_id, 0);
+        assert_eq!(claimed.amounts[0].amount, 25 * COIN);
+        assert_eq!(claimed.amounts[1].token_id, 3);
+        assert_eq!(claimed.amounts[1].amount, 7);
+
+        // Claiming again at the same height pays nothing
+        let again = Dex::claim(poolpair, rewards.clone(), owner, claimed.paid.clone()).unwrap();
+        assert!(again.amounts.iter().all(|amount| amount.amount == 0));
+
+        // All owners together are never paid more than was distributed
+        let rest = Dex::claim(
+            poolpair,
+            rewards.clone(),
+            poolpair.total_liquidity - owner,
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(
+            claimed.amounts[0].amount + rest.amounts[0].amount,
+            100 * COIN
+        );
+        assert!(claimed.amounts[1].amount + rest.amounts[1].amount <= 30);
+
+        // Nothing accrues while the pool is empty
+        let empty = PoolPair {
+            total_liquidity: 0,
+            ..poolpair
+        };
+        let idle = Dex::distribute(empty, rewards.clone(), 120).unwrap();
+        assert_eq!(
+            u128::from(idle[0].reward_per_share),
+            u128::from(rewards[0].reward_per_share)
+        );
+        assert_eq!(idle[0].last_height, 120);
+
+        assert!(matches!(
+            Dex::distribute(poolpair, rewards.clone(), 109),
+            Err(Error::InvalidInput)
+        ));
+        assert!(matches!(
+            Dex::claim(poolpair, rewards, poolpair.total_liquidity + 1, vec![]),
+            Err(Error::InvalidInput)
+        ));
+
+        // A reward per share that grows past 128 bits in one go fails instead of being truncated
+        let dust = PoolPair {
+            total_liquidity: 1,
+            ..poolpair
+        };
+        assert!(matches!(
+            Dex::distribute(dust, vec![reward(0, i64::MAX)], 200),
+            Err(Error::ArithmeticOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_dex_stabilization_fee() {
+        // Same vectors as ain-core's `calc_dex_fee` with the default coefficient of 1.8
+        let coefficient = ain_coin::DEFAULT_DEX_FEE_COEFFICIENT.0;
+        let cases = [
+            (510, 0),
+            (500, 0),
+            (490, 589517),
+            (250, 15829218),
+            (100, 26505381),
+        ];
+        for (algo_dusd, fee) in cases {
+            assert_eq!(
+                Dex::dex_stabilization_fee(algo_dusd, 1000, coefficient).unwrap(),
+                fee
+            );
+        }
+
+        assert!(matches!(
+            Dex::dex_stabilization_fee(100, 0, coefficient),
+            Err(Error::InvalidInput)
+        ));
+        assert!(matches!(
+            Dex::dex_stabilization_fee(-1, 1000, coefficient),
+            Err(Error::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_swap_dex_fee() {
+        let poolpair = pool(1, 2, 1_000 * COIN, 1_000 * COIN);
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: 100 * COIN,
+        };
+        // 5% in and 2% out from A to B, nothing the other way
+        let dex_fee = DexFee {
+            in_pct_ab: 5_000_000,
+            out_pct_ab: 2_000_000,
+            ..NO_FEE
+        };
+
+        let swapped = Dex::swap(poolpair, token_in, MAX_PRICE, None, dex_fee, false).unwrap();
+        assert_eq!(swapped.dex_fee_in, 5 * COIN);
+        assert_eq!(swapped.pool_pair.reserve_a, 1_095 * COIN);
+
+        // The fee out is taken from what the pool paid out for the input net of the fee in
+        let before_fee_out = Dex::quote(
+            poolpair,
+            TokenAmount {
+                token_id: 1,
+                amount: 95 * COIN,
+            },
+            NO_FEE,
+            false,
+        )
+        .unwrap();
+        assert_eq!(swapped.pool_pair.reserve_b, 1_000 * COIN - before_fee_out);
+        assert_eq!(swapped.dex_fee_out, before_fee_out / 50);
+        assert_eq!(
+            swapped.slop_swap_result,
+            before_fee_out - before_fee_out / 50
+        );
+
+        // The fees of the other direction do not apply
+        let reverse = TokenAmount {
+            token_id: 2,
+            amount: 100 * COIN,
+        };
+        let swapped = Dex::swap(poolpair, reverse, MAX_PRICE, None, dex_fee, false).unwrap();
+        assert_eq!(swapped.dex_fee_in, 0);
+        assert_eq!(swapped.dex_fee_out, 0);
+
+        let invalid = DexFee {
+            in_pct_ab: COIN + 1,
+            ..NO_FEE
+        };
+        assert!(matches!(
+            Dex::swap(poolpair, token_in, MAX_PRICE, None, invalid, false),
+            Err(Error::InvalidInput)
+        ));
+
+        // The amount is rejected before any fee is computed from it
+        let negative = TokenAmount {
+            token_id: 1,
+            amount: i64::MIN,
+        };
+        assert!(matches!(
+            Dex::swap(poolpair, negative, MAX_PRICE, None, dex_fee, false),
+            Err(Error::InvalidInput)
+        ));
+    }
+
+    #[test]
+    fn test_swap_trace() {
+        let poolpair = PoolPair {
+            commission: 2_000_000,
+            ..pool(1, 2, 1_000 * COIN, 1_000 * COIN)
+        };
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: 100 * COIN,
+        };
+        let dex_fee = DexFee {
+            in_pct_ab: 5_000_000,
+            out_pct_ab: 1_000_000,
+            ..NO_FEE
+        };
+
+        let traced = Dex::swap_trace(poolpair, token_in, MAX_PRICE, None, dex_fee, true).unwrap();
+        let swapped = Dex::swap(poolpair, token_in, MAX_PRICE, None, dex_fee, true).unwrap();
+        let result = traced.swap_result;
+        assert_eq!(result.slop_swap_result, swapped.slop_swap_result);
+        assert_eq!(result.pool_pair.reserve_b, swapped.pool_pair.reserve_b);
+
+        // 5% dex fee in, then 2% commission on the rest, then 1% dex fee out
+        let kinds: Vec<_> = traced.events.iter().map(|event| event.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SwapEventKind::DexFeeIn,
+                SwapEventKind::Commission,
+                SwapEventKind::DexFeeOut
+            ]
+        );
+        assert_eq!(traced.events[0].amount, 5 * COIN);
+        assert_eq!(traced.events[1].amount, 19 * COIN / 10);
+        assert_eq!(traced.events[1].token_id, 1);
+        assert_eq!(result.pool_pair.block_commission_a, 19 * COIN / 10);
+        assert_eq!(traced.events[2].token_id, 2);
+        assert_eq!(traced.events[2].amount, result.dex_fee_out);
+
+        // The steps add up to what the pool took in and paid out, and end on its reserves
+        let swap_in = token_in.amount - 5 * COIN - 19 * COIN / 10;
+        assert!(traced.steps.len() > 1);
+        assert_eq!(
+            traced.steps.iter().map(|step| step.step_from).sum::<i64>(),
+            swap_in
+        );
+        assert_eq!(
+            traced.steps.iter().map(|step| step.step_to).sum::<i64>(),
+            result.slop_swap_result + result.dex_fee_out
+        );
+        let last = traced.steps.last().unwrap();
+        assert_eq!(last.reserve_from, result.pool_pair.reserve_a);
+        assert_eq!(last.reserve_to, result.pool_pair.reserve_b);
+
+        // Before Bayfront Gardens the trade is a single step
+        let traced = Dex::swap_trace(
+            pool(1, 2, 1_000 * COIN, 1_000 * COIN),
+            token_in,
+            MAX_PRICE,
+            None,
+            NO_FEE,
+            false,
+        )
+        .unwrap();
+        assert_eq!(traced.steps.len(), 1);
+        assert_eq!(traced.steps[0].step_from, token_in.amount);
+        assert!(traced.events.is_empty());
+    }
+
+    #[test]
+    fn test_swap_batch() {
+        let poolpairs = vec![
+            pool(1, 2, 1_000 * COIN, 1_000 * COIN),
+            pool(2, 3, 500 * COIN, 2_000 * COIN),
+        ];
+        let operation = |pool_index, token_id, amount| SwapOperation {
+            pool_index,
+            token_in: TokenAmount { token_id, amount },
+            max_price: MAX_PRICE,
+            dex_fee: NO_FEE,
+        };
+        let operations = vec![
+            operation(0, 1, 10 * COIN),
+            operation(2, 1, 10 * COIN),
+            operation(1, 2, 5 * COIN),
+            operation(0, 3, 10 * COIN),
+            operation(0, 1, 20 * COIN),
+        ];
+
+        let batch = Dex::swap_batch(poolpairs.clone(), operations, true).unwrap();
+        assert_eq!(batch.results.len(), 5);
+        assert!(matches!(
+            batch.results[1],
+            Err(Error::PoolNotFound(PoolIndex { index: 2 }))
+        ));
+        assert!(matches!(batch.results[3], Err(Error::TokenMismatch(_))));
+
+        // The successful operations match applying the same swaps one after another
+        let first = Dex::swap(
+            poolpairs[0],
+            operation(0, 1, 10 * COIN).token_in,
+            MAX_PRICE,
+            None,
+            NO_FEE,
+            true,
+        )
+        .unwrap();
+        let second = Dex::swap(
+            poolpairs[1],
+            operation(1, 2, 5 * COIN).token_in,
+            MAX_PRICE,
+            None,
+            NO_FEE,
+            true,
+        )
+        .unwrap();
+        let third = Dex::swap(
+            first.pool_pair,
+            operation(0, 1, 20 * COIN).token_in,
+            MAX_PRICE,
+            None,
+            NO_FEE,
+            true,
+        )
+        .unwrap();
+        for (result, expected) in [(0, &first), (2, &second), (4, &third)] {
+            let output = batch.results[result].as_ref().unwrap();
+            assert_eq!(output.amount, expected.slop_swap_result);
+        }
+        assert_eq!(batch.pool_pairs[0].reserve_a, third.pool_pair.reserve_a);
+        assert_eq!(batch.pool_pairs[0].reserve_b, third.pool_pair.reserve_b);
+        assert_eq!(batch.pool_pairs[1].reserve_b, second.pool_pair.reserve_b);
+    }
+
+    #[test]
+    fn test_pool_registry() {
+        let entry = |pool_id, pool_pair| PoolEntry { pool_id, pool_pair };
+        let pools = vec![
+            entry(7, weighted_pool(2, 3, 500 * COIN, 2_000 * COIN, 80)),
+            entry(3, stable_pool(1, 2, 1_000 * COIN, 1_000 * COIN)),
+            entry(5, pool(1, 3, 10 * COIN, 40 * COIN)),
+        ];
+
+        // The encoding and its hash do not depend on the order pools are given in
+        let bytes = Dex::serialize_pools(pools.clone()).unwrap();
+        let mut reversed = pools.clone();
+        reversed.reverse();
+        assert_eq!(Dex::serialize_pools(reversed.clone()).unwrap(), bytes);
+        let hash = Dex::pools_hash(pools.clone()).unwrap();
+        assert_eq!(hash.len(), 32);
+        assert_eq!(Dex::pools_hash(reversed).unwrap(), hash);
+
+        let decoded = Dex::deserialize_pools(bytes.clone()).unwrap();
+        let ids: Vec<_> = decoded.iter().map(|entry| entry.pool_id).collect();
+        assert_eq!(ids, [3, 5, 7]);
+        assert_eq!(decoded[0].pool_pair.pool_type, PoolType::StableSwap);
+        assert_eq!(decoded[2].pool_pair.weight_a, 80);
+        assert_eq!(Dex::serialize_pools(decoded).unwrap(), bytes);
+
+        // Any change to a pool changes the state root
+        let mut changed = pools.clone();
+        changed[2].pool_pair.reserve_a += 1;
+        assert_ne!(Dex::pools_hash(changed).unwrap(), hash);
+
+        let mut duplicate = pools;
+        duplicate.push(entry(5, pool(1, 3, 10 * COIN, 40 * COIN)));
+        assert!(matches!(
+            Dex::serialize_pools(duplicate),
+            Err(Error::InvalidInput)
+        ));
+
+        // Only canonical encodings decode
+        for bad in [
+            bytes[..bytes.len() - 1].to_vec(),
+            [bytes.clone(), vec![0]].concat(),
+            [vec![0], bytes[1..].to_vec()].concat(),
+            Dex::serialize_pools(vec![]).unwrap()[..4].to_vec(),
+        ] {
+            assert!(matches!(
+                Dex::deserialize_pools(bad),
+                Err(Error::InvalidInput)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_create_update_pool_pair() {
+        let config = PoolPairConfig {
+            token_a: 0,
+            token_b: 2,
+            commission: 2_000_000,
+            status: true,
+            pool_type: PoolType::ConstantProduct,
+            amplification: 0,
+            weight_a: 0,
+            weight_b: 0,
+        };
+        let owner = "8defichainBurnAddressXXXXXXXdRQkSm".to_string();
+
+        let created = Dex::create_pool_pair(config, owner.clone()).unwrap();
+        assert_eq!((created.token_a, created.token_b), (0, 2));
+        assert_eq!(created.commission, 2_000_000);
+        assert_eq!(created.total_liquidity, 0);
+        assert!(created.status);
+        assert!(Dex::add_liquidity(created, 10 * COIN, 40 * COIN).is_ok());
+
+        for address in [
+            "dDWchkFJHhcUKTEKU3pEb4eAjmqhGojuVU",
+            "df1qqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5puqmww",
+            "DF1QQYPQXPQ9QCRSSZG2PVXQ6RS0ZQG3YYC5PUQMWW",
+            "df1pqqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0st63m2m",
+        ] {
+            assert!(Dex::create_pool_pair(config, address.to_string()).is_ok());
+        }
+        let invalid = |result: Result<PoolPair, Error>| match result {
+            Err(Error::InvalidPoolConfig(e)) => e,
+            _ => panic!("expected an invalid pool config"),
+        };
+        for address in [
+            "",
+            // Bitcoin addresses, bad checksums and mixed case
+            "16L5yRNPTuciSgXGHqYwn9N6NeoKqopAu",
+            "8defichainBurnAddressXXXXXXXdRQkSn",
+            "df1qqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5PUQMWW",
+            "df1pqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5puqmww",
+            "bc1qqypqxpq9qcrsszg2pvxq6rs0zqg3yyc5fcj4z3",
+        ] {
+            assert_eq!(
+                invalid(Dex::create_pool_pair(config, address.to_string())),
+                PoolConfigError::InvalidOwnerAddress
+            );
+        }
+
+        let same_token = PoolPairConfig {
+            token_b: 0,
+            ..config
+        };
+        assert_eq!(
+            invalid(Dex::create_pool_pair(same_token, owner.clone())),
+            PoolConfigError::SameToken
+        );
+        let commission = PoolPairConfig {
+            commission: COIN as u32 + 1,
+            ..config
+        };
+        assert_eq!(
+            invalid(Dex::create_pool_pair(commission, owner.clone())),
+            PoolConfigError::CommissionOutOfRange
+        );
+        let weighted = PoolPairConfig {
+            pool_type: PoolType::Weighted,
+            weight_a: 80,
+            weight_b: 30,
+            ..config
+        };
+        assert_eq!(
+            invalid(Dex::create_pool_pair(weighted, owner.clone())),
+            PoolConfigError::InvalidPoolType
+        );
+
+        // Updates only touch what is given
+        let updated = Dex::update_pool_pair(created, Some(false), None).unwrap();
+        assert!(!updated.status);
+        assert_eq!(updated.commission, created.commission);
+        let updated = Dex::update_pool_pair(updated, None, Some(0)).unwrap();
+        assert!(!updated.status);
+        assert_eq!(updated.commission, 0);
+        assert_eq!(
+            invalid(Dex::update_pool_pair(created, None, Some(COIN as u32 + 1))),
+            PoolConfigError::CommissionOutOfRange
+        );
+
+        // A disabled pool refuses swaps until it is enabled again
+        let funded = Dex::add_liquidity(created, 10 * COIN, 40 * COIN)
+            .unwrap()
+            .pool_pair;
+        let token_in = TokenAmount {
+            token_id: 0,
+            amount: COIN,
+        };
+        let disabled = Dex::update_pool_pair(funded, Some(false), None).unwrap();
+        assert!(matches!(
+            Dex::swap(disabled, token_in, MAX_PRICE, None, NO_FEE, true),
+            Err(Error::PoolDisabled)
+        ));
+        let enabled = Dex::update_pool_pair(disabled, Some(true), None).unwrap();
+        assert!(Dex::swap(enabled, token_in, MAX_PRICE, None, NO_FEE, true).is_ok());
     }
 }
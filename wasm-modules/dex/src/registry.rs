@@ -0,0 +1,162 @@
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use crate::dex::{CumulativePrice, DctId, Error, PoolEntry, PoolPair, PoolType};
+
+/// Bumped whenever the encoding of a pool pair changes
+const FORMAT_VERSION: u8 = 1;
+
+/// Pool pairs keyed by pool id.
+///
+/// The canonical encoding is the format version, the pool count as a little endian u32, then
+/// every pool in ascending id order: its id followed by the pool pair fields in the order they
+/// are declared in `dex.wit`, all little endian. A given pool set has exactly one encoding, so
+/// its hash can be persisted and compared as a state root.
+#[derive(Default)]
+pub struct PoolRegistry {
+    pools: BTreeMap<DctId, PoolPair>,
+}
+
+impl PoolRegistry {
+    pub fn from_entries(entries: Vec<PoolEntry>) -> Result<Self, Error> {
+        let mut registry = PoolRegistry::default();
+        for entry in entries {
+            if registry
+                .pools
+                .insert(entry.pool_id, entry.pool_pair)
+                .is_some()
+            {
+                return Err(Error::InvalidInput);
+            }
+        }
+        Ok(registry)
+    }
+
+    pub fn entries(&self) -> Vec<PoolEntry> {
+        self.pools
+            .iter()
+            .map(|(&pool_id, &pool_pair)| PoolEntry { pool_id, pool_pair })
+            .collect()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![FORMAT_VERSION];
+        bytes.extend((self.pools.len() as u32).to_le_bytes());
+        for (pool_id, pool) in &self.pools {
+            bytes.extend(pool_id.to_le_bytes());
+            bytes.extend(pool.token_a.to_le_bytes());
+            bytes.extend(pool.token_b.to_le_bytes());
+            bytes.extend(pool.commission.to_le_bytes());
+            bytes.extend(pool.reserve_a.to_le_bytes());
+            bytes.extend(pool.reserve_b.to_le_bytes());
+            bytes.extend(pool.total_liquidity.to_le_bytes());
+            bytes.extend(pool.block_commission_a.to_le_bytes());
+            bytes.extend(pool.block_commission_b.to_le_bytes());
+            bytes.push(pool.pool_type as u8);
+            bytes.extend(pool.amplification.to_le_bytes());
+            bytes.extend(pool.weight_a.to_le_bytes());
+            bytes.extend(pool.weight_b.to_le_bytes());
+            for cumulative in [pool.price_ab_cumulative, pool.price_ba_cumulative] {
+                bytes.extend(cumulative.high.to_le_bytes());
+                bytes.extend(cumulative.low.to_le_bytes());
+            }
+            bytes.extend(pool.price_height.to_le_bytes());
+            bytes.push(pool.status as u8);
+        }
+        bytes
+    }
+
+    /// Decodes a canonical encoding. Anything `to_bytes` could not have produced (another
+    /// version, ids out of order or repeated, an unknown pool type or status, trailing bytes) is
+    /// rejected with `InvalidInput`, so decoding and encoding again gives back the same bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader { bytes };
+        if reader.u8()? != FORMAT_VERSION {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut registry = PoolRegistry::default();
+        for _ in 0..reader.u32()? {
+            let pool_id = reader.u32()?;
+            if registry.pools.keys().next_back() >= Some(&pool_id) {
+                return Err(Error::InvalidInput);
+            }
+            let pool = PoolPair {
+                token_a: reader.u32()?,
+                token_b: reader.u32()?,
+                commission: reader.u32()?,
+                reserve_a: reader.i64()?,
+                reserve_b: reader.i64()?,
+                total_liquidity: reader.i64()?,
+                block_commission_a: reader.i64()?,
+                block_commission_b: reader.i64()?,
+                pool_type: match reader.u8()? {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => return Err(Error::InvalidInput),
+                },
+                amplification: reader.u32()?,
+                weight_a: reader.u32()?,
+                weight_b: reader.u32()?,
+                price_ab_cumulative: reader.cumulative_price()?,
+                price_ba_cumulative: reader.cumulative_price()?,
+                price_height: reader.u32()?,
+                status: match reader.u8()? {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(Error::InvalidInput),
+                },
+            };
+            registry.pools.insert(pool_id, pool);
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+        Ok(registry)
+    }
+
+    /// SHA-256 of the canonical encoding
+    pub fn hash(&self) -> [u8; 32] {
+        Sha256::digest(self.to_bytes()).into()
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        if self.bytes.len() < N {
+            return Err(Error::InvalidInput);
+        }
+        let (head, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        Ok(head.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64, Error> {
+        self.take().map(i64::from_le_bytes)
+    }
+
+    fn cumulative_price(&mut self) -> Result<CumulativePrice, Error> {
+        Ok(CumulativePrice {
+            high: self.u64()?,
+            low: self.u64()?,
+        })
+    }
+}
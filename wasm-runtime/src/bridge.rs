@@ -0,0 +1,1099 @@
+use crate::dex_bindings as bindings;
+use crate::error::{InvalidArgument, NotRegistered, Status};
+use crate::limits::ModuleLimits;
+use crate::wasi::WasiMode;
+use crate::{cache, registry};
+use wit_bindgen_wasmtime::anyhow::Result;
+
+/// The runtime as the node sees it, and the API the node should use. It covers every `ainrt_*`
+/// function of the C ABI, which stays for embedders that cannot link C++. Everything lives in
+/// the `ainrt` namespace, as the node already has its own `PoolPrice`. Errors are thrown as
+/// `rust::Error` instead of being kept for `ainrt_last_error_message`.
+#[cxx::bridge(namespace = "ainrt")]
+pub mod runtime {
+    /// See `limits::ModuleLimits`
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct ModuleLimits {
+        pub memory_size: usize,
+        pub table_elements: u32,
+        pub instances: usize,
+    }
+
+    /// See `wasi::WasiMode`
+    #[derive(Debug)]
+    pub enum WasiMode {
+        Consensus,
+        Tooling,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct ModuleInfo {
+        pub id: String,
+        pub version: u32,
+        pub activation_height: u32,
+    }
+
+    #[derive(Debug)]
+    pub enum PoolType {
+        ConstantProduct,
+        StableSwap,
+        Weighted,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct CumulativePrice {
+        pub high: u64,
+        pub low: u64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct PoolPair {
+        pub token_a: u32,
+        pub token_b: u32,
+        pub commission: u32,
+        pub reserve_a: i64,
+        pub reserve_b: i64,
+        pub total_liquidity: i64,
+        pub block_commission_a: i64,
+        pub block_commission_b: i64,
+        pub pool_type: PoolType,
+        pub amplification: u32,
+        pub weight_a: u32,
+        pub weight_b: u32,
+        pub price_ab_cumulative: CumulativePrice,
+        pub price_ba_cumulative: CumulativePrice,
+        pub price_height: u32,
+        pub status: bool,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct TokenAmount {
+        pub token_id: u32,
+        pub amount: i64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct PoolPrice {
+        pub integer: i64,
+        pub fraction: i64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct DexFee {
+        pub in_pct_ab: i64,
+        pub out_pct_ab: i64,
+        pub in_pct_ba: i64,
+        pub out_pct_ba: i64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SwapOutput {
+        pub amount: i64,
+        pub dex_fee_in: i64,
+        pub dex_fee_out: i64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SwapStep {
+        pub step_from: i64,
+        pub step_to: i64,
+        pub reserve_from: i64,
+        pub reserve_to: i64,
+    }
+
+    #[derive(Debug)]
+    pub enum SwapEventKind {
+        DexFeeIn,
+        Commission,
+        DexFeeOut,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SwapEvent {
+        pub kind: SwapEventKind,
+        pub token_id: u32,
+        pub amount: i64,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct SwapTrace {
+        pub output: SwapOutput,
+        pub steps: Vec<SwapStep>,
+        pub events: Vec<SwapEvent>,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct SwapOperation {
+        pub pool_index: u32,
+        pub token_in: TokenAmount,
+        pub max_price: PoolPrice,
+        pub dex_fee: DexFee,
+    }
+
+    /// Outcome of one operation of a batch. `status` is the `Status` the C ABI reports for it,
+    /// 0 on success, and `error` says why it failed. `output` is only set on success.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct SwapOperationResult {
+        pub status: u32,
+        pub error: String,
+        pub output: SwapOutput,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct SwapRoute {
+        pub path: Vec<u32>,
+        pub amount: i64,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct BestPath {
+        pub best: SwapRoute,
+        pub alternatives: Vec<SwapRoute>,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct PoolPairConfig {
+        pub token_a: u32,
+        pub token_b: u32,
+        pub commission: u32,
+        pub status: bool,
+        pub pool_type: PoolType,
+        pub amplification: u32,
+        pub weight_a: u32,
+        pub weight_b: u32,
+    }
+
+    /// Fields of a pool pair to change. Each value is only applied when its `update_` flag is
+    /// set.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct PoolPairUpdate {
+        pub update_status: bool,
+        pub status: bool,
+        pub update_commission: bool,
+        pub commission: u32,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct RewardPool {
+        pub token_id: u32,
+        pub reward_per_block: i64,
+        pub reward_per_share: CumulativePrice,
+        pub last_height: u32,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct PoolEntry {
+        pub pool_id: u32,
+        pub pool_pair: PoolPair,
+    }
+
+    extern "Rust" {
+        fn default_module_limits() -> ModuleLimits;
+
+        fn register_dex_module(
+            dex_module_file_path: &str,
+            version: u32,
+            activation_height: u32,
+            limits: &ModuleLimits,
+        ) -> Result<()>;
+
+        fn register_module(
+            module_id: &str,
+            version: u32,
+            activation_height: u32,
+            limits: &ModuleLimits,
+            wasi_mode: WasiMode,
+            module_file_path: &str,
+        ) -> Result<()>;
+
+        fn register_module_bytes(
+            module_id: &str,
+            version: u32,
+            activation_height: u32,
+            limits: &ModuleLimits,
+            wasi_mode: WasiMode,
+            bytes: &[u8],
+            expected_sha256: &[u8],
+        ) -> Result<()>;
+
+        fn unregister_module(module_id: &str, version: u32) -> Result<()>;
+
+        fn list_modules() -> Vec<ModuleInfo>;
+
+        fn lookup_module(module_id: &str, version: u32) -> bool;
+
+        fn latest_module_version(module_id: &str) -> Result<u32>;
+
+        fn active_module_version(module_id: &str, height: u32) -> Result<u32>;
+
+        fn set_module_cache_dir(dir: &str, key: &[u8]) -> Result<()>;
+
+        fn set_fuel_limit(fuel_limit: u64);
+
+        fn last_fuel_consumed() -> u64;
+
+        fn dex_swap(
+            height: u32,
+            poolpair: &mut PoolPair,
+            token_in: &TokenAmount,
+            max_price: &PoolPrice,
+            min_output: i64,
+            dex_fee: &DexFee,
+            post_bayfront_gardens: bool,
+        ) -> Result<SwapOutput>;
+
+        fn dex_swap_trace(
+            height: u32,
+            poolpair: &PoolPair,
+            token_in: &TokenAmount,
+            max_price: &PoolPrice,
+            min_output: i64,
+            dex_fee: &DexFee,
+            post_bayfront_gardens: bool,
+        ) -> Result<SwapTrace>;
+
+        fn dex_swap_batch(
+            height: u32,
+            poolpairs: &mut [PoolPair],
+            operations: &[SwapOperation],
+            post_bayfront_gardens: bool,
+        ) -> Result<Vec<SwapOperationResult>>;
+
+        fn dex_composite_swap(
+            height: u32,
+            poolpairs: &mut [PoolPair],
+            token_in: &TokenAmount,
+            max_price: &PoolPrice,
+            dex_fees: &[DexFee],
+            post_bayfront_gardens: bool,
+        ) -> Result<i64>;
+
+        fn dex_best_path(
+            height: u32,
+            poolpairs: &[PoolPair],
+            token_in: &TokenAmount,
+            token_to: u32,
+            max_hops: u32,
+            max_price: &PoolPrice,
+            dex_fees: &[DexFee],
+            post_bayfront_gardens: bool,
+        ) -> Result<BestPath>;
+
+        fn dex_quote(
+            height: u32,
+            poolpair: &PoolPair,
+            token_in: &TokenAmount,
+            dex_fee: &DexFee,
+            post_bayfront_gardens: bool,
+        ) -> Result<i64>;
+
+        fn dex_add_liquidity(
+            height: u32,
+            poolpair: &mut PoolPair,
+            amount_a: i64,
+            amount_b: i64,
+        ) -> Result<i64>;
+
+        fn dex_remove_liquidity(
+            height: u32,
+            poolpair: &mut PoolPair,
+            liquidity: i64,
+            amount_a: &mut i64,
+            amount_b: &mut i64,
+        ) -> Result<()>;
+
+        fn dex_create_pool_pair(
+            height: u32,
+            config: &PoolPairConfig,
+            owner_address: &str,
+        ) -> Result<PoolPair>;
+
+        fn dex_update_pool_pair(
+            height: u32,
+            poolpair: &mut PoolPair,
+            update: &PoolPairUpdate,
+        ) -> Result<()>;
+
+        fn dex_accumulate_price(height: u32, poolpair: &mut PoolPair) -> Result<()>;
+
+        fn dex_twap(
+            height: u32,
+            start: &PoolPair,
+            end: &PoolPair,
+            price_ab: &mut PoolPrice,
+            price_ba: &mut PoolPrice,
+        ) -> Result<()>;
+
+        fn dex_distribute(
+            height: u32,
+            poolpair: &PoolPair,
+            rewards: &mut [RewardPool],
+        ) -> Result<()>;
+
+        fn dex_claim(
+            height: u32,
+            poolpair: &PoolPair,
+            rewards: &[RewardPool],
+            liquidity: i64,
+            paid: &mut [CumulativePrice],
+        ) -> Result<Vec<TokenAmount>>;
+
+        fn dex_serialize_pools(height: u32, pools: &[PoolEntry]) -> Result<Vec<u8>>;
+
+        fn dex_deserialize_pools(height: u32, bytes: &[u8]) -> Result<Vec<PoolEntry>>;
+
+        fn dex_pools_hash(height: u32, pools: &[PoolEntry]) -> Result<Vec<u8>>;
+
+        fn dex_stabilization_fee(
+            height: u32,
+            algo_dusd: i64,
+            dusd_supply: i64,
+            coefficient: i64,
+        ) -> Result<i64>;
+    }
+}
+
+fn default_module_limits() -> runtime::ModuleLimits {
+    ModuleLimits::default().into()
+}
+
+/// Registers the dex module at `dex_module_file_path`. See `ainrt_register_dex_module`.
+fn register_dex_module(
+    dex_module_file_path: &str,
+    version: u32,
+    activation_height: u32,
+    limits: &runtime::ModuleLimits,
+) -> Result<()> {
+    crate::register_dex_module(
+        dex_module_file_path,
+        version,
+        activation_height,
+        (*limits).into(),
+    )
+}
+
+/// Registers the module at `module_file_path`. See `ainrt_register_module`.
+fn register_module(
+    module_id: &str,
+    version: u32,
+    activation_height: u32,
+    limits: &runtime::ModuleLimits,
+    wasi_mode: runtime::WasiMode,
+    module_file_path: &str,
+) -> Result<()> {
+    let module = registry::compile(module_file_path, (*limits).into(), wasi_mode.try_into()?)?;
+    registry::insert(module_id, version, activation_height, module)
+}
+
+/// Registers the module in `bytes` after checking that its SHA-256 is `expected_sha256`. See
+/// `ainrt_register_module_bytes`.
+fn register_module_bytes(
+    module_id: &str,
+    version: u32,
+    activation_height: u32,
+    limits: &runtime::ModuleLimits,
+    wasi_mode: runtime::WasiMode,
+    bytes: &[u8],
+    expected_sha256: &[u8],
+) -> Result<()> {
+    let expected_sha256: &[u8; 32] = expected_sha256
+        .try_into()
+        .map_err(|_| InvalidArgument("expected SHA-256 must be 32 bytes".into()))?;
+    crate::register_module_bytes(
+        module_id,
+        version,
+        activation_height,
+        (*limits).into(),
+        wasi_mode.try_into()?,
+        bytes,
+        expected_sha256,
+    )
+}
+
+fn unregister_module(module_id: &str, version: u32) -> Result<()> {
+    registry::remove(module_id, version)
+}
+
+/// Every registered module with its activation height, ordered by id and then version
+fn list_modules() -> Vec<runtime::ModuleInfo> {
+    registry::list()
+        .into_iter()
+        .map(|(module, activation_height)| runtime::ModuleInfo {
+            id: module.id,
+            version: module.version,
+            activation_height,
+        })
+        .collect()
+}
+
+fn lookup_module(module_id: &str, version: u32) -> bool {
+    registry::contains(module_id, version)
+}
+
+fn latest_module_version(module_id: &str) -> Result<u32> {
+    registry::latest_version(module_id).ok_or_else(|| {
+        NotRegistered(format!("module {} has no registered version", module_id)).into()
+    })
+}
+
+fn active_module_version(module_id: &str, height: u32) -> Result<u32> {
+    registry::active_version(module_id, height).ok_or_else(|| {
+        NotRegistered(format!(
+            "module {} has no version active at height {}",
+            module_id, height
+        ))
+        .into()
+    })
+}
+
+/// Caches compiled modules in `dir`, authenticated with `key`, or disables the cache if `dir`
+/// is empty. See `ainrt_set_module_cache_dir`.
+fn set_module_cache_dir(dir: &str, key: &[u8]) -> Result<()> {
+    let cache = match dir {
+        "" => None,
+        dir => Some(cache::ModuleCache::new(dir.into(), key)?),
+    };
+    cache::set(cache);
+    Ok(())
+}
+
+fn set_fuel_limit(fuel_limit: u64) {
+    registry::set_fuel_limit(fuel_limit);
+}
+
+/// Fuel consumed by the last module call made on this thread, whether it succeeded or not
+fn last_fuel_consumed() -> u64 {
+    registry::last_fuel_consumed()
+}
+
+/// Swaps `token_in` against `poolpair`, which is updated in place on success. A `min_output`
+/// of 0 requires no minimum output, and any other also holds the realized price of the trade
+/// to `max_price`. See `ainrt_call_dex_swap`.
+fn dex_swap(
+    height: u32,
+    poolpair: &mut runtime::PoolPair,
+    token_in: &runtime::TokenAmount,
+    max_price: &runtime::PoolPrice,
+    min_output: i64,
+    dex_fee: &runtime::DexFee,
+    post_bayfront_gardens: bool,
+) -> Result<runtime::SwapOutput> {
+    let res = crate::dex_swap(
+        height,
+        (*poolpair).try_into()?,
+        (*token_in).into(),
+        (*max_price).into(),
+        Some(min_output).filter(|min_output| *min_output != 0),
+        (*dex_fee).into(),
+        post_bayfront_gardens,
+    )?;
+    *poolpair = res.pool_pair.into();
+    Ok(runtime::SwapOutput {
+        amount: res.slop_swap_result,
+        dex_fee_in: res.dex_fee_in,
+        dex_fee_out: res.dex_fee_out,
+    })
+}
+
+/// Runs the same swap as `dex_swap` without updating `poolpair`, and returns each step of the
+/// trade and each fee taken along with its output
+fn dex_swap_trace(
+    height: u32,
+    poolpair: &runtime::PoolPair,
+    token_in: &runtime::TokenAmount,
+    max_price: &runtime::PoolPrice,
+    min_output: i64,
+    dex_fee: &runtime::DexFee,
+    post_bayfront_gardens: bool,
+) -> Result<runtime::SwapTrace> {
+    let res = crate::dex_swap_trace(
+        height,
+        (*poolpair).try_into()?,
+        (*token_in).into(),
+        (*max_price).into(),
+        Some(min_output).filter(|min_output| *min_output != 0),
+        (*dex_fee).into(),
+        post_bayfront_gardens,
+    )?;
+    Ok(runtime::SwapTrace {
+        output: runtime::SwapOutput {
+            amount: res.swap_result.slop_swap_result,
+            dex_fee_in: res.swap_result.dex_fee_in,
+            dex_fee_out: res.swap_result.dex_fee_out,
+        },
+        steps: res.steps.into_iter().map(Into::into).collect(),
+        events: res.events.into_iter().map(Into::into).collect(),
+    })
+}
+
+/// Applies `operations` one after another to `poolpairs`, which are updated in place. A failed
+/// operation leaves the pools unchanged and the rest still run, so only a batch that could not
+/// be run at all throws. See `ainrt_call_dex_swap_batch`.
+fn dex_swap_batch(
+    height: u32,
+    poolpairs: &mut [runtime::PoolPair],
+    operations: &[runtime::SwapOperation],
+    post_bayfront_gardens: bool,
+) -> Result<Vec<runtime::SwapOperationResult>> {
+    let pps = poolpairs
+        .iter()
+        .map(|pp| bindings::PoolPair::try_from(*pp))
+        .collect::<Result<Vec<_>, _>>()?;
+    let ops = operations
+        .iter()
+        .map(|&op| op.into())
+        .collect::<Vec<bindings::SwapOperation>>();
+    let res = crate::dex_swap_batch(height, &pps, &ops, post_bayfront_gardens)?;
+    for (pp, updated) in poolpairs.iter_mut().zip(res.pool_pairs) {
+        *pp = updated.into();
+    }
+    Ok(res
+        .results
+        .into_iter()
+        .map(|result| match result {
+            Ok(output) => runtime::SwapOperationResult {
+                status: Status::Ok as u32,
+                error: String::new(),
+                output: output.into(),
+            },
+            Err(e) => runtime::SwapOperationResult {
+                status: Status::from(&e) as u32,
+                error: e.to_string(),
+                output: runtime::SwapOutput {
+                    amount: 0,
+                    dex_fee_in: 0,
+                    dex_fee_out: 0,
+                },
+            },
+        })
+        .collect())
+}
+
+/// Swaps `token_in` through `poolpairs` in order, with the dex fee of the same index in
+/// `dex_fees` for each hop, updating them in place on success
+fn dex_composite_swap(
+    height: u32,
+    poolpairs: &mut [runtime::PoolPair],
+    token_in: &runtime::TokenAmount,
+    max_price: &runtime::PoolPrice,
+    dex_fees: &[runtime::DexFee],
+    post_bayfront_gardens: bool,
+) -> Result<i64> {
+    let pps = poolpairs
+        .iter()
+        .map(|pp| bindings::PoolPair::try_from(*pp))
+        .collect::<Result<Vec<_>, _>>()?;
+    let dex_fees = dex_fees
+        .iter()
+        .map(|&fee| fee.into())
+        .collect::<Vec<bindings::DexFee>>();
+    let res = crate::dex_composite_swap(
+        height,
+        &pps,
+        (*token_in).into(),
+        (*max_price).into(),
+        &dex_fees,
+        post_bayfront_gardens,
+    )?;
+    for (pp, updated) in poolpairs.iter_mut().zip(res.pool_pairs) {
+        *pp = updated.into();
+    }
+    Ok(res.slop_swap_result)
+}
+
+/// Finds the route from `token_in` to `token_to` through `poolpairs` with the highest output,
+/// with the dex fee of the same index in `dex_fees` for each pool. Paths hold pool indices.
+#[allow(clippy::too_many_arguments)]
+fn dex_best_path(
+    height: u32,
+    poolpairs: &[runtime::PoolPair],
+    token_in: &runtime::TokenAmount,
+    token_to: u32,
+    max_hops: u32,
+    max_price: &runtime::PoolPrice,
+    dex_fees: &[runtime::DexFee],
+    post_bayfront_gardens: bool,
+) -> Result<runtime::BestPath> {
+    let pps = poolpairs
+        .iter()
+        .map(|pp| bindings::PoolPair::try_from(*pp))
+        .collect::<Result<Vec<_>, _>>()?;
+    let dex_fees = dex_fees
+        .iter()
+        .map(|&fee| fee.into())
+        .collect::<Vec<bindings::DexFee>>();
+    let res = crate::dex_best_path(
+        height,
+        &pps,
+        (*token_in).into(),
+        token_to,
+        max_hops,
+        (*max_price).into(),
+        &dex_fees,
+        post_bayfront_gardens,
+    )?;
+    Ok(runtime::BestPath {
+        best: res.best.into(),
+        alternatives: res.alternatives.into_iter().map(Into::into).collect(),
+    })
+}
+
+fn dex_quote(
+    height: u32,
+    poolpair: &runtime::PoolPair,
+    token_in: &runtime::TokenAmount,
+    dex_fee: &runtime::DexFee,
+    post_bayfront_gardens: bool,
+) -> Result<i64> {
+    crate::dex_quote(
+        height,
+        (*poolpair).try_into()?,
+        (*token_in).into(),
+        (*dex_fee).into(),
+        post_bayfront_gardens,
+    )
+}
+
+/// Adds liquidity to `poolpair`, which is updated in place on success, and returns the
+/// liquidity minted
+fn dex_add_liquidity(
+    height: u32,
+    poolpair: &mut runtime::PoolPair,
+    amount_a: i64,
+    amount_b: i64,
+) -> Result<i64> {
+    let res = crate::dex_add_liquidity(height, (*poolpair).try_into()?, amount_a, amount_b)?;
+    *poolpair = res.pool_pair.into();
+    Ok(res.liquidity)
+}
+
+fn dex_remove_liquidity(
+    height: u32,
+    poolpair: &mut runtime::PoolPair,
+    liquidity: i64,
+    amount_a: &mut i64,
+    amount_b: &mut i64,
+) -> Result<()> {
+    let res = crate::dex_remove_liquidity(height, (*poolpair).try_into()?, liquidity)?;
+    *poolpair = res.pool_pair.into();
+    *amount_a = res.amount_a;
+    *amount_b = res.amount_b;
+    Ok(())
+}
+
+/// A new, empty pool pair built from `config` and `owner_address`, once both are validated
+fn dex_create_pool_pair(
+    height: u32,
+    config: &runtime::PoolPairConfig,
+    owner_address: &str,
+) -> Result<runtime::PoolPair> {
+    let res = crate::dex_create_pool_pair(height, (*config).try_into()?, owner_address)?;
+    Ok(res.into())
+}
+
+/// Applies `update` to `poolpair`, which is updated in place on success. See
+/// `ainrt_call_dex_update_pool_pair`.
+fn dex_update_pool_pair(
+    height: u32,
+    poolpair: &mut runtime::PoolPair,
+    update: &runtime::PoolPairUpdate,
+) -> Result<()> {
+    let res = crate::dex_update_pool_pair(
+        height,
+        (*poolpair).try_into()?,
+        Some(update.status).filter(|_| update.update_status),
+        Some(update.commission).filter(|_| update.update_commission),
+    )?;
+    *poolpair = res.into();
+    Ok(())
+}
+
+fn dex_accumulate_price(height: u32, poolpair: &mut runtime::PoolPair) -> Result<()> {
+    *poolpair = crate::dex_accumulate_price(height, (*poolpair).try_into()?)?.into();
+    Ok(())
+}
+
+fn dex_twap(
+    height: u32,
+    start: &runtime::PoolPair,
+    end: &runtime::PoolPair,
+    price_ab: &mut runtime::PoolPrice,
+    price_ba: &mut runtime::PoolPrice,
+) -> Result<()> {
+    let res = crate::dex_twap(height, (*start).try_into()?, (*end).try_into()?)?;
+    *price_ab = res.price_ab.into();
+    *price_ba = res.price_ba.into();
+    Ok(())
+}
+
+/// Accrues the rewards of `poolpair` up to `height` into `rewards`, which are updated in place
+/// on success
+fn dex_distribute(
+    height: u32,
+    poolpair: &runtime::PoolPair,
+    rewards: &mut [runtime::RewardPool],
+) -> Result<()> {
+    let pools = rewards
+        .iter()
+        .map(|&reward| reward.into())
+        .collect::<Vec<bindings::RewardPool>>();
+    let res = crate::dex_distribute(height, (*poolpair).try_into()?, &pools)?;
+    for (reward, updated) in rewards.iter_mut().zip(res) {
+        *reward = updated.into();
+    }
+    Ok(())
+}
+
+/// The rewards owed to an owner of `liquidity` in `poolpair`, one per reward pool. See
+/// `ainrt_call_dex_claim` for `paid`, which is updated in place on success.
+fn dex_claim(
+    height: u32,
+    poolpair: &runtime::PoolPair,
+    rewards: &[runtime::RewardPool],
+    liquidity: i64,
+    paid: &mut [runtime::CumulativePrice],
+) -> Result<Vec<runtime::TokenAmount>> {
+    let pools = rewards
+        .iter()
+        .map(|&reward| reward.into())
+        .collect::<Vec<bindings::RewardPool>>();
+    let paid_before = paid
+        .iter()
+        .map(|&price| price.into())
+        .collect::<Vec<bindings::RewardPerShare>>();
+    let res = crate::dex_claim(
+        height,
+        (*poolpair).try_into()?,
+        &pools,
+        liquidity,
+        &paid_before,
+    )?;
+    for (price, updated) in paid.iter_mut().zip(res.paid) {
+        *price = updated.into();
+    }
+    Ok(res.amounts.into_iter().map(Into::into).collect())
+}
+
+/// The canonical encoding of `pools`. See `ainrt_call_dex_serialize_pools`.
+fn dex_serialize_pools(height: u32, pools: &[runtime::PoolEntry]) -> Result<Vec<u8>> {
+    let pools = pools
+        .iter()
+        .map(|entry| bindings::PoolEntry::try_from(*entry))
+        .collect::<Result<Vec<_>, _>>()?;
+    crate::dex_serialize_pools(height, &pools)
+}
+
+/// Decodes pools written by `dex_serialize_pools`, in ascending id order
+fn dex_deserialize_pools(height: u32, bytes: &[u8]) -> Result<Vec<runtime::PoolEntry>> {
+    let pools = crate::dex_deserialize_pools(height, bytes)?;
+    Ok(pools.into_iter().map(Into::into).collect())
+}
+
+/// The SHA-256 of the canonical encoding of `pools`. See `ainrt_call_dex_pools_hash`.
+fn dex_pools_hash(height: u32, pools: &[runtime::PoolEntry]) -> Result<Vec<u8>> {
+    let pools = pools
+        .iter()
+        .map(|entry| bindings::PoolEntry::try_from(*entry))
+        .collect::<Result<Vec<_>, _>>()?;
+    crate::dex_pools_hash(height, &pools)
+}
+
+fn dex_stabilization_fee(
+    height: u32,
+    algo_dusd: i64,
+    dusd_supply: i64,
+    coefficient: i64,
+) -> Result<i64> {
+    crate::dex_stabilization_fee(height, algo_dusd, dusd_supply, coefficient)
+}
+
+impl From<ModuleLimits> for runtime::ModuleLimits {
+    fn from(limits: ModuleLimits) -> Self {
+        runtime::ModuleLimits {
+            memory_size: limits.memory_size,
+            table_elements: limits.table_elements,
+            instances: limits.instances,
+        }
+    }
+}
+
+impl From<runtime::ModuleLimits> for ModuleLimits {
+    fn from(limits: runtime::ModuleLimits) -> Self {
+        ModuleLimits {
+            memory_size: limits.memory_size,
+            table_elements: limits.table_elements,
+            instances: limits.instances,
+        }
+    }
+}
+
+impl TryFrom<runtime::WasiMode> for WasiMode {
+    type Error = InvalidArgument;
+
+    fn try_from(mode: runtime::WasiMode) -> Result<Self, Self::Error> {
+        match mode {
+            runtime::WasiMode::Consensus => Ok(WasiMode::Consensus),
+            runtime::WasiMode::Tooling => Ok(WasiMode::Tooling),
+            _ => Err(InvalidArgument(format!("unknown WASI mode {}", mode.repr))),
+        }
+    }
+}
+
+impl TryFrom<runtime::PoolType> for bindings::PoolType {
+    type Error = InvalidArgument;
+
+    fn try_from(pool_type: runtime::PoolType) -> Result<Self, Self::Error> {
+        match pool_type {
+            runtime::PoolType::ConstantProduct => Ok(bindings::PoolType::ConstantProduct),
+            runtime::PoolType::StableSwap => Ok(bindings::PoolType::StableSwap),
+            runtime::PoolType::Weighted => Ok(bindings::PoolType::Weighted),
+            _ => Err(InvalidArgument(format!(
+                "unknown pool type {}",
+                pool_type.repr
+            ))),
+        }
+    }
+}
+
+impl From<bindings::PoolType> for runtime::PoolType {
+    fn from(pool_type: bindings::PoolType) -> Self {
+        match pool_type {
+            bindings::PoolType::ConstantProduct => runtime::PoolType::ConstantProduct,
+            bindings::PoolType::StableSwap => runtime::PoolType::StableSwap,
+            bindings::PoolType::Weighted => runtime::PoolType::Weighted,
+        }
+    }
+}
+
+impl From<runtime::CumulativePrice> for bindings::CumulativePrice {
+    fn from(price: runtime::CumulativePrice) -> Self {
+        bindings::CumulativePrice {
+            high: price.high,
+            low: price.low,
+        }
+    }
+}
+
+impl From<bindings::CumulativePrice> for runtime::CumulativePrice {
+    fn from(price: bindings::CumulativePrice) -> Self {
+        runtime::CumulativePrice {
+            high: price.high,
+            low: price.low,
+        }
+    }
+}
+
+impl TryFrom<runtime::PoolPair> for bindings::PoolPair {
+    type Error = InvalidArgument;
+
+    fn try_from(pp: runtime::PoolPair) -> Result<Self, Self::Error> {
+        Ok(bindings::PoolPair {
+            token_a: pp.token_a,
+            token_b: pp.token_b,
+            commission: pp.commission,
+            reserve_a: pp.reserve_a,
+            reserve_b: pp.reserve_b,
+            total_liquidity: pp.total_liquidity,
+            block_commission_a: pp.block_commission_a,
+            block_commission_b: pp.block_commission_b,
+            pool_type: pp.pool_type.try_into()?,
+            amplification: pp.amplification,
+            weight_a: pp.weight_a,
+            weight_b: pp.weight_b,
+            price_ab_cumulative: pp.price_ab_cumulative.into(),
+            price_ba_cumulative: pp.price_ba_cumulative.into(),
+            price_height: pp.price_height,
+            status: pp.status,
+        })
+    }
+}
+
+impl From<bindings::PoolPair> for runtime::PoolPair {
+    fn from(pp: bindings::PoolPair) -> Self {
+        runtime::PoolPair {
+            token_a: pp.token_a,
+            token_b: pp.token_b,
+            commission: pp.commission,
+            reserve_a: pp.reserve_a,
+            reserve_b: pp.reserve_b,
+            total_liquidity: pp.total_liquidity,
+            block_commission_a: pp.block_commission_a,
+            block_commission_b: pp.block_commission_b,
+            pool_type: pp.pool_type.into(),
+            amplification: pp.amplification,
+            weight_a: pp.weight_a,
+            weight_b: pp.weight_b,
+            price_ab_cumulative: pp.price_ab_cumulative.into(),
+            price_ba_cumulative: pp.price_ba_cumulative.into(),
+            price_height: pp.price_height,
+            status: pp.status,
+        }
+    }
+}
+
+impl From<runtime::TokenAmount> for bindings::TokenAmount {
+    fn from(token: runtime::TokenAmount) -> Self {
+        bindings::TokenAmount {
+            token_id: token.token_id,
+            amount: token.amount,
+        }
+    }
+}
+
+impl From<runtime::PoolPrice> for bindings::PoolPrice {
+    fn from(price: runtime::PoolPrice) -> Self {
+        bindings::PoolPrice {
+            integer: price.integer,
+            fraction: price.fraction,
+        }
+    }
+}
+
+impl From<bindings::PoolPrice> for runtime::PoolPrice {
+    fn from(price: bindings::PoolPrice) -> Self {
+        runtime::PoolPrice {
+            integer: price.integer,
+            fraction: price.fraction,
+        }
+    }
+}
+
+impl From<runtime::DexFee> for bindings::DexFee {
+    fn from(fee: runtime::DexFee) -> Self {
+        bindings::DexFee {
+            in_pct_ab: fee.in_pct_ab,
+            out_pct_ab: fee.out_pct_ab,
+            in_pct_ba: fee.in_pct_ba,
+            out_pct_ba: fee.out_pct_ba,
+        }
+    }
+}
+
+impl From<bindings::TokenAmount> for runtime::TokenAmount {
+    fn from(token: bindings::TokenAmount) -> Self {
+        runtime::TokenAmount {
+            token_id: token.token_id,
+            amount: token.amount,
+        }
+    }
+}
+
+impl From<bindings::SwapOutput> for runtime::SwapOutput {
+    fn from(output: bindings::SwapOutput) -> Self {
+        runtime::SwapOutput {
+            amount: output.amount,
+            dex_fee_in: output.dex_fee_in,
+            dex_fee_out: output.dex_fee_out,
+        }
+    }
+}
+
+impl From<bindings::SwapStep> for runtime::SwapStep {
+    fn from(step: bindings::SwapStep) -> Self {
+        runtime::SwapStep {
+            step_from: step.step_from,
+            step_to: step.step_to,
+            reserve_from: step.reserve_from,
+            reserve_to: step.reserve_to,
+        }
+    }
+}
+
+impl From<bindings::SwapEventKind> for runtime::SwapEventKind {
+    fn from(kind: bindings::SwapEventKind) -> Self {
+        match kind {
+            bindings::SwapEventKind::DexFeeIn => runtime::SwapEventKind::DexFeeIn,
+            bindings::SwapEventKind::Commission => runtime::SwapEventKind::Commission,
+            bindings::SwapEventKind::DexFeeOut => runtime::SwapEventKind::DexFeeOut,
+        }
+    }
+}
+
+impl From<bindings::SwapEvent> for runtime::SwapEvent {
+    fn from(event: bindings::SwapEvent) -> Self {
+        runtime::SwapEvent {
+            kind: event.kind.into(),
+            token_id: event.token_id,
+            amount: event.amount,
+        }
+    }
+}
+
+impl From<runtime::SwapOperation> for bindings::SwapOperation {
+    fn from(op: runtime::SwapOperation) -> Self {
+        bindings::SwapOperation {
+            pool_index: op.pool_index,
+            token_in: op.token_in.into(),
+            max_price: op.max_price.into(),
+            dex_fee: op.dex_fee.into(),
+        }
+    }
+}
+
+impl From<bindings::SwapRoute> for runtime::SwapRoute {
+    fn from(route: bindings::SwapRoute) -> Self {
+        runtime::SwapRoute {
+            path: route.path,
+            amount: route.slop_swap_result,
+        }
+    }
+}
+
+impl TryFrom<runtime::PoolPairConfig> for bindings::PoolPairConfig {
+    type Error = InvalidArgument;
+
+    fn try_from(config: runtime::PoolPairConfig) -> Result<Self, Self::Error> {
+        Ok(bindings::PoolPairConfig {
+            token_a: config.token_a,
+            token_b: config.token_b,
+            commission: config.commission,
+            status: config.status,
+            pool_type: config.pool_type.try_into()?,
+            amplification: config.amplification,
+            weight_a: config.weight_a,
+            weight_b: config.weight_b,
+        })
+    }
+}
+
+impl From<runtime::RewardPool> for bindings::RewardPool {
+    fn from(reward: runtime::RewardPool) -> Self {
+        bindings::RewardPool {
+            token_id: reward.token_id,
+            reward_per_block: reward.reward_per_block,
+            reward_per_share: reward.reward_per_share.into(),
+            last_height: reward.last_height,
+        }
+    }
+}
+
+impl From<bindings::RewardPool> for runtime::RewardPool {
+    fn from(reward: bindings::RewardPool) -> Self {
+        runtime::RewardPool {
+            token_id: reward.token_id,
+            reward_per_block: reward.reward_per_block,
+            reward_per_share: reward.reward_per_share.into(),
+            last_height: reward.last_height,
+        }
+    }
+}
+
+impl TryFrom<runtime::PoolEntry> for bindings::PoolEntry {
+    type Error = InvalidArgument;
+
+    fn try_from(entry: runtime::PoolEntry) -> Result<Self, Self::Error> {
+        Ok(bindings::PoolEntry {
+            pool_id: entry.pool_id,
+            pool_pair: entry.pool_pair.try_into()?,
+        })
+    }
+}
+
+impl From<bindings::PoolEntry> for runtime::PoolEntry {
+    fn from(entry: bindings::PoolEntry) -> Self {
+        runtime::PoolEntry {
+            pool_id: entry.pool_id,
+            pool_pair: entry.pool_pair.into(),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::error::InvalidArgument;
+use crate::registry::to_hex;
+use hmac::{Hmac, Mac};
+use lazy_static::lazy_static;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use wit_bindgen_wasmtime::anyhow::Result;
+use wit_bindgen_wasmtime::wasmtime::{Engine, Module};
+
+/// Shortest key accepted for authenticating cached artifacts, in bytes
+pub const MIN_KEY_LEN: usize = 32;
+
+/// Length of the MAC stored in front of every artifact
+const MAC_LEN: usize = 32;
+
+/// The smallest valid wasm module, compiled to fingerprint an engine
+const EMPTY_MODULE: &[u8] = b"\0asm\x01\0\0\0";
+
+/// Numbers temporary files, so concurrent writers never share one
+static NEXT_TMP: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    static ref CACHE: RwLock<Option<ModuleCache>> = RwLock::new(None);
+}
+
+/// A directory of compiled modules, authenticated with a key held by the node. Artifacts are
+/// deserialized into executable code, so one that anyone without the key could have written
+/// is never loaded.
+pub struct ModuleCache {
+    dir: PathBuf,
+    key: Vec<u8>,
+}
+
+impl ModuleCache {
+    /// A cache in `dir` whose artifacts are authenticated with `key`, which must be at least
+    /// `MIN_KEY_LEN` bytes of secret, random data
+    pub fn new(dir: PathBuf, key: &[u8]) -> Result<Self> {
+        if key.len() < MIN_KEY_LEN {
+            let message = format!("module cache key must be at least {} bytes", MIN_KEY_LEN);
+            return Err(InvalidArgument(message).into());
+        }
+        Ok(ModuleCache {
+            dir,
+            key: key.to_vec(),
+        })
+    }
+
+    /// Compiles the wasm module in `bytes`, or loads it from the cache if this node compiled it
+    /// before with an identical engine. A missing, forged, corrupted or stale artifact is
+    /// replaced by a fresh compilation.
+    pub fn compile(&self, engine: &Engine, bytes: &[u8]) -> Result<Module> {
+        let cache_key = cache_key(engine, bytes)?;
+        let path = self.dir.join(format!("{}.cwasm", cache_key));
+        if let Some(module) = self.read(engine, &cache_key, &path) {
+            return Ok(module);
+        }
+        let module = Module::new(engine, bytes)?;
+        // A cache that cannot be written only costs the next start a compilation
+        let _ = self.write(&cache_key, &path, &module);
+        Ok(module)
+    }
+
+    /// A MAC of an artifact stored under `cache_key`, still to be given the artifact. Covering
+    /// the cache key makes an artifact valid only at the path it was written to.
+    fn mac(&self, cache_key: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC takes any key");
+        mac.update(cache_key.as_bytes());
+        mac
+    }
+
+    /// The cached module at `path`, if there is one and its MAC verifies
+    fn read(&self, engine: &Engine, cache_key: &str, path: &Path) -> Option<Module> {
+        let file = fs::read(path).ok()?;
+        if file.len() < MAC_LEN {
+            return None;
+        }
+        let (tag, artifact) = file.split_at(MAC_LEN);
+        let mut mac = self.mac(cache_key);
+        mac.update(artifact);
+        // Compares in constant time
+        mac.verify_slice(tag).ok()?;
+        // Safety: the MAC shows the artifact was serialized by `write` with this node's key and
+        // is unchanged since, so it is wasmtime's own output for this module. Wasmtime checks
+        // that it matches this engine's configuration and version.
+        unsafe { Module::deserialize(engine, artifact) }.ok()
+    }
+
+    fn write(&self, cache_key: &str, path: &Path, module: &Module) -> Result<()> {
+        let artifact = module.serialize()?;
+        let mut mac = self.mac(cache_key);
+        mac.update(&artifact);
+        let mut file = mac.finalize().into_bytes().to_vec();
+        file.extend(artifact);
+
+        // Written next to its final path and renamed, so readers never see a partial file
+        fs::create_dir_all(&self.dir)?;
+        let tmp = path.with_extension(format!(
+            "tmp{}-{}",
+            std::process::id(),
+            NEXT_TMP.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp, file)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Sets the cache compiled modules are kept in, or disables it if `cache` is `None`
+pub fn set(cache: Option<ModuleCache>) {
+    *CACHE.write().unwrap() = cache;
+}
+
+/// Compiles the wasm module in `bytes` through the cache if one is set
+pub fn compile(engine: &Engine, bytes: &[u8]) -> Result<Module> {
+    match &*CACHE.read().unwrap() {
+        Some(cache) => cache.compile(engine, bytes),
+        None => Module::new(engine, bytes),
+    }
+}
+
+/// Keys an artifact on the module and the engine compiling it
+fn cache_key(engine: &Engine, bytes: &[u8]) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(Sha256::digest(bytes));
+    hasher.update(engine_fingerprint(engine)?);
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Identifies the wasmtime version and engine configuration, which wasmtime writes into every
+/// serialized module, so a change to either moves artifacts to new keys instead of reading old
+/// ones
+fn engine_fingerprint(engine: &Engine) -> Result<[u8; 32]> {
+    let empty = Module::new(engine, EMPTY_MODULE)?.serialize()?;
+    Ok(Sha256::digest(empty).into())
+}
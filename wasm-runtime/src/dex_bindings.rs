@@ -1,28 +1,7 @@
 use std::marker::PhantomData;
 #[allow(unused_imports)]
 use wit_bindgen_wasmtime::{anyhow, wasmtime};
-#[repr(u8)]
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub enum Error {
-    Runtime,
-    NotFound,
-    InvalidInput,
-    LackOfLiquidity,
-    PriceHigherThanIndex,
-    PoolReserveOverflow,
-}
-impl std::fmt::Debug for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Error::Runtime => f.debug_tuple("Error::Runtime").finish(),
-            Error::NotFound => f.debug_tuple("Error::NotFound").finish(),
-            Error::InvalidInput => f.debug_tuple("Error::InvalidInput").finish(),
-            Error::LackOfLiquidity => f.debug_tuple("Error::LackOfLiquidity").finish(),
-            Error::PriceHigherThanIndex => f.debug_tuple("Error::PriceHigherThanIndex").finish(),
-            Error::PoolReserveOverflow => f.debug_tuple("Error::PoolReserveOverflow").finish(),
-        }
-    }
-}
+
 pub type DctId = u32;
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -84,6 +63,267 @@ impl wit_bindgen_wasmtime::Endian for PoolPrice {
 unsafe impl wit_bindgen_wasmtime::AllBytesValid for PoolPrice {}
 #[repr(C)]
 #[derive(Copy, Clone)]
+pub struct Reserves {
+    pub reserve_a: i64,
+    pub reserve_b: i64,
+}
+impl std::fmt::Debug for Reserves {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reserves")
+            .field("reserve-a", &self.reserve_a)
+            .field("reserve-b", &self.reserve_b)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for Reserves {
+    fn into_le(self) -> Self {
+        Self {
+            reserve_a: self.reserve_a.into_le(),
+            reserve_b: self.reserve_b.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            reserve_a: self.reserve_a.from_le(),
+            reserve_b: self.reserve_b.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for Reserves {}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PriceLimit {
+    pub price: PoolPrice,
+    pub max_price: PoolPrice,
+}
+impl std::fmt::Debug for PriceLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PriceLimit")
+            .field("price", &self.price)
+            .field("max-price", &self.max_price)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for PriceLimit {
+    fn into_le(self) -> Self {
+        Self {
+            price: self.price.into_le(),
+            max_price: self.max_price.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            price: self.price.from_le(),
+            max_price: self.max_price.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for PriceLimit {}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct OutputLimit {
+    pub amount: i64,
+    pub min_output: i64,
+}
+impl std::fmt::Debug for OutputLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutputLimit")
+            .field("amount", &self.amount)
+            .field("min-output", &self.min_output)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for OutputLimit {
+    fn into_le(self) -> Self {
+        Self {
+            amount: self.amount.into_le(),
+            min_output: self.min_output.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            amount: self.amount.from_le(),
+            min_output: self.min_output.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for OutputLimit {}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PoolIndex {
+    pub index: u32,
+}
+impl std::fmt::Debug for PoolIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolIndex")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for PoolIndex {
+    fn into_le(self) -> Self {
+        Self {
+            index: self.index.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            index: self.index.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for PoolIndex {}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct InputTokenMismatch {
+    pub token_id: DctId,
+    pub token_a: DctId,
+    pub token_b: DctId,
+}
+impl std::fmt::Debug for InputTokenMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputTokenMismatch")
+            .field("token-id", &self.token_id)
+            .field("token-a", &self.token_a)
+            .field("token-b", &self.token_b)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for InputTokenMismatch {
+    fn into_le(self) -> Self {
+        Self {
+            token_id: self.token_id.into_le(),
+            token_a: self.token_a.into_le(),
+            token_b: self.token_b.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            token_id: self.token_id.from_le(),
+            token_a: self.token_a.from_le(),
+            token_b: self.token_b.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for InputTokenMismatch {}
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PoolConfigError {
+    SameToken,
+    CommissionOutOfRange,
+    InvalidOwnerAddress,
+    InvalidPoolType,
+}
+impl std::fmt::Debug for PoolConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PoolConfigError::SameToken => f.debug_tuple("PoolConfigError::SameToken").finish(),
+            PoolConfigError::CommissionOutOfRange => f
+                .debug_tuple("PoolConfigError::CommissionOutOfRange")
+                .finish(),
+            PoolConfigError::InvalidOwnerAddress => f
+                .debug_tuple("PoolConfigError::InvalidOwnerAddress")
+                .finish(),
+            PoolConfigError::InvalidPoolType => {
+                f.debug_tuple("PoolConfigError::InvalidPoolType").finish()
+            }
+        }
+    }
+}
+#[derive(Clone, Copy)]
+pub enum Error {
+    Runtime,
+    NotFound,
+    InvalidInput,
+    LackOfLiquidity(Reserves),
+    PriceHigherThanIndex(PriceLimit),
+    PoolReserveOverflow(Reserves),
+    OutputBelowMinimum(OutputLimit),
+    TokenMismatch(InputTokenMismatch),
+    ArithmeticOverflow,
+    InvariantViolation(Reserves),
+    InvalidPoolConfig(PoolConfigError),
+    PoolNotFound(PoolIndex),
+    PoolDisabled,
+}
+impl std::fmt::Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Runtime => f.debug_tuple("Error::Runtime").finish(),
+            Error::NotFound => f.debug_tuple("Error::NotFound").finish(),
+            Error::InvalidInput => f.debug_tuple("Error::InvalidInput").finish(),
+            Error::LackOfLiquidity(e) => f.debug_tuple("Error::LackOfLiquidity").field(e).finish(),
+            Error::PriceHigherThanIndex(e) => f
+                .debug_tuple("Error::PriceHigherThanIndex")
+                .field(e)
+                .finish(),
+            Error::PoolReserveOverflow(e) => f
+                .debug_tuple("Error::PoolReserveOverflow")
+                .field(e)
+                .finish(),
+            Error::OutputBelowMinimum(e) => {
+                f.debug_tuple("Error::OutputBelowMinimum").field(e).finish()
+            }
+            Error::TokenMismatch(e) => f.debug_tuple("Error::TokenMismatch").field(e).finish(),
+            Error::ArithmeticOverflow => f.debug_tuple("Error::ArithmeticOverflow").finish(),
+            Error::InvariantViolation(e) => {
+                f.debug_tuple("Error::InvariantViolation").field(e).finish()
+            }
+            Error::InvalidPoolConfig(e) => {
+                f.debug_tuple("Error::InvalidPoolConfig").field(e).finish()
+            }
+            Error::PoolNotFound(e) => f.debug_tuple("Error::PoolNotFound").field(e).finish(),
+            Error::PoolDisabled => f.debug_tuple("Error::PoolDisabled").finish(),
+        }
+    }
+}
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PoolType {
+    ConstantProduct,
+    StableSwap,
+    Weighted,
+}
+impl std::fmt::Debug for PoolType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PoolType::ConstantProduct => f.debug_tuple("PoolType::ConstantProduct").finish(),
+            PoolType::StableSwap => f.debug_tuple("PoolType::StableSwap").finish(),
+            PoolType::Weighted => f.debug_tuple("PoolType::Weighted").finish(),
+        }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct CumulativePrice {
+    pub high: u64,
+    pub low: u64,
+}
+impl std::fmt::Debug for CumulativePrice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CumulativePrice")
+            .field("high", &self.high)
+            .field("low", &self.low)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for CumulativePrice {
+    fn into_le(self) -> Self {
+        Self {
+            high: self.high.into_le(),
+            low: self.low.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            high: self.high.from_le(),
+            low: self.low.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for CumulativePrice {}
+#[repr(C)]
+#[derive(Copy, Clone)]
 pub struct PoolPair {
     pub token_a: DctId,
     pub token_b: DctId,
@@ -93,6 +333,14 @@ pub struct PoolPair {
     pub total_liquidity: i64,
     pub block_commission_a: i64,
     pub block_commission_b: i64,
+    pub pool_type: PoolType,
+    pub amplification: u32,
+    pub weight_a: u32,
+    pub weight_b: u32,
+    pub price_ab_cumulative: CumulativePrice,
+    pub price_ba_cumulative: CumulativePrice,
+    pub price_height: u32,
+    pub status: bool,
 }
 impl std::fmt::Debug for PoolPair {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -105,125 +353,515 @@ impl std::fmt::Debug for PoolPair {
             .field("total-liquidity", &self.total_liquidity)
             .field("block-commission-a", &self.block_commission_a)
             .field("block-commission-b", &self.block_commission_b)
+            .field("pool-type", &self.pool_type)
+            .field("amplification", &self.amplification)
+            .field("weight-a", &self.weight_a)
+            .field("weight-b", &self.weight_b)
+            .field("price-ab-cumulative", &self.price_ab_cumulative)
+            .field("price-ba-cumulative", &self.price_ba_cumulative)
+            .field("price-height", &self.price_height)
+            .field("status", &self.status)
+            .finish()
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PoolPairConfig {
+    pub token_a: DctId,
+    pub token_b: DctId,
+    pub commission: DctId,
+    pub status: bool,
+    pub pool_type: PoolType,
+    pub amplification: u32,
+    pub weight_a: u32,
+    pub weight_b: u32,
+}
+impl std::fmt::Debug for PoolPairConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolPairConfig")
+            .field("token-a", &self.token_a)
+            .field("token-b", &self.token_b)
+            .field("commission", &self.commission)
+            .field("status", &self.status)
+            .field("pool-type", &self.pool_type)
+            .field("amplification", &self.amplification)
+            .field("weight-a", &self.weight_a)
+            .field("weight-b", &self.weight_b)
+            .finish()
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PoolEntry {
+    pub pool_id: DctId,
+    pub pool_pair: PoolPair,
+}
+impl std::fmt::Debug for PoolEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PoolEntry")
+            .field("pool-id", &self.pool_id)
+            .field("pool-pair", &self.pool_pair)
+            .finish()
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct DexFee {
+    pub in_pct_ab: i64,
+    pub out_pct_ab: i64,
+    pub in_pct_ba: i64,
+    pub out_pct_ba: i64,
+}
+impl std::fmt::Debug for DexFee {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DexFee")
+            .field("in-pct-ab", &self.in_pct_ab)
+            .field("out-pct-ab", &self.out_pct_ab)
+            .field("in-pct-ba", &self.in_pct_ba)
+            .field("out-pct-ba", &self.out_pct_ba)
             .finish()
     }
 }
-impl wit_bindgen_wasmtime::Endian for PoolPair {
+impl wit_bindgen_wasmtime::Endian for DexFee {
     fn into_le(self) -> Self {
         Self {
-            token_a: self.token_a.into_le(),
-            token_b: self.token_b.into_le(),
-            commission: self.commission.into_le(),
-            reserve_a: self.reserve_a.into_le(),
-            reserve_b: self.reserve_b.into_le(),
-            total_liquidity: self.total_liquidity.into_le(),
-            block_commission_a: self.block_commission_a.into_le(),
-            block_commission_b: self.block_commission_b.into_le(),
+            in_pct_ab: self.in_pct_ab.into_le(),
+            out_pct_ab: self.out_pct_ab.into_le(),
+            in_pct_ba: self.in_pct_ba.into_le(),
+            out_pct_ba: self.out_pct_ba.into_le(),
         }
     }
     fn from_le(self) -> Self {
         Self {
-            token_a: self.token_a.from_le(),
-            token_b: self.token_b.from_le(),
-            commission: self.commission.from_le(),
-            reserve_a: self.reserve_a.from_le(),
-            reserve_b: self.reserve_b.from_le(),
-            total_liquidity: self.total_liquidity.from_le(),
-            block_commission_a: self.block_commission_a.from_le(),
-            block_commission_b: self.block_commission_b.from_le(),
+            in_pct_ab: self.in_pct_ab.from_le(),
+            out_pct_ab: self.out_pct_ab.from_le(),
+            in_pct_ba: self.in_pct_ba.from_le(),
+            out_pct_ba: self.out_pct_ba.from_le(),
         }
     }
 }
-unsafe impl wit_bindgen_wasmtime::AllBytesValid for PoolPair {}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for DexFee {}
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct SwapResult {
     pub pool_pair: PoolPair,
     pub slop_swap_result: i64,
+    pub dex_fee_in: i64,
+    pub dex_fee_out: i64,
 }
 impl std::fmt::Debug for SwapResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SwapResult")
             .field("pool-pair", &self.pool_pair)
             .field("slop-swap-result", &self.slop_swap_result)
+            .field("dex-fee-in", &self.dex_fee_in)
+            .field("dex-fee-out", &self.dex_fee_out)
+            .finish()
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SwapStep {
+    pub step_from: i64,
+    pub step_to: i64,
+    pub reserve_from: i64,
+    pub reserve_to: i64,
+}
+impl std::fmt::Debug for SwapStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwapStep")
+            .field("step-from", &self.step_from)
+            .field("step-to", &self.step_to)
+            .field("reserve-from", &self.reserve_from)
+            .field("reserve-to", &self.reserve_to)
             .finish()
     }
 }
-impl wit_bindgen_wasmtime::Endian for SwapResult {
+impl wit_bindgen_wasmtime::Endian for SwapStep {
     fn into_le(self) -> Self {
         Self {
-            pool_pair: self.pool_pair.into_le(),
-            slop_swap_result: self.slop_swap_result.into_le(),
+            step_from: self.step_from.into_le(),
+            step_to: self.step_to.into_le(),
+            reserve_from: self.reserve_from.into_le(),
+            reserve_to: self.reserve_to.into_le(),
         }
     }
     fn from_le(self) -> Self {
         Self {
-            pool_pair: self.pool_pair.from_le(),
-            slop_swap_result: self.slop_swap_result.from_le(),
+            step_from: self.step_from.from_le(),
+            step_to: self.step_to.from_le(),
+            reserve_from: self.reserve_from.from_le(),
+            reserve_to: self.reserve_to.from_le(),
         }
     }
 }
-unsafe impl wit_bindgen_wasmtime::AllBytesValid for SwapResult {}
-
-/// Auxiliary data associated with the wasm exports.
-///
-/// This is required to be stored within the data of a
-/// `Store<T>` itself so lifting/lowering state can be managed
-/// when translating between the host and wasm.
-#[derive(Default)]
-pub struct DexData {}
-
-type SwapTypedFunc = wasmtime::TypedFunc<
-    (
-        i32,
-        i32,
-        i32,
-        i64,
-        i64,
-        i64,
-        i64,
-        i64,
-        i32,
-        i64,
-        i64,
-        i64,
-        i32,
-    ),
-    (i32,),
->;
-
-pub struct Dex<T> {
-    memory: wasmtime::Memory,
-    swap: SwapTypedFunc,
-    data: PhantomData<T>,
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for SwapStep {}
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SwapEventKind {
+    DexFeeIn,
+    Commission,
+    DexFeeOut,
 }
-impl<T> Dex<T> {
-    #[allow(unused_variables)]
-
-    /// Adds any intrinsics, if necessary for this exported wasm
-    /// functionality to the `linker` provided.
-    ///
-    /// The `get_state` closure is required to access the
-    /// auxiliary data necessary for these wasm exports from
-    /// the general store's state.
-    pub fn add_to_linker(linker: &mut wasmtime::Linker<T>) -> anyhow::Result<()> {
-        Ok(())
+impl std::fmt::Debug for SwapEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SwapEventKind::DexFeeIn => f.debug_tuple("SwapEventKind::DexFeeIn").finish(),
+            SwapEventKind::Commission => f.debug_tuple("SwapEventKind::Commission").finish(),
+            SwapEventKind::DexFeeOut => f.debug_tuple("SwapEventKind::DexFeeOut").finish(),
+        }
     }
-
-    /// Instantiates the provided `module` using the specified
-    /// parameters, wrapping up the result in a structure that
-    /// translates between wasm and the host.
-    ///
-    /// The `linker` provided will have intrinsics added to it
-    /// automatically, so it's not necessary to call
-    /// `add_to_linker` beforehand. This function will
-    /// instantiate the `module` otherwise using `linker`, and
-    /// both an instance of this structure and the underlying
-    /// `wasmtime::Instance` will be returned.
-    ///
-    /// The `get_state` parameter is used to access the
-    /// auxiliary state necessary for these wasm exports from
-    /// the general store state `T`.
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SwapEvent {
+    pub kind: SwapEventKind,
+    pub token_id: DctId,
+    pub amount: i64,
+}
+impl std::fmt::Debug for SwapEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwapEvent")
+            .field("kind", &self.kind)
+            .field("token-id", &self.token_id)
+            .field("amount", &self.amount)
+            .finish()
+    }
+}
+#[derive(Clone)]
+pub struct SwapTraceResult {
+    pub swap_result: SwapResult,
+    pub steps: Vec<SwapStep>,
+    pub events: Vec<SwapEvent>,
+}
+impl std::fmt::Debug for SwapTraceResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwapTraceResult")
+            .field("swap-result", &self.swap_result)
+            .field("steps", &self.steps)
+            .field("events", &self.events)
+            .finish()
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SwapOperation {
+    pub pool_index: u32,
+    pub token_in: TokenAmount,
+    pub max_price: PoolPrice,
+    pub dex_fee: DexFee,
+}
+impl std::fmt::Debug for SwapOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwapOperation")
+            .field("pool-index", &self.pool_index)
+            .field("token-in", &self.token_in)
+            .field("max-price", &self.max_price)
+            .field("dex-fee", &self.dex_fee)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for SwapOperation {
+    fn into_le(self) -> Self {
+        Self {
+            pool_index: self.pool_index.into_le(),
+            token_in: self.token_in.into_le(),
+            max_price: self.max_price.into_le(),
+            dex_fee: self.dex_fee.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            pool_index: self.pool_index.from_le(),
+            token_in: self.token_in.from_le(),
+            max_price: self.max_price.from_le(),
+            dex_fee: self.dex_fee.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for SwapOperation {}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SwapOutput {
+    pub amount: i64,
+    pub dex_fee_in: i64,
+    pub dex_fee_out: i64,
+}
+impl std::fmt::Debug for SwapOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwapOutput")
+            .field("amount", &self.amount)
+            .field("dex-fee-in", &self.dex_fee_in)
+            .field("dex-fee-out", &self.dex_fee_out)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for SwapOutput {
+    fn into_le(self) -> Self {
+        Self {
+            amount: self.amount.into_le(),
+            dex_fee_in: self.dex_fee_in.into_le(),
+            dex_fee_out: self.dex_fee_out.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            amount: self.amount.from_le(),
+            dex_fee_in: self.dex_fee_in.from_le(),
+            dex_fee_out: self.dex_fee_out.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for SwapOutput {}
+#[derive(Clone)]
+pub struct SwapBatchResult {
+    pub pool_pairs: Vec<PoolPair>,
+    pub results: Vec<Result<SwapOutput, Error>>,
+}
+impl std::fmt::Debug for SwapBatchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwapBatchResult")
+            .field("pool-pairs", &self.pool_pairs)
+            .field("results", &self.results)
+            .finish()
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct LiquidityResult {
+    pub pool_pair: PoolPair,
+    pub amount_a: i64,
+    pub amount_b: i64,
+    pub liquidity: i64,
+}
+impl std::fmt::Debug for LiquidityResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LiquidityResult")
+            .field("pool-pair", &self.pool_pair)
+            .field("amount-a", &self.amount_a)
+            .field("amount-b", &self.amount_b)
+            .field("liquidity", &self.liquidity)
+            .finish()
+    }
+}
+pub type RewardPerShare = CumulativePrice;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct RewardPool {
+    pub token_id: DctId,
+    pub reward_per_block: i64,
+    pub reward_per_share: RewardPerShare,
+    pub last_height: u32,
+}
+impl std::fmt::Debug for RewardPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RewardPool")
+            .field("token-id", &self.token_id)
+            .field("reward-per-block", &self.reward_per_block)
+            .field("reward-per-share", &self.reward_per_share)
+            .field("last-height", &self.last_height)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for RewardPool {
+    fn into_le(self) -> Self {
+        Self {
+            token_id: self.token_id.into_le(),
+            reward_per_block: self.reward_per_block.into_le(),
+            reward_per_share: self.reward_per_share.into_le(),
+            last_height: self.last_height.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            token_id: self.token_id.from_le(),
+            reward_per_block: self.reward_per_block.from_le(),
+            reward_per_share: self.reward_per_share.from_le(),
+            last_height: self.last_height.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for RewardPool {}
+#[derive(Clone)]
+pub struct ClaimResult {
+    pub amounts: Vec<TokenAmount>,
+    pub paid: Vec<RewardPerShare>,
+}
+impl std::fmt::Debug for ClaimResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClaimResult")
+            .field("amounts", &self.amounts)
+            .field("paid", &self.paid)
+            .finish()
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct TwapResult {
+    pub price_ab: PoolPrice,
+    pub price_ba: PoolPrice,
+}
+impl std::fmt::Debug for TwapResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwapResult")
+            .field("price-ab", &self.price_ab)
+            .field("price-ba", &self.price_ba)
+            .finish()
+    }
+}
+impl wit_bindgen_wasmtime::Endian for TwapResult {
+    fn into_le(self) -> Self {
+        Self {
+            price_ab: self.price_ab.into_le(),
+            price_ba: self.price_ba.into_le(),
+        }
+    }
+    fn from_le(self) -> Self {
+        Self {
+            price_ab: self.price_ab.from_le(),
+            price_ba: self.price_ba.from_le(),
+        }
+    }
+}
+unsafe impl wit_bindgen_wasmtime::AllBytesValid for TwapResult {}
+#[derive(Clone)]
+pub struct CompositeSwapResult {
+    pub pool_pairs: Vec<PoolPair>,
+    pub slop_swap_result: i64,
+}
+impl std::fmt::Debug for CompositeSwapResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositeSwapResult")
+            .field("pool-pairs", &self.pool_pairs)
+            .field("slop-swap-result", &self.slop_swap_result)
+            .finish()
+    }
+}
+#[derive(Clone)]
+pub struct SwapRoute {
+    pub path: Vec<u32>,
+    pub slop_swap_result: i64,
+}
+impl std::fmt::Debug for SwapRoute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SwapRoute")
+            .field("path", &self.path)
+            .field("slop-swap-result", &self.slop_swap_result)
+            .finish()
+    }
+}
+#[derive(Clone)]
+pub struct BestPathResult {
+    pub best: SwapRoute,
+    pub alternatives: Vec<SwapRoute>,
+}
+impl std::fmt::Debug for BestPathResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BestPathResult")
+            .field("best", &self.best)
+            .field("alternatives", &self.alternatives)
+            .finish()
+    }
+}
+
+/// Auxiliary data associated with the wasm exports.
+///
+/// This is required to be stored within the data of a
+/// `Store<T>` itself so lifting/lowering state can be managed
+/// when translating between the host and wasm.
+#[derive(Default)]
+pub struct DexData {}
+
+type SwapTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type SwapTraceTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type SwapBatchTypedFunc = wasmtime::TypedFunc<(i32, i32, i32, i32, i32), (i32,)>;
+
+type CompositeSwapTypedFunc =
+    wasmtime::TypedFunc<(i32, i32, i32, i64, i64, i64, i32, i32, i32), (i32,)>;
+
+type BestPathTypedFunc =
+    wasmtime::TypedFunc<(i32, i32, i32, i64, i32, i32, i64, i64, i32, i32, i32), (i32,)>;
+
+type QuoteTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type AddLiquidityTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type RemoveLiquidityTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type CreatePoolPairTypedFunc =
+    wasmtime::TypedFunc<(i32, i32, i32, i32, i32, i32, i32, i32, i32, i32), (i32,)>;
+
+type UpdatePoolPairTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type AccumulatePriceTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type TwapTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type DistributeTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type ClaimTypedFunc = wasmtime::TypedFunc<(i32,), (i32,)>;
+
+type SerializePoolsTypedFunc = wasmtime::TypedFunc<(i32, i32), (i32,)>;
+
+type DeserializePoolsTypedFunc = wasmtime::TypedFunc<(i32, i32), (i32,)>;
+
+type PoolsHashTypedFunc = wasmtime::TypedFunc<(i32, i32), (i32,)>;
+
+type DexStabilizationFeeTypedFunc = wasmtime::TypedFunc<(i64, i64, i64), (i32,)>;
+
+pub struct Dex<T> {
+    canonical_abi_free: wasmtime::TypedFunc<(i32, i32, i32), ()>,
+    canonical_abi_realloc: wasmtime::TypedFunc<(i32, i32, i32, i32), (i32,)>,
+    memory: wasmtime::Memory,
+    swap: SwapTypedFunc,
+    swap_trace: SwapTraceTypedFunc,
+    swap_batch: SwapBatchTypedFunc,
+    composite_swap: CompositeSwapTypedFunc,
+    best_path: BestPathTypedFunc,
+    quote: QuoteTypedFunc,
+    add_liquidity: AddLiquidityTypedFunc,
+    remove_liquidity: RemoveLiquidityTypedFunc,
+    create_pool_pair: CreatePoolPairTypedFunc,
+    update_pool_pair: UpdatePoolPairTypedFunc,
+    accumulate_price: AccumulatePriceTypedFunc,
+    twap: TwapTypedFunc,
+    distribute: DistributeTypedFunc,
+    claim: ClaimTypedFunc,
+    serialize_pools: SerializePoolsTypedFunc,
+    deserialize_pools: DeserializePoolsTypedFunc,
+    pools_hash: PoolsHashTypedFunc,
+    dex_stabilization_fee: DexStabilizationFeeTypedFunc,
+    data: PhantomData<T>,
+}
+impl<T> Dex<T> {
+    #[allow(unused_variables)]
+
+    /// Adds any intrinsics, if necessary for this exported wasm
+    /// functionality to the `linker` provided.
+    ///
+    /// The `get_state` closure is required to access the
+    /// auxiliary data necessary for these wasm exports from
+    /// the general store's state.
+    pub fn add_to_linker(linker: &mut wasmtime::Linker<T>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Instantiates the provided `module` using the specified
+    /// parameters, wrapping up the result in a structure that
+    /// translates between wasm and the host.
+    ///
+    /// The `linker` provided will have intrinsics added to it
+    /// automatically, so it's not necessary to call
+    /// `add_to_linker` beforehand. This function will
+    /// instantiate the `module` otherwise using `linker`, and
+    /// both an instance of this structure and the underlying
+    /// `wasmtime::Instance` will be returned.
+    ///
+    /// The `get_state` parameter is used to access the
+    /// auxiliary state necessary for these wasm exports from
+    /// the general store state `T`.
     pub fn instantiate(
         mut store: impl wasmtime::AsContextMut<Data = T>,
         module: &wasmtime::Module,
@@ -247,115 +885,3874 @@ impl<T> Dex<T> {
         instance: &wasmtime::Instance,
     ) -> anyhow::Result<Self> {
         let mut store = store.as_context_mut();
+        let canonical_abi_free =
+            instance.get_typed_func::<(i32, i32, i32), (), _>(&mut store, "canonical_abi_free")?;
+        let canonical_abi_realloc = instance.get_typed_func::<(i32, i32, i32, i32), (i32,), _>(
+            &mut store,
+            "canonical_abi_realloc",
+        )?;
         let memory = instance
             .get_memory(&mut store, "memory")
             .ok_or_else(|| anyhow::anyhow!("`memory` export not a memory"))?;
-        let swap = instance.get_typed_func::<(
-            i32,
-            i32,
-            i32,
-            i64,
-            i64,
-            i64,
-            i64,
-            i64,
-            i32,
-            i64,
-            i64,
-            i64,
-            i32,
-        ), (i32,), _>(&mut store, "swap")?;
+        let swap = instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "swap")?;
+        let swap_trace = instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "swap-trace")?;
+        let swap_batch = instance
+            .get_typed_func::<(i32, i32, i32, i32, i32), (i32,), _>(&mut store, "swap-batch")?;
+        let composite_swap = instance
+            .get_typed_func::<(i32, i32, i32, i64, i64, i64, i32, i32, i32), (i32,), _>(
+                &mut store,
+                "composite-swap",
+            )?;
+        let best_path = instance
+            .get_typed_func::<(i32, i32, i32, i64, i32, i32, i64, i64, i32, i32, i32), (i32,), _>(
+                &mut store,
+                "best-path",
+            )?;
+        let quote = instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "quote")?;
+        let add_liquidity =
+            instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "add-liquidity")?;
+        let remove_liquidity =
+            instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "remove-liquidity")?;
+        let create_pool_pair = instance
+            .get_typed_func::<(i32, i32, i32, i32, i32, i32, i32, i32, i32, i32), (i32,), _>(
+                &mut store,
+                "create-pool-pair",
+            )?;
+        let update_pool_pair =
+            instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "update-pool-pair")?;
+        let accumulate_price =
+            instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "accumulate-price")?;
+        let twap = instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "twap")?;
+        let distribute = instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "distribute")?;
+        let claim = instance.get_typed_func::<(i32,), (i32,), _>(&mut store, "claim")?;
+        let serialize_pools =
+            instance.get_typed_func::<(i32, i32), (i32,), _>(&mut store, "serialize-pools")?;
+        let deserialize_pools =
+            instance.get_typed_func::<(i32, i32), (i32,), _>(&mut store, "deserialize-pools")?;
+        let pools_hash =
+            instance.get_typed_func::<(i32, i32), (i32,), _>(&mut store, "pools-hash")?;
+        let dex_stabilization_fee = instance
+            .get_typed_func::<(i64, i64, i64), (i32,), _>(&mut store, "dex-stabilization-fee")?;
         Ok(Dex {
+            canonical_abi_free,
+            canonical_abi_realloc,
             memory,
             swap,
+            swap_trace,
+            swap_batch,
+            composite_swap,
+            best_path,
+            quote,
+            add_liquidity,
+            remove_liquidity,
+            create_pool_pair,
+            update_pool_pair,
+            accumulate_price,
+            twap,
+            distribute,
+            claim,
+            serialize_pools,
+            deserialize_pools,
+            pools_hash,
+            dex_stabilization_fee,
             data: Default::default(),
         })
     }
+    #[allow(clippy::too_many_arguments)]
     pub fn swap(
         &self,
         mut caller: impl wasmtime::AsContextMut<Data = T>,
         poolpair: PoolPair,
         token_in: TokenAmount,
         max_price: PoolPrice,
+        min_output: Option<i64>,
+        dex_fee: DexFee,
         post_bayfront_gardens: bool,
     ) -> Result<Result<SwapResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
         let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 200))?;
         let PoolPair {
-            token_a: token_a0,
-            token_b: token_b0,
-            commission: commission0,
-            reserve_a: reserve_a0,
-            reserve_b: reserve_b0,
-            total_liquidity: total_liquidity0,
-            block_commission_a: block_commission_a0,
-            block_commission_b: block_commission_b0,
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
         } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
         let TokenAmount {
-            token_id: token_id1,
-            amount: amount1,
+            token_id: token_id4,
+            amount: amount4,
         } = token_in;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 112, wit_bindgen_wasmtime::rt::as_i32(token_id4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 120, wit_bindgen_wasmtime::rt::as_i64(amount4))?;
         let PoolPrice {
-            integer: integer2,
-            fraction: fraction2,
+            integer: integer5,
+            fraction: fraction5,
         } = max_price;
-        let result3 = match post_bayfront_gardens {
-            false => 0i32,
-            true => 1i32,
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 128, wit_bindgen_wasmtime::rt::as_i64(integer5))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 136, wit_bindgen_wasmtime::rt::as_i64(fraction5))?;
+        match min_output {
+            Some(e) => {
+                memory.data_mut(&mut caller).store(ptr0 + 144, 1u8)?;
+                memory
+                    .data_mut(&mut caller)
+                    .store(ptr0 + 152, wit_bindgen_wasmtime::rt::as_i64(e))?;
+            }
+            None => {
+                memory.data_mut(&mut caller).store(ptr0 + 144, 0u8)?;
+            }
         };
-        let (result4_0,) = self.swap.call(
-            &mut caller,
-            (
-                wit_bindgen_wasmtime::rt::as_i32(token_a0),
-                wit_bindgen_wasmtime::rt::as_i32(token_b0),
-                wit_bindgen_wasmtime::rt::as_i32(commission0),
-                wit_bindgen_wasmtime::rt::as_i64(reserve_a0),
-                wit_bindgen_wasmtime::rt::as_i64(reserve_b0),
-                wit_bindgen_wasmtime::rt::as_i64(total_liquidity0),
-                wit_bindgen_wasmtime::rt::as_i64(block_commission_a0),
-                wit_bindgen_wasmtime::rt::as_i64(block_commission_b0),
-                wit_bindgen_wasmtime::rt::as_i32(token_id1),
-                wit_bindgen_wasmtime::rt::as_i64(amount1),
-                wit_bindgen_wasmtime::rt::as_i64(integer2),
-                wit_bindgen_wasmtime::rt::as_i64(fraction2),
-                result3,
-            ),
+        let DexFee {
+            in_pct_ab: in_pct_ab6,
+            out_pct_ab: out_pct_ab6,
+            in_pct_ba: in_pct_ba6,
+            out_pct_ba: out_pct_ba6,
+        } = dex_fee;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 160, wit_bindgen_wasmtime::rt::as_i64(in_pct_ab6))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 168, wit_bindgen_wasmtime::rt::as_i64(out_pct_ab6))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 176, wit_bindgen_wasmtime::rt::as_i64(in_pct_ba6))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 184, wit_bindgen_wasmtime::rt::as_i64(out_pct_ba6))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 192,
+            match post_bayfront_gardens {
+                false => 0u8,
+                true => 1u8,
+            },
         )?;
-        let load5 = memory.data_mut(&mut caller).load::<i32>(result4_0)?;
-        let load6 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 8)?;
-        let load7 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 16)?;
-        let load8 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 24)?;
-        let load9 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 32)?;
-        let load10 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 40)?;
-        let load11 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 48)?;
-        let load12 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 56)?;
-        let load13 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 64)?;
-        let load14 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 72)?;
-        Ok(match load5 {
+        let (result7_0,) = self.swap.call(&mut caller, (ptr0,))?;
+        let load8 = memory.data_mut(&mut caller).load::<i32>(result7_0)?;
+        let load9 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 8)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 16)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 24)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 32)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 40)?;
+        let load14 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 48)?;
+        let load15 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 56)?;
+        let load16 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 64)?;
+        let load17 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 72)?;
+        let load18 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 80)?;
+        let load19 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 88)?;
+        let load20 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 96)?;
+        let load21 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 104)?;
+        let load22 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 112)?;
+        let load23 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 120)?;
+        let load24 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 128)?;
+        let load25 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 136)?;
+        let load26 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 144)?;
+        let load27 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 152)?;
+        let load28 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 160)?;
+        let load29 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 168)?;
+        Ok(match load8 {
             0 => Ok(SwapResult {
                 pool_pair: PoolPair {
-                    token_a: load6 as u32,
-                    token_b: load7 as u32,
-                    commission: load8 as u32,
-                    reserve_a: load9,
-                    reserve_b: load10,
-                    total_liquidity: load11,
-                    block_commission_a: load12,
-                    block_commission_b: load13,
+                    token_a: load9 as u32,
+                    token_b: load10 as i32 as u32,
+                    commission: load11 as i32 as u32,
+                    reserve_a: load12,
+                    reserve_b: load13,
+                    total_liquidity: load14,
+                    block_commission_a: load15,
+                    block_commission_b: load16,
+                    pool_type: match load17 {
+                        0 => PoolType::ConstantProduct,
+                        1 => PoolType::StableSwap,
+                        2 => PoolType::Weighted,
+                        _ => return Err(invalid_variant("PoolType")),
+                    },
+                    amplification: load18 as u32,
+                    weight_a: load19 as u32,
+                    weight_b: load20 as u32,
+                    price_ab_cumulative: CumulativePrice {
+                        high: load21 as u64,
+                        low: load22 as u64,
+                    },
+                    price_ba_cumulative: CumulativePrice {
+                        high: load23 as u64,
+                        low: load24 as u64,
+                    },
+                    price_height: load25 as u32,
+                    status: match load26 {
+                        0 => false,
+                        1 => true,
+                        _ => return Err(invalid_variant("bool")),
+                    },
                 },
-                slop_swap_result: load14,
+                slop_swap_result: load27,
+                dex_fee_in: load28,
+                dex_fee_out: load29,
             }),
-            1 => Err(match load6 {
+            1 => Err(match load9 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load10,
+                        fraction: load11,
+                    },
+                    max_price: PoolPrice {
+                        integer: load12,
+                        fraction: load13,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load10,
+                    min_output: load11,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load10 as i32 as u32,
+                    token_a: load11 as i32 as u32,
+                    token_b: load12 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                10 => Error::InvalidPoolConfig(match load10 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load10 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_trace(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpair: PoolPair,
+        token_in: TokenAmount,
+        max_price: PoolPrice,
+        min_output: Option<i64>,
+        dex_fee: DexFee,
+        post_bayfront_gardens: bool,
+    ) -> Result<Result<SwapTraceResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 200))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        let TokenAmount {
+            token_id: token_id4,
+            amount: amount4,
+        } = token_in;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 112, wit_bindgen_wasmtime::rt::as_i32(token_id4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 120, wit_bindgen_wasmtime::rt::as_i64(amount4))?;
+        let PoolPrice {
+            integer: integer5,
+            fraction: fraction5,
+        } = max_price;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 128, wit_bindgen_wasmtime::rt::as_i64(integer5))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 136, wit_bindgen_wasmtime::rt::as_i64(fraction5))?;
+        match min_output {
+            Some(e) => {
+                memory.data_mut(&mut caller).store(ptr0 + 144, 1u8)?;
+                memory
+                    .data_mut(&mut caller)
+                    .store(ptr0 + 152, wit_bindgen_wasmtime::rt::as_i64(e))?;
+            }
+            None => {
+                memory.data_mut(&mut caller).store(ptr0 + 144, 0u8)?;
+            }
+        };
+        let DexFee {
+            in_pct_ab: in_pct_ab6,
+            out_pct_ab: out_pct_ab6,
+            in_pct_ba: in_pct_ba6,
+            out_pct_ba: out_pct_ba6,
+        } = dex_fee;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 160, wit_bindgen_wasmtime::rt::as_i64(in_pct_ab6))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 168, wit_bindgen_wasmtime::rt::as_i64(out_pct_ab6))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 176, wit_bindgen_wasmtime::rt::as_i64(in_pct_ba6))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 184, wit_bindgen_wasmtime::rt::as_i64(out_pct_ba6))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 192,
+            match post_bayfront_gardens {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        let (result7_0,) = self.swap_trace.call(&mut caller, (ptr0,))?;
+        let load8 = memory.data_mut(&mut caller).load::<i32>(result7_0)?;
+        let load9 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 8)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 16)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 24)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 32)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 40)?;
+        let load14 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 48)?;
+        let load15 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 56)?;
+        let load16 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 64)?;
+        let load17 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 72)?;
+        let load18 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 80)?;
+        let load19 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 88)?;
+        let load20 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 96)?;
+        let load21 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 104)?;
+        let load22 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 112)?;
+        let load23 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 120)?;
+        let load24 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 128)?;
+        let load25 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 136)?;
+        let load26 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 144)?;
+        let load27 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 152)?;
+        let load28 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 160)?;
+        let load29 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 168)?;
+        let load30 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 176)?;
+        let load31 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 184)?;
+        let load32 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 192)?;
+        let load33 = memory.data_mut(&mut caller).load::<i32>(result7_0 + 200)?;
+        Ok(match load8 {
+            0 => Ok(SwapTraceResult {
+                swap_result: SwapResult {
+                    pool_pair: PoolPair {
+                        token_a: load9 as u32,
+                        token_b: load10 as i32 as u32,
+                        commission: load11 as i32 as u32,
+                        reserve_a: load12,
+                        reserve_b: load13,
+                        total_liquidity: load14,
+                        block_commission_a: load15,
+                        block_commission_b: load16,
+                        pool_type: match load17 {
+                            0 => PoolType::ConstantProduct,
+                            1 => PoolType::StableSwap,
+                            2 => PoolType::Weighted,
+                            _ => return Err(invalid_variant("PoolType")),
+                        },
+                        amplification: load18 as u32,
+                        weight_a: load19 as u32,
+                        weight_b: load20 as u32,
+                        price_ab_cumulative: CumulativePrice {
+                            high: load21 as u64,
+                            low: load22 as u64,
+                        },
+                        price_ba_cumulative: CumulativePrice {
+                            high: load23 as u64,
+                            low: load24 as u64,
+                        },
+                        price_height: load25 as u32,
+                        status: match load26 {
+                            0 => false,
+                            1 => true,
+                            _ => return Err(invalid_variant("bool")),
+                        },
+                    },
+                    slop_swap_result: load27,
+                    dex_fee_in: load28,
+                    dex_fee_out: load29,
+                },
+                steps: {
+                    let len35 = load31;
+                    let data35 = copy_slice(&mut caller, memory, load30, len35, 8)?;
+                    func_canonical_abi_free.call(&mut caller, (load30, len35 * 32, 8))?;
+                    data35
+                },
+                events: {
+                    let len36 = load33;
+                    let base36 = load32;
+                    let mut result36 = Vec::with_capacity(len36 as usize);
+                    for i in 0..len36 {
+                        let base = base36 + i * 16;
+                        result36.push(SwapEvent {
+                            kind: match i32::from(memory.data_mut(&mut caller).load::<u8>(base)?) {
+                                0 => SwapEventKind::DexFeeIn,
+                                1 => SwapEventKind::Commission,
+                                2 => SwapEventKind::DexFeeOut,
+                                _ => return Err(invalid_variant("SwapEventKind")),
+                            },
+                            token_id: memory.data_mut(&mut caller).load::<i32>(base + 4)? as u32,
+                            amount: memory.data_mut(&mut caller).load::<i64>(base + 8)?,
+                        });
+                    }
+                    func_canonical_abi_free.call(&mut caller, (base36, len36 * 16, 8))?;
+                    result36
+                },
+            }),
+            1 => Err(match load9 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load10,
+                        fraction: load11,
+                    },
+                    max_price: PoolPrice {
+                        integer: load12,
+                        fraction: load13,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load10,
+                    min_output: load11,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load10 as i32 as u32,
+                    token_a: load11 as i32 as u32,
+                    token_b: load12 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                10 => Error::InvalidPoolConfig(match load10 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load10 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn swap_batch(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpairs: &[PoolPair],
+        operations: &[SwapOperation],
+        post_bayfront_gardens: bool,
+    ) -> Result<Result<SwapBatchResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        let vec1 = poolpairs;
+        let len1 = vec1.len() as i32;
+        let ptr1 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len1 * 112))?;
+        for (i, e) in vec1.iter().enumerate() {
+            let base = ptr1 + (i as i32) * 112;
+            let PoolPair {
+                token_a: token_a2,
+                token_b: token_b2,
+                commission: commission2,
+                reserve_a: reserve_a2,
+                reserve_b: reserve_b2,
+                total_liquidity: total_liquidity2,
+                block_commission_a: block_commission_a2,
+                block_commission_b: block_commission_b2,
+                pool_type: pool_type2,
+                amplification: amplification2,
+                weight_a: weight_a2,
+                weight_b: weight_b2,
+                price_ab_cumulative: price_ab_cumulative2,
+                price_ba_cumulative: price_ba_cumulative2,
+                price_height: price_height2,
+                status: status2,
+            } = *e;
+            memory
+                .data_mut(&mut caller)
+                .store(base, wit_bindgen_wasmtime::rt::as_i32(token_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 4, wit_bindgen_wasmtime::rt::as_i32(token_b2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 8, wit_bindgen_wasmtime::rt::as_i32(commission2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b2))?;
+            memory.data_mut(&mut caller).store(
+                base + 32,
+                wit_bindgen_wasmtime::rt::as_i64(total_liquidity2),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 40,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_a2),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 48,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_b2),
+            )?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 56, (pool_type2 as i32) as u8)?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 60, wit_bindgen_wasmtime::rt::as_i32(amplification2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b2))?;
+            let CumulativePrice {
+                high: high3,
+                low: low3,
+            } = price_ab_cumulative2;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 72, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 80, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+            let CumulativePrice {
+                high: high4,
+                low: low4,
+            } = price_ba_cumulative2;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 88, wit_bindgen_wasmtime::rt::as_i64(high4))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 96, wit_bindgen_wasmtime::rt::as_i64(low4))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 104, wit_bindgen_wasmtime::rt::as_i32(price_height2))?;
+            memory.data_mut(&mut caller).store(
+                base + 108,
+                match status2 {
+                    false => 0u8,
+                    true => 1u8,
+                },
+            )?;
+        }
+        let vec5 = operations;
+        let len5 = vec5.len() as i32;
+        let ptr5 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len5 * 72))?;
+        memory.data_mut(&mut caller).store_many(ptr5, vec5)?;
+        let (result6_0,) = self.swap_batch.call(
+            &mut caller,
+            (
+                ptr1,
+                len1,
+                ptr5,
+                len5,
+                match post_bayfront_gardens {
+                    false => 0i32,
+                    true => 1i32,
+                },
+            ),
+        )?;
+        let load7 = memory.data_mut(&mut caller).load::<i32>(result6_0)?;
+        let load8 = memory.data_mut(&mut caller).load::<i32>(result6_0 + 8)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 16)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 24)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 32)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 40)?;
+        Ok(match load7 {
+            0 => Ok(SwapBatchResult {
+                pool_pairs: {
+                    let len14 = load9 as i32;
+                    let base14 = load8;
+                    let mut result14 = Vec::with_capacity(len14 as usize);
+                    for i in 0..len14 {
+                        let base = base14 + i * 112;
+                        result14.push(PoolPair {
+                            token_a: memory.data_mut(&mut caller).load::<i32>(base)? as u32,
+                            token_b: memory.data_mut(&mut caller).load::<i32>(base + 4)? as u32,
+                            commission: memory.data_mut(&mut caller).load::<i32>(base + 8)? as u32,
+                            reserve_a: memory.data_mut(&mut caller).load::<i64>(base + 16)?,
+                            reserve_b: memory.data_mut(&mut caller).load::<i64>(base + 24)?,
+                            total_liquidity: memory.data_mut(&mut caller).load::<i64>(base + 32)?,
+                            block_commission_a: memory
+                                .data_mut(&mut caller)
+                                .load::<i64>(base + 40)?,
+                            block_commission_b: memory
+                                .data_mut(&mut caller)
+                                .load::<i64>(base + 48)?,
+                            pool_type: match i32::from(
+                                memory.data_mut(&mut caller).load::<u8>(base + 56)?,
+                            ) {
+                                0 => PoolType::ConstantProduct,
+                                1 => PoolType::StableSwap,
+                                2 => PoolType::Weighted,
+                                _ => return Err(invalid_variant("PoolType")),
+                            },
+                            amplification: memory.data_mut(&mut caller).load::<i32>(base + 60)?
+                                as u32,
+                            weight_a: memory.data_mut(&mut caller).load::<i32>(base + 64)? as u32,
+                            weight_b: memory.data_mut(&mut caller).load::<i32>(base + 68)? as u32,
+                            price_ab_cumulative: CumulativePrice {
+                                high: memory.data_mut(&mut caller).load::<i64>(base + 72)? as u64,
+                                low: memory.data_mut(&mut caller).load::<i64>(base + 80)? as u64,
+                            },
+                            price_ba_cumulative: CumulativePrice {
+                                high: memory.data_mut(&mut caller).load::<i64>(base + 88)? as u64,
+                                low: memory.data_mut(&mut caller).load::<i64>(base + 96)? as u64,
+                            },
+                            price_height: memory.data_mut(&mut caller).load::<i32>(base + 104)?
+                                as u32,
+                            status: match i32::from(
+                                memory.data_mut(&mut caller).load::<u8>(base + 108)?,
+                            ) {
+                                0 => false,
+                                1 => true,
+                                _ => return Err(invalid_variant("bool")),
+                            },
+                        });
+                    }
+                    func_canonical_abi_free.call(&mut caller, (base14, len14 * 112, 8))?;
+                    result14
+                },
+                results: {
+                    let len15 = load11 as i32;
+                    let base15 = load10 as i32;
+                    let mut result15 = Vec::with_capacity(len15 as usize);
+                    for i in 0..len15 {
+                        let base = base15 + i * 48;
+                        result15.push(
+                            match i32::from(memory.data_mut(&mut caller).load::<u8>(base)?) {
+                                0 => Ok(SwapOutput {
+                                    amount: memory.data_mut(&mut caller).load::<i64>(base + 8)?,
+                                    dex_fee_in: memory
+                                        .data_mut(&mut caller)
+                                        .load::<i64>(base + 16)?,
+                                    dex_fee_out: memory
+                                        .data_mut(&mut caller)
+                                        .load::<i64>(base + 24)?,
+                                }),
+                                1 => Err(
+                                    match i32::from(
+                                        memory.data_mut(&mut caller).load::<u8>(base + 8)?,
+                                    ) {
+                                        0 => Error::Runtime,
+                                        1 => Error::NotFound,
+                                        2 => Error::InvalidInput,
+                                        3 => Error::LackOfLiquidity(Reserves {
+                                            reserve_a: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i64>(base + 16)?,
+                                            reserve_b: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i64>(base + 24)?,
+                                        }),
+                                        4 => Error::PriceHigherThanIndex(PriceLimit {
+                                            price: PoolPrice {
+                                                integer: memory
+                                                    .data_mut(&mut caller)
+                                                    .load::<i64>(base + 16)?,
+                                                fraction: memory
+                                                    .data_mut(&mut caller)
+                                                    .load::<i64>(base + 24)?,
+                                            },
+                                            max_price: PoolPrice {
+                                                integer: memory
+                                                    .data_mut(&mut caller)
+                                                    .load::<i64>(base + 32)?,
+                                                fraction: memory
+                                                    .data_mut(&mut caller)
+                                                    .load::<i64>(base + 40)?,
+                                            },
+                                        }),
+                                        5 => Error::PoolReserveOverflow(Reserves {
+                                            reserve_a: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i64>(base + 16)?,
+                                            reserve_b: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i64>(base + 24)?,
+                                        }),
+                                        6 => Error::OutputBelowMinimum(OutputLimit {
+                                            amount: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i64>(base + 16)?,
+                                            min_output: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i64>(base + 24)?,
+                                        }),
+                                        7 => Error::TokenMismatch(InputTokenMismatch {
+                                            token_id: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i32>(base + 16)?
+                                                as u32,
+                                            token_a: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i32>(base + 20)?
+                                                as u32,
+                                            token_b: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i32>(base + 24)?
+                                                as u32,
+                                        }),
+                                        8 => Error::ArithmeticOverflow,
+                                        9 => Error::InvariantViolation(Reserves {
+                                            reserve_a: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i64>(base + 16)?,
+                                            reserve_b: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i64>(base + 24)?,
+                                        }),
+                                        10 => Error::InvalidPoolConfig(
+                                            match i32::from(
+                                                memory
+                                                    .data_mut(&mut caller)
+                                                    .load::<u8>(base + 16)?,
+                                            ) {
+                                                0 => PoolConfigError::SameToken,
+                                                1 => PoolConfigError::CommissionOutOfRange,
+                                                2 => PoolConfigError::InvalidOwnerAddress,
+                                                3 => PoolConfigError::InvalidPoolType,
+                                                _ => {
+                                                    return Err(invalid_variant("PoolConfigError"))
+                                                }
+                                            },
+                                        ),
+                                        11 => Error::PoolNotFound(PoolIndex {
+                                            index: memory
+                                                .data_mut(&mut caller)
+                                                .load::<i32>(base + 16)?
+                                                as u32,
+                                        }),
+                                        12 => Error::PoolDisabled,
+                                        _ => return Err(invalid_variant("Error")),
+                                    },
+                                ),
+                                _ => return Err(invalid_variant("Result")),
+                            },
+                        );
+                    }
+                    func_canonical_abi_free.call(&mut caller, (base15, len15 * 48, 8))?;
+                    result15
+                },
+            }),
+            1 => Err(match load8 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                    max_price: PoolPrice {
+                        integer: load11,
+                        fraction: load12,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load9,
+                    min_output: load10,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load9 as i32 as u32,
+                    token_a: load10 as i32 as u32,
+                    token_b: load11 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                10 => Error::InvalidPoolConfig(match load9 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load9 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn composite_swap(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpairs: &[PoolPair],
+        token_in: TokenAmount,
+        max_price: PoolPrice,
+        dex_fees: &[DexFee],
+        post_bayfront_gardens: bool,
+    ) -> Result<Result<CompositeSwapResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        let vec1 = poolpairs;
+        let len1 = vec1.len() as i32;
+        let ptr1 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len1 * 112))?;
+        for (i, e) in vec1.iter().enumerate() {
+            let base = ptr1 + (i as i32) * 112;
+            let PoolPair {
+                token_a: token_a2,
+                token_b: token_b2,
+                commission: commission2,
+                reserve_a: reserve_a2,
+                reserve_b: reserve_b2,
+                total_liquidity: total_liquidity2,
+                block_commission_a: block_commission_a2,
+                block_commission_b: block_commission_b2,
+                pool_type: pool_type2,
+                amplification: amplification2,
+                weight_a: weight_a2,
+                weight_b: weight_b2,
+                price_ab_cumulative: price_ab_cumulative2,
+                price_ba_cumulative: price_ba_cumulative2,
+                price_height: price_height2,
+                status: status2,
+            } = *e;
+            memory
+                .data_mut(&mut caller)
+                .store(base, wit_bindgen_wasmtime::rt::as_i32(token_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 4, wit_bindgen_wasmtime::rt::as_i32(token_b2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 8, wit_bindgen_wasmtime::rt::as_i32(commission2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b2))?;
+            memory.data_mut(&mut caller).store(
+                base + 32,
+                wit_bindgen_wasmtime::rt::as_i64(total_liquidity2),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 40,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_a2),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 48,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_b2),
+            )?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 56, (pool_type2 as i32) as u8)?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 60, wit_bindgen_wasmtime::rt::as_i32(amplification2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b2))?;
+            let CumulativePrice {
+                high: high3,
+                low: low3,
+            } = price_ab_cumulative2;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 72, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 80, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+            let CumulativePrice {
+                high: high4,
+                low: low4,
+            } = price_ba_cumulative2;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 88, wit_bindgen_wasmtime::rt::as_i64(high4))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 96, wit_bindgen_wasmtime::rt::as_i64(low4))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 104, wit_bindgen_wasmtime::rt::as_i32(price_height2))?;
+            memory.data_mut(&mut caller).store(
+                base + 108,
+                match status2 {
+                    false => 0u8,
+                    true => 1u8,
+                },
+            )?;
+        }
+        let TokenAmount {
+            token_id: token_id5,
+            amount: amount5,
+        } = token_in;
+        let PoolPrice {
+            integer: integer6,
+            fraction: fraction6,
+        } = max_price;
+        let vec7 = dex_fees;
+        let len7 = vec7.len() as i32;
+        let ptr7 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len7 * 32))?;
+        memory.data_mut(&mut caller).store_many(ptr7, vec7)?;
+        let (result8_0,) = self.composite_swap.call(
+            &mut caller,
+            (
+                ptr1,
+                len1,
+                wit_bindgen_wasmtime::rt::as_i32(token_id5),
+                wit_bindgen_wasmtime::rt::as_i64(amount5),
+                wit_bindgen_wasmtime::rt::as_i64(integer6),
+                wit_bindgen_wasmtime::rt::as_i64(fraction6),
+                ptr7,
+                len7,
+                match post_bayfront_gardens {
+                    false => 0i32,
+                    true => 1i32,
+                },
+            ),
+        )?;
+        let load9 = memory.data_mut(&mut caller).load::<i32>(result8_0)?;
+        let load10 = memory.data_mut(&mut caller).load::<i32>(result8_0 + 8)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result8_0 + 16)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result8_0 + 24)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result8_0 + 32)?;
+        let load14 = memory.data_mut(&mut caller).load::<i64>(result8_0 + 40)?;
+        Ok(match load9 {
+            0 => Ok(CompositeSwapResult {
+                pool_pairs: {
+                    let len16 = load11 as i32;
+                    let base16 = load10;
+                    let mut result16 = Vec::with_capacity(len16 as usize);
+                    for i in 0..len16 {
+                        let base = base16 + i * 112;
+                        result16.push(PoolPair {
+                            token_a: memory.data_mut(&mut caller).load::<i32>(base)? as u32,
+                            token_b: memory.data_mut(&mut caller).load::<i32>(base + 4)? as u32,
+                            commission: memory.data_mut(&mut caller).load::<i32>(base + 8)? as u32,
+                            reserve_a: memory.data_mut(&mut caller).load::<i64>(base + 16)?,
+                            reserve_b: memory.data_mut(&mut caller).load::<i64>(base + 24)?,
+                            total_liquidity: memory.data_mut(&mut caller).load::<i64>(base + 32)?,
+                            block_commission_a: memory
+                                .data_mut(&mut caller)
+                                .load::<i64>(base + 40)?,
+                            block_commission_b: memory
+                                .data_mut(&mut caller)
+                                .load::<i64>(base + 48)?,
+                            pool_type: match i32::from(
+                                memory.data_mut(&mut caller).load::<u8>(base + 56)?,
+                            ) {
+                                0 => PoolType::ConstantProduct,
+                                1 => PoolType::StableSwap,
+                                2 => PoolType::Weighted,
+                                _ => return Err(invalid_variant("PoolType")),
+                            },
+                            amplification: memory.data_mut(&mut caller).load::<i32>(base + 60)?
+                                as u32,
+                            weight_a: memory.data_mut(&mut caller).load::<i32>(base + 64)? as u32,
+                            weight_b: memory.data_mut(&mut caller).load::<i32>(base + 68)? as u32,
+                            price_ab_cumulative: CumulativePrice {
+                                high: memory.data_mut(&mut caller).load::<i64>(base + 72)? as u64,
+                                low: memory.data_mut(&mut caller).load::<i64>(base + 80)? as u64,
+                            },
+                            price_ba_cumulative: CumulativePrice {
+                                high: memory.data_mut(&mut caller).load::<i64>(base + 88)? as u64,
+                                low: memory.data_mut(&mut caller).load::<i64>(base + 96)? as u64,
+                            },
+                            price_height: memory.data_mut(&mut caller).load::<i32>(base + 104)?
+                                as u32,
+                            status: match i32::from(
+                                memory.data_mut(&mut caller).load::<u8>(base + 108)?,
+                            ) {
+                                0 => false,
+                                1 => true,
+                                _ => return Err(invalid_variant("bool")),
+                            },
+                        });
+                    }
+                    func_canonical_abi_free.call(&mut caller, (base16, len16 * 112, 8))?;
+                    result16
+                },
+                slop_swap_result: load12,
+            }),
+            1 => Err(match load10 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load11,
+                    reserve_b: load12,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load11,
+                        fraction: load12,
+                    },
+                    max_price: PoolPrice {
+                        integer: load13,
+                        fraction: load14,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load11,
+                    reserve_b: load12,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load11,
+                    min_output: load12,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load11 as i32 as u32,
+                    token_a: load12 as i32 as u32,
+                    token_b: load13 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load11,
+                    reserve_b: load12,
+                }),
+                10 => Error::InvalidPoolConfig(match load11 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load11 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn best_path(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpairs: &[PoolPair],
+        token_in: TokenAmount,
+        token_to: DctId,
+        max_hops: u32,
+        max_price: PoolPrice,
+        dex_fees: &[DexFee],
+        post_bayfront_gardens: bool,
+    ) -> Result<Result<BestPathResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        let vec1 = poolpairs;
+        let len1 = vec1.len() as i32;
+        let ptr1 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len1 * 112))?;
+        for (i, e) in vec1.iter().enumerate() {
+            let base = ptr1 + (i as i32) * 112;
+            let PoolPair {
+                token_a: token_a2,
+                token_b: token_b2,
+                commission: commission2,
+                reserve_a: reserve_a2,
+                reserve_b: reserve_b2,
+                total_liquidity: total_liquidity2,
+                block_commission_a: block_commission_a2,
+                block_commission_b: block_commission_b2,
+                pool_type: pool_type2,
+                amplification: amplification2,
+                weight_a: weight_a2,
+                weight_b: weight_b2,
+                price_ab_cumulative: price_ab_cumulative2,
+                price_ba_cumulative: price_ba_cumulative2,
+                price_height: price_height2,
+                status: status2,
+            } = *e;
+            memory
+                .data_mut(&mut caller)
+                .store(base, wit_bindgen_wasmtime::rt::as_i32(token_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 4, wit_bindgen_wasmtime::rt::as_i32(token_b2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 8, wit_bindgen_wasmtime::rt::as_i32(commission2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b2))?;
+            memory.data_mut(&mut caller).store(
+                base + 32,
+                wit_bindgen_wasmtime::rt::as_i64(total_liquidity2),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 40,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_a2),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 48,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_b2),
+            )?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 56, (pool_type2 as i32) as u8)?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 60, wit_bindgen_wasmtime::rt::as_i32(amplification2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a2))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b2))?;
+            let CumulativePrice {
+                high: high3,
+                low: low3,
+            } = price_ab_cumulative2;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 72, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 80, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+            let CumulativePrice {
+                high: high4,
+                low: low4,
+            } = price_ba_cumulative2;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 88, wit_bindgen_wasmtime::rt::as_i64(high4))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 96, wit_bindgen_wasmtime::rt::as_i64(low4))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 104, wit_bindgen_wasmtime::rt::as_i32(price_height2))?;
+            memory.data_mut(&mut caller).store(
+                base + 108,
+                match status2 {
+                    false => 0u8,
+                    true => 1u8,
+                },
+            )?;
+        }
+        let TokenAmount {
+            token_id: token_id5,
+            amount: amount5,
+        } = token_in;
+        let PoolPrice {
+            integer: integer6,
+            fraction: fraction6,
+        } = max_price;
+        let vec7 = dex_fees;
+        let len7 = vec7.len() as i32;
+        let ptr7 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len7 * 32))?;
+        memory.data_mut(&mut caller).store_many(ptr7, vec7)?;
+        let (result8_0,) = self.best_path.call(
+            &mut caller,
+            (
+                ptr1,
+                len1,
+                wit_bindgen_wasmtime::rt::as_i32(token_id5),
+                wit_bindgen_wasmtime::rt::as_i64(amount5),
+                wit_bindgen_wasmtime::rt::as_i32(token_to),
+                wit_bindgen_wasmtime::rt::as_i32(max_hops),
+                wit_bindgen_wasmtime::rt::as_i64(integer6),
+                wit_bindgen_wasmtime::rt::as_i64(fraction6),
+                ptr7,
+                len7,
+                match post_bayfront_gardens {
+                    false => 0i32,
+                    true => 1i32,
+                },
+            ),
+        )?;
+        let load9 = memory.data_mut(&mut caller).load::<i32>(result8_0)?;
+        let load10 = memory.data_mut(&mut caller).load::<i32>(result8_0 + 8)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result8_0 + 16)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result8_0 + 24)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result8_0 + 32)?;
+        let load14 = memory.data_mut(&mut caller).load::<i64>(result8_0 + 40)?;
+        Ok(match load9 {
+            0 => Ok(BestPathResult {
+                best: SwapRoute {
+                    path: {
+                        let len16 = load11 as i32;
+                        let data16 = copy_slice(&mut caller, memory, load10, len16, 4)?;
+                        func_canonical_abi_free.call(&mut caller, (load10, len16 * 4, 4))?;
+                        data16
+                    },
+                    slop_swap_result: load12,
+                },
+                alternatives: {
+                    let len17 = load14 as i32;
+                    let base17 = load13 as i32;
+                    let mut result17 = Vec::with_capacity(len17 as usize);
+                    for i in 0..len17 {
+                        let base = base17 + i * 16;
+                        result17.push(SwapRoute {
+                            path: {
+                                let ptr18 = memory.data_mut(&mut caller).load::<i32>(base)?;
+                                let len18 = memory.data_mut(&mut caller).load::<i32>(base + 4)?;
+                                {
+                                    let len19 = len18;
+                                    let data19 = copy_slice(&mut caller, memory, ptr18, len19, 4)?;
+                                    func_canonical_abi_free
+                                        .call(&mut caller, (ptr18, len19 * 4, 4))?;
+                                    data19
+                                }
+                            },
+                            slop_swap_result: memory.data_mut(&mut caller).load::<i64>(base + 8)?,
+                        });
+                    }
+                    func_canonical_abi_free.call(&mut caller, (base17, len17 * 16, 8))?;
+                    result17
+                },
+            }),
+            1 => Err(match load10 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load11,
+                    reserve_b: load12,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load11,
+                        fraction: load12,
+                    },
+                    max_price: PoolPrice {
+                        integer: load13,
+                        fraction: load14,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load11,
+                    reserve_b: load12,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load11,
+                    min_output: load12,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load11 as i32 as u32,
+                    token_a: load12 as i32 as u32,
+                    token_b: load13 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load11,
+                    reserve_b: load12,
+                }),
+                10 => Error::InvalidPoolConfig(match load11 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load11 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn quote(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpair: PoolPair,
+        token_in: TokenAmount,
+        dex_fee: DexFee,
+        post_bayfront_gardens: bool,
+    ) -> Result<Result<i64, Error>, wasmtime::Trap> {
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 168))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        let TokenAmount {
+            token_id: token_id4,
+            amount: amount4,
+        } = token_in;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 112, wit_bindgen_wasmtime::rt::as_i32(token_id4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 120, wit_bindgen_wasmtime::rt::as_i64(amount4))?;
+        let DexFee {
+            in_pct_ab: in_pct_ab5,
+            out_pct_ab: out_pct_ab5,
+            in_pct_ba: in_pct_ba5,
+            out_pct_ba: out_pct_ba5,
+        } = dex_fee;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 128, wit_bindgen_wasmtime::rt::as_i64(in_pct_ab5))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 136, wit_bindgen_wasmtime::rt::as_i64(out_pct_ab5))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 144, wit_bindgen_wasmtime::rt::as_i64(in_pct_ba5))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 152, wit_bindgen_wasmtime::rt::as_i64(out_pct_ba5))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 160,
+            match post_bayfront_gardens {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        let (result6_0,) = self.quote.call(&mut caller, (ptr0,))?;
+        let load7 = memory.data_mut(&mut caller).load::<i32>(result6_0)?;
+        let load8 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 8)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 16)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 24)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 32)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 40)?;
+        Ok(match load7 {
+            0 => Ok(load8),
+            1 => Err(match load8 as i32 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                    max_price: PoolPrice {
+                        integer: load11,
+                        fraction: load12,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load9,
+                    min_output: load10,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load9 as i32 as u32,
+                    token_a: load10 as i32 as u32,
+                    token_b: load11 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                10 => Error::InvalidPoolConfig(match load9 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load9 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn add_liquidity(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpair: PoolPair,
+        amount_a: i64,
+        amount_b: i64,
+    ) -> Result<Result<LiquidityResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 128))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 112, wit_bindgen_wasmtime::rt::as_i64(amount_a))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 120, wit_bindgen_wasmtime::rt::as_i64(amount_b))?;
+        let (result4_0,) = self.add_liquidity.call(&mut caller, (ptr0,))?;
+        let load5 = memory.data_mut(&mut caller).load::<i32>(result4_0)?;
+        let load6 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 8)?;
+        let load7 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 16)?;
+        let load8 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 24)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 32)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 40)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 48)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 56)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 64)?;
+        let load14 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 72)?;
+        let load15 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 80)?;
+        let load16 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 88)?;
+        let load17 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 96)?;
+        let load18 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 104)?;
+        let load19 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 112)?;
+        let load20 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 120)?;
+        let load21 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 128)?;
+        let load22 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 136)?;
+        let load23 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 144)?;
+        let load24 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 152)?;
+        let load25 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 160)?;
+        let load26 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 168)?;
+        Ok(match load5 {
+            0 => Ok(LiquidityResult {
+                pool_pair: PoolPair {
+                    token_a: load6 as u32,
+                    token_b: load7 as i32 as u32,
+                    commission: load8 as i32 as u32,
+                    reserve_a: load9,
+                    reserve_b: load10,
+                    total_liquidity: load11,
+                    block_commission_a: load12,
+                    block_commission_b: load13,
+                    pool_type: match load14 {
+                        0 => PoolType::ConstantProduct,
+                        1 => PoolType::StableSwap,
+                        2 => PoolType::Weighted,
+                        _ => return Err(invalid_variant("PoolType")),
+                    },
+                    amplification: load15 as u32,
+                    weight_a: load16 as u32,
+                    weight_b: load17 as u32,
+                    price_ab_cumulative: CumulativePrice {
+                        high: load18 as u64,
+                        low: load19 as u64,
+                    },
+                    price_ba_cumulative: CumulativePrice {
+                        high: load20 as u64,
+                        low: load21 as u64,
+                    },
+                    price_height: load22 as u32,
+                    status: match load23 {
+                        0 => false,
+                        1 => true,
+                        _ => return Err(invalid_variant("bool")),
+                    },
+                },
+                amount_a: load24,
+                amount_b: load25,
+                liquidity: load26,
+            }),
+            1 => Err(match load6 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load7,
+                        fraction: load8,
+                    },
+                    max_price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load7,
+                    min_output: load8,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load7 as i32 as u32,
+                    token_a: load8 as i32 as u32,
+                    token_b: load9 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                10 => Error::InvalidPoolConfig(match load7 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load7 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn remove_liquidity(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpair: PoolPair,
+        liquidity: i64,
+    ) -> Result<Result<LiquidityResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 120))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 112, wit_bindgen_wasmtime::rt::as_i64(liquidity))?;
+        let (result4_0,) = self.remove_liquidity.call(&mut caller, (ptr0,))?;
+        let load5 = memory.data_mut(&mut caller).load::<i32>(result4_0)?;
+        let load6 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 8)?;
+        let load7 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 16)?;
+        let load8 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 24)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 32)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 40)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 48)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 56)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 64)?;
+        let load14 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 72)?;
+        let load15 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 80)?;
+        let load16 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 88)?;
+        let load17 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 96)?;
+        let load18 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 104)?;
+        let load19 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 112)?;
+        let load20 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 120)?;
+        let load21 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 128)?;
+        let load22 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 136)?;
+        let load23 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 144)?;
+        let load24 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 152)?;
+        let load25 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 160)?;
+        let load26 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 168)?;
+        Ok(match load5 {
+            0 => Ok(LiquidityResult {
+                pool_pair: PoolPair {
+                    token_a: load6 as u32,
+                    token_b: load7 as i32 as u32,
+                    commission: load8 as i32 as u32,
+                    reserve_a: load9,
+                    reserve_b: load10,
+                    total_liquidity: load11,
+                    block_commission_a: load12,
+                    block_commission_b: load13,
+                    pool_type: match load14 {
+                        0 => PoolType::ConstantProduct,
+                        1 => PoolType::StableSwap,
+                        2 => PoolType::Weighted,
+                        _ => return Err(invalid_variant("PoolType")),
+                    },
+                    amplification: load15 as u32,
+                    weight_a: load16 as u32,
+                    weight_b: load17 as u32,
+                    price_ab_cumulative: CumulativePrice {
+                        high: load18 as u64,
+                        low: load19 as u64,
+                    },
+                    price_ba_cumulative: CumulativePrice {
+                        high: load20 as u64,
+                        low: load21 as u64,
+                    },
+                    price_height: load22 as u32,
+                    status: match load23 {
+                        0 => false,
+                        1 => true,
+                        _ => return Err(invalid_variant("bool")),
+                    },
+                },
+                amount_a: load24,
+                amount_b: load25,
+                liquidity: load26,
+            }),
+            1 => Err(match load6 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load7,
+                        fraction: load8,
+                    },
+                    max_price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load7,
+                    min_output: load8,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load7 as i32 as u32,
+                    token_a: load8 as i32 as u32,
+                    token_b: load9 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                10 => Error::InvalidPoolConfig(match load7 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load7 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn create_pool_pair(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        config: PoolPairConfig,
+        owner_address: &str,
+    ) -> Result<Result<PoolPair, Error>, wasmtime::Trap> {
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        let PoolPairConfig {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            status: status1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+        } = config;
+        let vec2 = owner_address;
+        let len2 = vec2.len() as i32;
+        let ptr2 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 1, len2 * 1))?;
+        memory
+            .data_mut(&mut caller)
+            .store_many(ptr2, vec2.as_bytes())?;
+        let (result3_0,) = self.create_pool_pair.call(
+            &mut caller,
+            (
+                wit_bindgen_wasmtime::rt::as_i32(token_a1),
+                wit_bindgen_wasmtime::rt::as_i32(token_b1),
+                wit_bindgen_wasmtime::rt::as_i32(commission1),
+                match status1 {
+                    false => 0i32,
+                    true => 1i32,
+                },
+                pool_type1 as i32,
+                wit_bindgen_wasmtime::rt::as_i32(amplification1),
+                wit_bindgen_wasmtime::rt::as_i32(weight_a1),
+                wit_bindgen_wasmtime::rt::as_i32(weight_b1),
+                ptr2,
+                len2,
+            ),
+        )?;
+        let load4 = memory.data_mut(&mut caller).load::<i32>(result3_0)?;
+        let load5 = memory.data_mut(&mut caller).load::<i32>(result3_0 + 8)?;
+        let load6 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 16)?;
+        let load7 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 24)?;
+        let load8 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 32)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 40)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 48)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 56)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 64)?;
+        let load13 = memory.data_mut(&mut caller).load::<i32>(result3_0 + 72)?;
+        let load14 = memory.data_mut(&mut caller).load::<i32>(result3_0 + 80)?;
+        let load15 = memory.data_mut(&mut caller).load::<i32>(result3_0 + 88)?;
+        let load16 = memory.data_mut(&mut caller).load::<i32>(result3_0 + 96)?;
+        let load17 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 104)?;
+        let load18 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 112)?;
+        let load19 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 120)?;
+        let load20 = memory.data_mut(&mut caller).load::<i64>(result3_0 + 128)?;
+        let load21 = memory.data_mut(&mut caller).load::<i32>(result3_0 + 136)?;
+        let load22 = memory.data_mut(&mut caller).load::<i32>(result3_0 + 144)?;
+        Ok(match load4 {
+            0 => Ok(PoolPair {
+                token_a: load5 as u32,
+                token_b: load6 as i32 as u32,
+                commission: load7 as i32 as u32,
+                reserve_a: load8,
+                reserve_b: load9,
+                total_liquidity: load10,
+                block_commission_a: load11,
+                block_commission_b: load12,
+                pool_type: match load13 {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => return Err(invalid_variant("PoolType")),
+                },
+                amplification: load14 as u32,
+                weight_a: load15 as u32,
+                weight_b: load16 as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: load17 as u64,
+                    low: load18 as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: load19 as u64,
+                    low: load20 as u64,
+                },
+                price_height: load21 as u32,
+                status: match load22 {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(invalid_variant("bool")),
+                },
+            }),
+            1 => Err(match load5 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load6,
+                    reserve_b: load7,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load6,
+                        fraction: load7,
+                    },
+                    max_price: PoolPrice {
+                        integer: load8,
+                        fraction: load9,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load6,
+                    reserve_b: load7,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load6,
+                    min_output: load7,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load6 as i32 as u32,
+                    token_a: load7 as i32 as u32,
+                    token_b: load8 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load6,
+                    reserve_b: load7,
+                }),
+                10 => Error::InvalidPoolConfig(match load6 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load6 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn update_pool_pair(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpair: PoolPair,
+        status: Option<bool>,
+        commission: Option<DctId>,
+    ) -> Result<Result<PoolPair, Error>, wasmtime::Trap> {
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 128))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        match status {
+            Some(e) => {
+                memory.data_mut(&mut caller).store(ptr0 + 112, 1u8)?;
+                memory.data_mut(&mut caller).store(
+                    ptr0 + 113,
+                    match e {
+                        false => 0u8,
+                        true => 1u8,
+                    },
+                )?;
+            }
+            None => {
+                memory.data_mut(&mut caller).store(ptr0 + 112, 0u8)?;
+            }
+        };
+        match commission {
+            Some(e) => {
+                memory.data_mut(&mut caller).store(ptr0 + 116, 1u8)?;
+                memory
+                    .data_mut(&mut caller)
+                    .store(ptr0 + 120, wit_bindgen_wasmtime::rt::as_i32(e))?;
+            }
+            None => {
+                memory.data_mut(&mut caller).store(ptr0 + 116, 0u8)?;
+            }
+        };
+        let (result4_0,) = self.update_pool_pair.call(&mut caller, (ptr0,))?;
+        let load5 = memory.data_mut(&mut caller).load::<i32>(result4_0)?;
+        let load6 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 8)?;
+        let load7 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 16)?;
+        let load8 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 24)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 32)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 40)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 48)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 56)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 64)?;
+        let load14 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 72)?;
+        let load15 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 80)?;
+        let load16 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 88)?;
+        let load17 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 96)?;
+        let load18 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 104)?;
+        let load19 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 112)?;
+        let load20 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 120)?;
+        let load21 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 128)?;
+        let load22 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 136)?;
+        let load23 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 144)?;
+        Ok(match load5 {
+            0 => Ok(PoolPair {
+                token_a: load6 as u32,
+                token_b: load7 as i32 as u32,
+                commission: load8 as i32 as u32,
+                reserve_a: load9,
+                reserve_b: load10,
+                total_liquidity: load11,
+                block_commission_a: load12,
+                block_commission_b: load13,
+                pool_type: match load14 {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => return Err(invalid_variant("PoolType")),
+                },
+                amplification: load15 as u32,
+                weight_a: load16 as u32,
+                weight_b: load17 as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: load18 as u64,
+                    low: load19 as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: load20 as u64,
+                    low: load21 as u64,
+                },
+                price_height: load22 as u32,
+                status: match load23 {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(invalid_variant("bool")),
+                },
+            }),
+            1 => Err(match load6 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load7,
+                        fraction: load8,
+                    },
+                    max_price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load7,
+                    min_output: load8,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load7 as i32 as u32,
+                    token_a: load8 as i32 as u32,
+                    token_b: load9 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                10 => Error::InvalidPoolConfig(match load7 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load7 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn accumulate_price(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpair: PoolPair,
+        height: u32,
+    ) -> Result<Result<PoolPair, Error>, wasmtime::Trap> {
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 120))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 112, wit_bindgen_wasmtime::rt::as_i32(height))?;
+        let (result4_0,) = self.accumulate_price.call(&mut caller, (ptr0,))?;
+        let load5 = memory.data_mut(&mut caller).load::<i32>(result4_0)?;
+        let load6 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 8)?;
+        let load7 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 16)?;
+        let load8 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 24)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 32)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 40)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 48)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 56)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 64)?;
+        let load14 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 72)?;
+        let load15 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 80)?;
+        let load16 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 88)?;
+        let load17 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 96)?;
+        let load18 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 104)?;
+        let load19 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 112)?;
+        let load20 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 120)?;
+        let load21 = memory.data_mut(&mut caller).load::<i64>(result4_0 + 128)?;
+        let load22 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 136)?;
+        let load23 = memory.data_mut(&mut caller).load::<i32>(result4_0 + 144)?;
+        Ok(match load5 {
+            0 => Ok(PoolPair {
+                token_a: load6 as u32,
+                token_b: load7 as i32 as u32,
+                commission: load8 as i32 as u32,
+                reserve_a: load9,
+                reserve_b: load10,
+                total_liquidity: load11,
+                block_commission_a: load12,
+                block_commission_b: load13,
+                pool_type: match load14 {
+                    0 => PoolType::ConstantProduct,
+                    1 => PoolType::StableSwap,
+                    2 => PoolType::Weighted,
+                    _ => return Err(invalid_variant("PoolType")),
+                },
+                amplification: load15 as u32,
+                weight_a: load16 as u32,
+                weight_b: load17 as u32,
+                price_ab_cumulative: CumulativePrice {
+                    high: load18 as u64,
+                    low: load19 as u64,
+                },
+                price_ba_cumulative: CumulativePrice {
+                    high: load20 as u64,
+                    low: load21 as u64,
+                },
+                price_height: load22 as u32,
+                status: match load23 {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(invalid_variant("bool")),
+                },
+            }),
+            1 => Err(match load6 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load7,
+                        fraction: load8,
+                    },
+                    max_price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load7,
+                    min_output: load8,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load7 as i32 as u32,
+                    token_a: load8 as i32 as u32,
+                    token_b: load9 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load7,
+                    reserve_b: load8,
+                }),
+                10 => Error::InvalidPoolConfig(match load7 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load7 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn twap(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        start: PoolPair,
+        end: PoolPair,
+    ) -> Result<Result<TwapResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 224))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = start;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        let PoolPair {
+            token_a: token_a4,
+            token_b: token_b4,
+            commission: commission4,
+            reserve_a: reserve_a4,
+            reserve_b: reserve_b4,
+            total_liquidity: total_liquidity4,
+            block_commission_a: block_commission_a4,
+            block_commission_b: block_commission_b4,
+            pool_type: pool_type4,
+            amplification: amplification4,
+            weight_a: weight_a4,
+            weight_b: weight_b4,
+            price_ab_cumulative: price_ab_cumulative4,
+            price_ba_cumulative: price_ba_cumulative4,
+            price_height: price_height4,
+            status: status4,
+        } = end;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 112, wit_bindgen_wasmtime::rt::as_i32(token_a4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 116, wit_bindgen_wasmtime::rt::as_i32(token_b4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 120, wit_bindgen_wasmtime::rt::as_i32(commission4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 128, wit_bindgen_wasmtime::rt::as_i64(reserve_a4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 136, wit_bindgen_wasmtime::rt::as_i64(reserve_b4))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 144,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity4),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 152,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a4),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 160,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b4),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 168, (pool_type4 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 172, wit_bindgen_wasmtime::rt::as_i32(amplification4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 176, wit_bindgen_wasmtime::rt::as_i32(weight_a4))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 180, wit_bindgen_wasmtime::rt::as_i32(weight_b4))?;
+        let CumulativePrice {
+            high: high5,
+            low: low5,
+        } = price_ab_cumulative4;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 184, wit_bindgen_wasmtime::rt::as_i64(high5))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 192, wit_bindgen_wasmtime::rt::as_i64(low5))?;
+        let CumulativePrice {
+            high: high6,
+            low: low6,
+        } = price_ba_cumulative4;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 200, wit_bindgen_wasmtime::rt::as_i64(high6))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 208, wit_bindgen_wasmtime::rt::as_i64(low6))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 216, wit_bindgen_wasmtime::rt::as_i32(price_height4))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 220,
+            match status4 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        let (result7_0,) = self.twap.call(&mut caller, (ptr0,))?;
+        let load8 = memory.data_mut(&mut caller).load::<i32>(result7_0)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 8)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 16)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 24)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 32)?;
+        let load13 = memory.data_mut(&mut caller).load::<i64>(result7_0 + 40)?;
+        Ok(match load8 {
+            0 => Ok(TwapResult {
+                price_ab: PoolPrice {
+                    integer: load9,
+                    fraction: load10,
+                },
+                price_ba: PoolPrice {
+                    integer: load11,
+                    fraction: load12,
+                },
+            }),
+            1 => Err(match load9 as i32 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load10,
+                        fraction: load11,
+                    },
+                    max_price: PoolPrice {
+                        integer: load12,
+                        fraction: load13,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load10,
+                    min_output: load11,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load10 as i32 as u32,
+                    token_a: load11 as i32 as u32,
+                    token_b: load12 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load10,
+                    reserve_b: load11,
+                }),
+                10 => Error::InvalidPoolConfig(match load10 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load10 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn distribute(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpair: PoolPair,
+        rewards: &[RewardPool],
+        height: u32,
+    ) -> Result<Result<Vec<RewardPool>, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 128))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        let vec4 = rewards;
+        let len4 = vec4.len() as i32;
+        let ptr4 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len4 * 40))?;
+        memory.data_mut(&mut caller).store_many(ptr4, vec4)?;
+        memory.data_mut(&mut caller).store(ptr0 + 116, len4)?;
+        memory.data_mut(&mut caller).store(ptr0 + 112, ptr4)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 120, wit_bindgen_wasmtime::rt::as_i32(height))?;
+        let (result5_0,) = self.distribute.call(&mut caller, (ptr0,))?;
+        let load6 = memory.data_mut(&mut caller).load::<i32>(result5_0)?;
+        let load7 = memory.data_mut(&mut caller).load::<i32>(result5_0 + 8)?;
+        let load8 = memory.data_mut(&mut caller).load::<i64>(result5_0 + 16)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result5_0 + 24)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result5_0 + 32)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result5_0 + 40)?;
+        Ok(match load6 {
+            0 => Ok({
+                let len13 = load8 as i32;
+                let data13 = copy_slice(&mut caller, memory, load7, len13, 8)?;
+                func_canonical_abi_free.call(&mut caller, (load7, len13 * 40, 8))?;
+                data13
+            }),
+            1 => Err(match load7 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load8,
+                    reserve_b: load9,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load8,
+                        fraction: load9,
+                    },
+                    max_price: PoolPrice {
+                        integer: load10,
+                        fraction: load11,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load8,
+                    reserve_b: load9,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load8,
+                    min_output: load9,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load8 as i32 as u32,
+                    token_a: load9 as i32 as u32,
+                    token_b: load10 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load8,
+                    reserve_b: load9,
+                }),
+                10 => Error::InvalidPoolConfig(match load8 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load8 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn claim(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        poolpair: PoolPair,
+        rewards: &[RewardPool],
+        liquidity: i64,
+        paid: &[RewardPerShare],
+    ) -> Result<Result<ClaimResult, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        // The parameters flatten to more than 16 values, so they are passed through memory
+        let ptr0 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, 136))?;
+        let PoolPair {
+            token_a: token_a1,
+            token_b: token_b1,
+            commission: commission1,
+            reserve_a: reserve_a1,
+            reserve_b: reserve_b1,
+            total_liquidity: total_liquidity1,
+            block_commission_a: block_commission_a1,
+            block_commission_b: block_commission_b1,
+            pool_type: pool_type1,
+            amplification: amplification1,
+            weight_a: weight_a1,
+            weight_b: weight_b1,
+            price_ab_cumulative: price_ab_cumulative1,
+            price_ba_cumulative: price_ba_cumulative1,
+            price_height: price_height1,
+            status: status1,
+        } = poolpair;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0, wit_bindgen_wasmtime::rt::as_i32(token_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 4, wit_bindgen_wasmtime::rt::as_i32(token_b1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 8, wit_bindgen_wasmtime::rt::as_i32(commission1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 16, wit_bindgen_wasmtime::rt::as_i64(reserve_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_b1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 32,
+            wit_bindgen_wasmtime::rt::as_i64(total_liquidity1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 40,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_a1),
+        )?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 48,
+            wit_bindgen_wasmtime::rt::as_i64(block_commission_b1),
+        )?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 56, (pool_type1 as i32) as u8)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 60, wit_bindgen_wasmtime::rt::as_i32(amplification1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 64, wit_bindgen_wasmtime::rt::as_i32(weight_a1))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 68, wit_bindgen_wasmtime::rt::as_i32(weight_b1))?;
+        let CumulativePrice {
+            high: high2,
+            low: low2,
+        } = price_ab_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 72, wit_bindgen_wasmtime::rt::as_i64(high2))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 80, wit_bindgen_wasmtime::rt::as_i64(low2))?;
+        let CumulativePrice {
+            high: high3,
+            low: low3,
+        } = price_ba_cumulative1;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 88, wit_bindgen_wasmtime::rt::as_i64(high3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 96, wit_bindgen_wasmtime::rt::as_i64(low3))?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 104, wit_bindgen_wasmtime::rt::as_i32(price_height1))?;
+        memory.data_mut(&mut caller).store(
+            ptr0 + 108,
+            match status1 {
+                false => 0u8,
+                true => 1u8,
+            },
+        )?;
+        let vec4 = rewards;
+        let len4 = vec4.len() as i32;
+        let ptr4 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len4 * 40))?;
+        memory.data_mut(&mut caller).store_many(ptr4, vec4)?;
+        memory.data_mut(&mut caller).store(ptr0 + 116, len4)?;
+        memory.data_mut(&mut caller).store(ptr0 + 112, ptr4)?;
+        memory
+            .data_mut(&mut caller)
+            .store(ptr0 + 120, wit_bindgen_wasmtime::rt::as_i64(liquidity))?;
+        let vec5 = paid;
+        let len5 = vec5.len() as i32;
+        let ptr5 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len5 * 16))?;
+        memory.data_mut(&mut caller).store_many(ptr5, vec5)?;
+        memory.data_mut(&mut caller).store(ptr0 + 132, len5)?;
+        memory.data_mut(&mut caller).store(ptr0 + 128, ptr5)?;
+        let (result6_0,) = self.claim.call(&mut caller, (ptr0,))?;
+        let load7 = memory.data_mut(&mut caller).load::<i32>(result6_0)?;
+        let load8 = memory.data_mut(&mut caller).load::<i32>(result6_0 + 8)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 16)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 24)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 32)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 40)?;
+        Ok(match load7 {
+            0 => Ok(ClaimResult {
+                amounts: {
+                    let len14 = load9 as i32;
+                    let data14 = copy_slice(&mut caller, memory, load8, len14, 8)?;
+                    func_canonical_abi_free.call(&mut caller, (load8, len14 * 16, 8))?;
+                    data14
+                },
+                paid: {
+                    let len15 = load11 as i32;
+                    let data15 = copy_slice(&mut caller, memory, load10 as i32, len15, 8)?;
+                    func_canonical_abi_free.call(&mut caller, (load10 as i32, len15 * 16, 8))?;
+                    data15
+                },
+            }),
+            1 => Err(match load8 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                    max_price: PoolPrice {
+                        integer: load11,
+                        fraction: load12,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load9,
+                    min_output: load10,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load9 as i32 as u32,
+                    token_a: load10 as i32 as u32,
+                    token_b: load11 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                10 => Error::InvalidPoolConfig(match load9 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load9 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn serialize_pools(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        pools: &[PoolEntry],
+    ) -> Result<Result<Vec<u8>, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        let vec1 = pools;
+        let len1 = vec1.len() as i32;
+        let ptr1 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len1 * 120))?;
+        for (i, e) in vec1.iter().enumerate() {
+            let base = ptr1 + (i as i32) * 120;
+            let PoolEntry {
+                pool_id: pool_id2,
+                pool_pair: pool_pair2,
+            } = *e;
+            memory
+                .data_mut(&mut caller)
+                .store(base, wit_bindgen_wasmtime::rt::as_i32(pool_id2))?;
+            let PoolPair {
+                token_a: token_a3,
+                token_b: token_b3,
+                commission: commission3,
+                reserve_a: reserve_a3,
+                reserve_b: reserve_b3,
+                total_liquidity: total_liquidity3,
+                block_commission_a: block_commission_a3,
+                block_commission_b: block_commission_b3,
+                pool_type: pool_type3,
+                amplification: amplification3,
+                weight_a: weight_a3,
+                weight_b: weight_b3,
+                price_ab_cumulative: price_ab_cumulative3,
+                price_ba_cumulative: price_ba_cumulative3,
+                price_height: price_height3,
+                status: status3,
+            } = pool_pair2;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 8, wit_bindgen_wasmtime::rt::as_i32(token_a3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 12, wit_bindgen_wasmtime::rt::as_i32(token_b3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 16, wit_bindgen_wasmtime::rt::as_i32(commission3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_a3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 32, wit_bindgen_wasmtime::rt::as_i64(reserve_b3))?;
+            memory.data_mut(&mut caller).store(
+                base + 40,
+                wit_bindgen_wasmtime::rt::as_i64(total_liquidity3),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 48,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_a3),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 56,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_b3),
+            )?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 64, (pool_type3 as i32) as u8)?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 68, wit_bindgen_wasmtime::rt::as_i32(amplification3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 72, wit_bindgen_wasmtime::rt::as_i32(weight_a3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 76, wit_bindgen_wasmtime::rt::as_i32(weight_b3))?;
+            let CumulativePrice {
+                high: high4,
+                low: low4,
+            } = price_ab_cumulative3;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 80, wit_bindgen_wasmtime::rt::as_i64(high4))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 88, wit_bindgen_wasmtime::rt::as_i64(low4))?;
+            let CumulativePrice {
+                high: high5,
+                low: low5,
+            } = price_ba_cumulative3;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 96, wit_bindgen_wasmtime::rt::as_i64(high5))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 104, wit_bindgen_wasmtime::rt::as_i64(low5))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 112, wit_bindgen_wasmtime::rt::as_i32(price_height3))?;
+            memory.data_mut(&mut caller).store(
+                base + 116,
+                match status3 {
+                    false => 0u8,
+                    true => 1u8,
+                },
+            )?;
+        }
+        let (result6_0,) = self.serialize_pools.call(&mut caller, (ptr1, len1))?;
+        let load7 = memory.data_mut(&mut caller).load::<i32>(result6_0)?;
+        let load8 = memory.data_mut(&mut caller).load::<i32>(result6_0 + 8)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 16)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 24)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 32)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 40)?;
+        Ok(match load7 {
+            0 => Ok({
+                let len14 = load9 as i32;
+                let data14 = copy_slice(&mut caller, memory, load8, len14, 1)?;
+                func_canonical_abi_free.call(&mut caller, (load8, len14 * 1, 1))?;
+                data14
+            }),
+            1 => Err(match load8 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                    max_price: PoolPrice {
+                        integer: load11,
+                        fraction: load12,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load9,
+                    min_output: load10,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load9 as i32 as u32,
+                    token_a: load10 as i32 as u32,
+                    token_b: load11 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                10 => Error::InvalidPoolConfig(match load9 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load9 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn deserialize_pools(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        bytes: &[u8],
+    ) -> Result<Result<Vec<PoolEntry>, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        let vec1 = bytes;
+        let len1 = vec1.len() as i32;
+        let ptr1 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 1, len1 * 1))?;
+        memory.data_mut(&mut caller).store_many(ptr1, vec1)?;
+        let (result2_0,) = self.deserialize_pools.call(&mut caller, (ptr1, len1))?;
+        let load3 = memory.data_mut(&mut caller).load::<i32>(result2_0)?;
+        let load4 = memory.data_mut(&mut caller).load::<i32>(result2_0 + 8)?;
+        let load5 = memory.data_mut(&mut caller).load::<i64>(result2_0 + 16)?;
+        let load6 = memory.data_mut(&mut caller).load::<i64>(result2_0 + 24)?;
+        let load7 = memory.data_mut(&mut caller).load::<i64>(result2_0 + 32)?;
+        let load8 = memory.data_mut(&mut caller).load::<i64>(result2_0 + 40)?;
+        Ok(match load3 {
+            0 => Ok({
+                let len10 = load5 as i32;
+                let base10 = load4;
+                let mut result10 = Vec::with_capacity(len10 as usize);
+                for i in 0..len10 {
+                    let base = base10 + i * 120;
+                    result10.push(PoolEntry {
+                        pool_id: memory.data_mut(&mut caller).load::<i32>(base)? as u32,
+                        pool_pair: PoolPair {
+                            token_a: memory.data_mut(&mut caller).load::<i32>(base + 8)? as u32,
+                            token_b: memory.data_mut(&mut caller).load::<i32>(base + 12)? as u32,
+                            commission: memory.data_mut(&mut caller).load::<i32>(base + 16)? as u32,
+                            reserve_a: memory.data_mut(&mut caller).load::<i64>(base + 24)?,
+                            reserve_b: memory.data_mut(&mut caller).load::<i64>(base + 32)?,
+                            total_liquidity: memory.data_mut(&mut caller).load::<i64>(base + 40)?,
+                            block_commission_a: memory
+                                .data_mut(&mut caller)
+                                .load::<i64>(base + 48)?,
+                            block_commission_b: memory
+                                .data_mut(&mut caller)
+                                .load::<i64>(base + 56)?,
+                            pool_type: match i32::from(
+                                memory.data_mut(&mut caller).load::<u8>(base + 64)?,
+                            ) {
+                                0 => PoolType::ConstantProduct,
+                                1 => PoolType::StableSwap,
+                                2 => PoolType::Weighted,
+                                _ => return Err(invalid_variant("PoolType")),
+                            },
+                            amplification: memory.data_mut(&mut caller).load::<i32>(base + 68)?
+                                as u32,
+                            weight_a: memory.data_mut(&mut caller).load::<i32>(base + 72)? as u32,
+                            weight_b: memory.data_mut(&mut caller).load::<i32>(base + 76)? as u32,
+                            price_ab_cumulative: CumulativePrice {
+                                high: memory.data_mut(&mut caller).load::<i64>(base + 80)? as u64,
+                                low: memory.data_mut(&mut caller).load::<i64>(base + 88)? as u64,
+                            },
+                            price_ba_cumulative: CumulativePrice {
+                                high: memory.data_mut(&mut caller).load::<i64>(base + 96)? as u64,
+                                low: memory.data_mut(&mut caller).load::<i64>(base + 104)? as u64,
+                            },
+                            price_height: memory.data_mut(&mut caller).load::<i32>(base + 112)?
+                                as u32,
+                            status: match i32::from(
+                                memory.data_mut(&mut caller).load::<u8>(base + 116)?,
+                            ) {
+                                0 => false,
+                                1 => true,
+                                _ => return Err(invalid_variant("bool")),
+                            },
+                        },
+                    });
+                }
+                func_canonical_abi_free.call(&mut caller, (base10, len10 * 120, 8))?;
+                result10
+            }),
+            1 => Err(match load4 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load5,
+                    reserve_b: load6,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load5,
+                        fraction: load6,
+                    },
+                    max_price: PoolPrice {
+                        integer: load7,
+                        fraction: load8,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load5,
+                    reserve_b: load6,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load5,
+                    min_output: load6,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load5 as i32 as u32,
+                    token_a: load6 as i32 as u32,
+                    token_b: load7 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load5,
+                    reserve_b: load6,
+                }),
+                10 => Error::InvalidPoolConfig(match load5 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load5 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn pools_hash(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        pools: &[PoolEntry],
+    ) -> Result<Result<Vec<u8>, Error>, wasmtime::Trap> {
+        let func_canonical_abi_free = &self.canonical_abi_free;
+        let func_canonical_abi_realloc = &self.canonical_abi_realloc;
+        let memory = &self.memory;
+        let vec1 = pools;
+        let len1 = vec1.len() as i32;
+        let ptr1 = func_canonical_abi_realloc.call(&mut caller, (0, 0, 8, len1 * 120))?;
+        for (i, e) in vec1.iter().enumerate() {
+            let base = ptr1 + (i as i32) * 120;
+            let PoolEntry {
+                pool_id: pool_id2,
+                pool_pair: pool_pair2,
+            } = *e;
+            memory
+                .data_mut(&mut caller)
+                .store(base, wit_bindgen_wasmtime::rt::as_i32(pool_id2))?;
+            let PoolPair {
+                token_a: token_a3,
+                token_b: token_b3,
+                commission: commission3,
+                reserve_a: reserve_a3,
+                reserve_b: reserve_b3,
+                total_liquidity: total_liquidity3,
+                block_commission_a: block_commission_a3,
+                block_commission_b: block_commission_b3,
+                pool_type: pool_type3,
+                amplification: amplification3,
+                weight_a: weight_a3,
+                weight_b: weight_b3,
+                price_ab_cumulative: price_ab_cumulative3,
+                price_ba_cumulative: price_ba_cumulative3,
+                price_height: price_height3,
+                status: status3,
+            } = pool_pair2;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 8, wit_bindgen_wasmtime::rt::as_i32(token_a3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 12, wit_bindgen_wasmtime::rt::as_i32(token_b3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 16, wit_bindgen_wasmtime::rt::as_i32(commission3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 24, wit_bindgen_wasmtime::rt::as_i64(reserve_a3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 32, wit_bindgen_wasmtime::rt::as_i64(reserve_b3))?;
+            memory.data_mut(&mut caller).store(
+                base + 40,
+                wit_bindgen_wasmtime::rt::as_i64(total_liquidity3),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 48,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_a3),
+            )?;
+            memory.data_mut(&mut caller).store(
+                base + 56,
+                wit_bindgen_wasmtime::rt::as_i64(block_commission_b3),
+            )?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 64, (pool_type3 as i32) as u8)?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 68, wit_bindgen_wasmtime::rt::as_i32(amplification3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 72, wit_bindgen_wasmtime::rt::as_i32(weight_a3))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 76, wit_bindgen_wasmtime::rt::as_i32(weight_b3))?;
+            let CumulativePrice {
+                high: high4,
+                low: low4,
+            } = price_ab_cumulative3;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 80, wit_bindgen_wasmtime::rt::as_i64(high4))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 88, wit_bindgen_wasmtime::rt::as_i64(low4))?;
+            let CumulativePrice {
+                high: high5,
+                low: low5,
+            } = price_ba_cumulative3;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 96, wit_bindgen_wasmtime::rt::as_i64(high5))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 104, wit_bindgen_wasmtime::rt::as_i64(low5))?;
+            memory
+                .data_mut(&mut caller)
+                .store(base + 112, wit_bindgen_wasmtime::rt::as_i32(price_height3))?;
+            memory.data_mut(&mut caller).store(
+                base + 116,
+                match status3 {
+                    false => 0u8,
+                    true => 1u8,
+                },
+            )?;
+        }
+        let (result6_0,) = self.pools_hash.call(&mut caller, (ptr1, len1))?;
+        let load7 = memory.data_mut(&mut caller).load::<i32>(result6_0)?;
+        let load8 = memory.data_mut(&mut caller).load::<i32>(result6_0 + 8)?;
+        let load9 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 16)?;
+        let load10 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 24)?;
+        let load11 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 32)?;
+        let load12 = memory.data_mut(&mut caller).load::<i64>(result6_0 + 40)?;
+        Ok(match load7 {
+            0 => Ok({
+                let len14 = load9 as i32;
+                let data14 = copy_slice(&mut caller, memory, load8, len14, 1)?;
+                func_canonical_abi_free.call(&mut caller, (load8, len14 * 1, 1))?;
+                data14
+            }),
+            1 => Err(match load8 {
+                0 => Error::Runtime,
+                1 => Error::NotFound,
+                2 => Error::InvalidInput,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load9,
+                        fraction: load10,
+                    },
+                    max_price: PoolPrice {
+                        integer: load11,
+                        fraction: load12,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load9,
+                    min_output: load10,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load9 as i32 as u32,
+                    token_a: load10 as i32 as u32,
+                    token_b: load11 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load9,
+                    reserve_b: load10,
+                }),
+                10 => Error::InvalidPoolConfig(match load9 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load9 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
+                _ => return Err(invalid_variant("Error")),
+            }),
+            _ => return Err(invalid_variant("Result")),
+        })
+    }
+    pub fn dex_stabilization_fee(
+        &self,
+        mut caller: impl wasmtime::AsContextMut<Data = T>,
+        algo_dusd: i64,
+        dusd_supply: i64,
+        coefficient: i64,
+    ) -> Result<Result<i64, Error>, wasmtime::Trap> {
+        let memory = &self.memory;
+        let (result1_0,) = self.dex_stabilization_fee.call(
+            &mut caller,
+            (
+                wit_bindgen_wasmtime::rt::as_i64(algo_dusd),
+                wit_bindgen_wasmtime::rt::as_i64(dusd_supply),
+                wit_bindgen_wasmtime::rt::as_i64(coefficient),
+            ),
+        )?;
+        let load2 = memory.data_mut(&mut caller).load::<i32>(result1_0)?;
+        let load3 = memory.data_mut(&mut caller).load::<i64>(result1_0 + 8)?;
+        let load4 = memory.data_mut(&mut caller).load::<i64>(result1_0 + 16)?;
+        let load5 = memory.data_mut(&mut caller).load::<i64>(result1_0 + 24)?;
+        let load6 = memory.data_mut(&mut caller).load::<i64>(result1_0 + 32)?;
+        let load7 = memory.data_mut(&mut caller).load::<i64>(result1_0 + 40)?;
+        Ok(match load2 {
+            0 => Ok(load3),
+            1 => Err(match load3 as i32 {
                 0 => Error::Runtime,
                 1 => Error::NotFound,
                 2 => Error::InvalidInput,
-                3 => Error::LackOfLiquidity,
-                4 => Error::PriceHigherThanIndex,
-                5 => Error::PoolReserveOverflow,
+                3 => Error::LackOfLiquidity(Reserves {
+                    reserve_a: load4,
+                    reserve_b: load5,
+                }),
+                4 => Error::PriceHigherThanIndex(PriceLimit {
+                    price: PoolPrice {
+                        integer: load4,
+                        fraction: load5,
+                    },
+                    max_price: PoolPrice {
+                        integer: load6,
+                        fraction: load7,
+                    },
+                }),
+                5 => Error::PoolReserveOverflow(Reserves {
+                    reserve_a: load4,
+                    reserve_b: load5,
+                }),
+                6 => Error::OutputBelowMinimum(OutputLimit {
+                    amount: load4,
+                    min_output: load5,
+                }),
+                7 => Error::TokenMismatch(InputTokenMismatch {
+                    token_id: load4 as i32 as u32,
+                    token_a: load5 as i32 as u32,
+                    token_b: load6 as i32 as u32,
+                }),
+                8 => Error::ArithmeticOverflow,
+                9 => Error::InvariantViolation(Reserves {
+                    reserve_a: load4,
+                    reserve_b: load5,
+                }),
+                10 => Error::InvalidPoolConfig(match load4 as i32 {
+                    0 => PoolConfigError::SameToken,
+                    1 => PoolConfigError::CommissionOutOfRange,
+                    2 => PoolConfigError::InvalidOwnerAddress,
+                    3 => PoolConfigError::InvalidPoolType,
+                    _ => return Err(invalid_variant("PoolConfigError")),
+                }),
+                11 => Error::PoolNotFound(PoolIndex {
+                    index: load4 as i32 as u32,
+                }),
+                12 => Error::PoolDisabled,
                 _ => return Err(invalid_variant("Error")),
             }),
             _ => return Err(invalid_variant("Result")),
         })
     }
 }
+use wit_bindgen_wasmtime::rt::copy_slice;
 use wit_bindgen_wasmtime::rt::invalid_variant;
 use wit_bindgen_wasmtime::rt::RawMem;
@@ -0,0 +1,246 @@
+use crate::dex_bindings::{Error, PoolConfigError, PoolPrice};
+use std::cell::RefCell;
+use std::fmt;
+use std::os::raw::c_char;
+use std::str::Utf8Error;
+use wit_bindgen_wasmtime::anyhow;
+use wit_bindgen_wasmtime::wasmtime::Trap;
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = RefCell::new(String::new());
+}
+
+/// Outcome of an `ainrt_*` call. Failures reported by the dex module map one to one to the
+/// variants of its `error`, failures of the runtime itself start at 100.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok = 0,
+    DexRuntime = 1,
+    NotFound = 2,
+    InvalidInput = 3,
+    LackOfLiquidity = 4,
+    PriceHigherThanIndex = 5,
+    PoolReserveOverflow = 6,
+    OutputBelowMinimum = 7,
+    TokenMismatch = 8,
+    ArithmeticOverflow = 9,
+    InvariantViolation = 10,
+    InvalidPoolConfig = 11,
+    PoolNotFound = 12,
+    PoolDisabled = 13,
+    /// The module trapped
+    Trap = 100,
+    /// No version of the module is registered, or none is active at the requested height
+    NotRegistered = 101,
+    /// The call used up its fuel, see `ainrt_set_fuel_limit`
+    OutOfFuel = 102,
+    /// The module grew past the limits it was registered with
+    LimitExceeded = 103,
+    /// An argument was malformed, such as a string that is not UTF-8
+    InvalidArgument = 104,
+    /// Any other failure, such as a module file that cannot be read or compiled
+    Failed = 105,
+}
+
+impl From<&Error> for Status {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::Runtime => Status::DexRuntime,
+            Error::NotFound => Status::NotFound,
+            Error::InvalidInput => Status::InvalidInput,
+            Error::LackOfLiquidity(_) => Status::LackOfLiquidity,
+            Error::PriceHigherThanIndex(_) => Status::PriceHigherThanIndex,
+            Error::PoolReserveOverflow(_) => Status::PoolReserveOverflow,
+            Error::OutputBelowMinimum(_) => Status::OutputBelowMinimum,
+            Error::TokenMismatch(_) => Status::TokenMismatch,
+            Error::ArithmeticOverflow => Status::ArithmeticOverflow,
+            Error::InvariantViolation(_) => Status::InvariantViolation,
+            Error::InvalidPoolConfig(_) => Status::InvalidPoolConfig,
+            Error::PoolNotFound(_) => Status::PoolNotFound,
+            Error::PoolDisabled => Status::PoolDisabled,
+        }
+    }
+}
+
+impl From<&anyhow::Error> for Status {
+    fn from(error: &anyhow::Error) -> Self {
+        if let Some(error) = error.downcast_ref::<Error>() {
+            error.into()
+        } else if error.is::<OutOfFuel>() {
+            Status::OutOfFuel
+        } else if error.is::<LimitExceeded>() {
+            Status::LimitExceeded
+        } else if error.is::<Trap>() {
+            Status::Trap
+        } else if error.is::<NotRegistered>() {
+            Status::NotRegistered
+        } else if error.is::<InvalidArgument>() || error.is::<Utf8Error>() {
+            Status::InvalidArgument
+        } else {
+            Status::Failed
+        }
+    }
+}
+
+/// Maps the outcome of an `ainrt_*` call to its status, recording the reason it failed as the
+/// calling thread's last error
+pub(crate) fn status(result: anyhow::Result<()>) -> Status {
+    match result {
+        Ok(()) => Status::Ok,
+        Err(e) => {
+            set_last_error(e.to_string());
+            (&e).into()
+        }
+    }
+}
+
+pub(crate) fn set_last_error(message: String) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+pub(crate) fn last_error() -> String {
+    LAST_ERROR.with(|last| last.borrow().clone())
+}
+
+/// Formats a pool price the way the node does, with 8 decimal places
+struct Price(PoolPrice);
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:08}", self.0.integer, self.0.fraction)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Runtime => write!(f, "Runtime error in dex module"),
+            Error::NotFound => write!(f, "No swap path found"),
+            Error::InvalidInput => write!(f, "Invalid input to dex module"),
+            Error::LackOfLiquidity(r) => write!(
+                f,
+                "Lack of liquidity (reserve A: {}, reserve B: {})",
+                r.reserve_a, r.reserve_b
+            ),
+            Error::PriceHigherThanIndex(p) => write!(
+                f,
+                "Price is higher than indicated (price: {}, max price: {})",
+                Price(p.price),
+                Price(p.max_price)
+            ),
+            Error::PoolReserveOverflow(r) => write!(
+                f,
+                "Swapping will lead to pool's reserve overflow (reserve A: {}, reserve B: {})",
+                r.reserve_a, r.reserve_b
+            ),
+            Error::OutputBelowMinimum(o) => write!(
+                f,
+                "Swap output {} is below the minimum output {}",
+                o.amount, o.min_output
+            ),
+            Error::TokenMismatch(t) => write!(
+                f,
+                "Error, input token ID ({}) doesn't match pool tokens ({},{})",
+                t.token_id, t.token_a, t.token_b
+            ),
+            Error::ArithmeticOverflow => write!(f, "Arithmetic overflow in swap calculation"),
+            Error::InvariantViolation(r) => write!(
+                f,
+                "Swap result violates pool invariants (reserve A: {}, reserve B: {})",
+                r.reserve_a, r.reserve_b
+            ),
+            Error::InvalidPoolConfig(e) => match e {
+                PoolConfigError::SameToken => write!(f, "Pool tokens IDs are the same"),
+                PoolConfigError::CommissionOutOfRange => write!(f, "Pool commission > 100%"),
+                PoolConfigError::InvalidOwnerAddress => write!(f, "Pool owner address is invalid"),
+                PoolConfigError::InvalidPoolType => {
+                    write!(f, "Pool parameters do not match its pool type")
+                }
+            },
+            Error::PoolNotFound(p) => write!(f, "No pool pair at index {}", p.index),
+            Error::PoolDisabled => write!(f, "Pool trading is turned off!"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A module call that used up its whole fuel budget before returning
+#[derive(Debug)]
+pub struct OutOfFuel {
+    pub limit: u64,
+}
+
+impl fmt::Display for OutOfFuel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Out of fuel (limit: {})", self.limit)
+    }
+}
+
+impl std::error::Error for OutOfFuel {}
+
+/// A module that tried to grow a memory or table past the limits it was registered with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitExceeded {
+    MemorySize { limit: usize, desired: usize },
+    TableElements { limit: u32, desired: u32 },
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::MemorySize { limit, desired } => write!(
+                f,
+                "Module memory limit exceeded (limit: {} bytes, requested: {} bytes)",
+                limit, desired
+            ),
+            LimitExceeded::TableElements { limit, desired } => write!(
+                f,
+                "Module table limit exceeded (limit: {} elements, requested: {} elements)",
+                limit, desired
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// No version of a module is registered, or none is active at the requested height
+#[derive(Debug)]
+pub struct NotRegistered(pub String);
+
+impl fmt::Display for NotRegistered {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NotRegistered {}
+
+/// An argument the runtime refused, such as a module whose hash does not match
+#[derive(Debug)]
+pub struct InvalidArgument(pub String);
+
+impl fmt::Display for InvalidArgument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidArgument {}
+
+/// Copies `message` into the caller provided buffer as a NUL terminated string, truncating it to
+/// fit. Does nothing if the buffer is null or empty.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `buf_len` bytes
+pub(crate) unsafe fn write_error_message(message: &str, buf: *mut c_char, buf_len: usize) {
+    if buf.is_null() || buf_len == 0 {
+        return;
+    }
+    let len = message.len().min(buf_len - 1);
+    std::ptr::copy_nonoverlapping(message.as_ptr() as *const c_char, buf, len);
+    *buf.add(len) = 0;
+}
@@ -1,123 +1,1227 @@
+//! Runs the wasm modules of the node, the dex module first among them. The node calls it
+//! through the C++ bridge in `bridge`, its canonical API. The `ainrt_*` functions are a C ABI
+//! over the same calls for other embedders. Every one of them has a bridge counterpart but
+//! `ainrt_last_error_message`, as the bridge throws its errors.
+
+mod bridge;
+mod cache;
 mod dex_bindings;
+mod error;
+mod limits;
+mod registry;
+mod wasi;
 
-use crate::dex_bindings::{Dex, PoolPair, PoolPrice, SwapResult, TokenAmount};
-use dashmap::DashMap;
-use lazy_static::lazy_static;
+use crate::dex_bindings::{
+    BestPathResult, ClaimResult, CompositeSwapResult, DctId, Dex, DexFee, LiquidityResult,
+    PoolEntry, PoolPair, PoolPairConfig, PoolPrice, RewardPerShare, RewardPool, SwapBatchResult,
+    SwapEvent, SwapOperation, SwapOutput, SwapResult, SwapStep, SwapTraceResult, TokenAmount,
+    TwapResult,
+};
+use crate::error::{
+    last_error, set_last_error, status, write_error_message, NotRegistered, Status,
+};
+use crate::limits::ModuleLimits;
+use crate::registry::MODULE_ID_MAX_LEN;
+use crate::wasi::WasiMode;
 use std::ffi::CStr;
 use std::os::raw::c_char;
 use std::path::Path;
-use std::sync::Arc;
-use wasmtime_wasi::{WasiCtx, WasiCtxBuilder};
-use wit_bindgen_wasmtime::anyhow::{anyhow, Result};
-use wit_bindgen_wasmtime::wasmtime::{Engine, Instance, Linker, Module, Store};
+use wit_bindgen_wasmtime::anyhow::{bail, Result};
 
 const DEX_MODULE_ID: &str = "dex";
 
-lazy_static! {
-    static ref MODULEMAP: Arc<DashMap<&'static str, Instance>> = Arc::new(DashMap::new());
-    static ref STOREMAP: Arc<DashMap<&'static str, Store<WasiCtx>>> = Arc::new(DashMap::new());
+/// A registered module, as listed by `ainrt_list_modules`
+#[repr(C)]
+pub struct ModuleInfo {
+    /// NUL terminated module id
+    pub id: [c_char; MODULE_ID_MAX_LEN + 1],
+    pub version: u32,
+    pub activation_height: u32,
+}
+
+/// Registers the dex module at `dex_module_file_path` as `version`, active from
+/// `activation_height` on, in consensus WASI mode. See `ainrt_register_module`.
+///
+/// # Safety
+///
+/// `dex_module_file_path` must be a NUL terminated string. `limits` may be null to use the
+/// default limits.
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_register_dex_module(
+    dex_module_file_path: *const c_char,
+    version: u32,
+    activation_height: u32,
+    limits: *const ModuleLimits,
+) -> Status {
+    status((|| {
+        let dex_module_file_path = CStr::from_ptr(dex_module_file_path).to_str()?;
+        let limits = limits.as_ref().copied().unwrap_or_default();
+        register_dex_module(dex_module_file_path, version, activation_height, limits)
+    })())
+}
+
+fn register_dex_module<P: AsRef<Path>>(
+    path: P,
+    version: u32,
+    activation_height: u32,
+    limits: ModuleLimits,
+) -> Result<()> {
+    let module = registry::compile(path, limits, WasiMode::Consensus)?;
+    // Fails early if the module does not export the dex interface. The instance gives its pool
+    // slot back before `insert` needs one.
+    {
+        let mut instance = module.instantiate()?;
+        Dex::new(&mut instance.store, &instance.instance)?;
+    }
+    registry::insert(DEX_MODULE_ID, version, activation_height, module)
+}
+
+/// Loads the wasm module at `module_file_path` and registers it under `module_id` and `version`.
+/// Calls at block heights from `activation_height` on run this version, until a higher version
+/// activates. A version that is already registered, or a higher version that would activate
+/// before a lower one, fails with `Status::InvalidArgument`. The module fails to load, or any
+/// call that grows it past `limits` fails, with `Status::LimitExceeded`. Calls that would need
+/// more than `limits.instances` concurrent instances of it wait for one to be done. In
+/// `WasiMode::Consensus` the module fails to register if it imports any non-deterministic WASI
+/// function.
+///
+/// # Safety
+///
+/// `module_id` and `module_file_path` must be NUL terminated strings. `limits` may be null to
+/// use the default limits.
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_register_module(
+    module_id: *const c_char,
+    version: u32,
+    activation_height: u32,
+    limits: *const ModuleLimits,
+    wasi_mode: WasiMode,
+    module_file_path: *const c_char,
+) -> Status {
+    status((|| {
+        let module_id = CStr::from_ptr(module_id).to_str()?;
+        let module_file_path = CStr::from_ptr(module_file_path).to_str()?;
+        let limits = limits.as_ref().copied().unwrap_or_default();
+        let module = registry::compile(module_file_path, limits, wasi_mode)?;
+        registry::insert(module_id, version, activation_height, module)
+    })())
+}
+
+/// Registers the wasm module in `bytes` like `ainrt_register_module`, after checking that its
+/// SHA-256 is `expected_sha256`. On a hash mismatch it fails with `Status::InvalidArgument` and
+/// nothing is compiled or registered.
+///
+/// # Safety
+///
+/// `module_id` must be a NUL terminated string and `bytes` must point to `bytes_len` bytes.
+/// `limits` may be null to use the default limits.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn ainrt_register_module_bytes(
+    module_id: *const c_char,
+    version: u32,
+    activation_height: u32,
+    limits: *const ModuleLimits,
+    wasi_mode: WasiMode,
+    bytes: *const u8,
+    bytes_len: usize,
+    expected_sha256: &[u8; 32],
+) -> Status {
+    status((|| {
+        let module_id = CStr::from_ptr(module_id).to_str()?;
+        let bytes = std::slice::from_raw_parts(bytes, bytes_len);
+        let limits = limits.as_ref().copied().unwrap_or_default();
+        register_module_bytes(
+            module_id,
+            version,
+            activation_height,
+            limits,
+            wasi_mode,
+            bytes,
+            expected_sha256,
+        )
+    })())
+}
+
+fn register_module_bytes(
+    id: &str,
+    version: u32,
+    activation_height: u32,
+    limits: ModuleLimits,
+    wasi_mode: WasiMode,
+    bytes: &[u8],
+    expected_sha256: &[u8; 32],
+) -> Result<()> {
+    let module = registry::compile_bytes(bytes, expected_sha256, limits, wasi_mode)?;
+    registry::insert(id, version, activation_height, module)
 }
 
+/// Unregisters the module registered under `module_id` and `version`. Fails with
+/// `Status::NotRegistered` if no such module is registered.
+///
+/// # Safety
+///
+/// `module_id` must be a NUL terminated string
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_unregister_module(module_id: *const c_char, version: u32) -> Status {
+    status((|| {
+        let module_id = CStr::from_ptr(module_id).to_str()?;
+        registry::remove(module_id, version)
+    })())
+}
+
+/// Writes every registered module with its activation height, ordered by id and then version,
+/// into `modules_out` and their count into `modules_out_len`.
+///
+/// # Safety
+///
+/// `modules_out` must have room for `modules_out_cap` entries. Only the first `modules_out_cap`
+/// are written when there are more.
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_list_modules(
+    modules_out: *mut ModuleInfo,
+    modules_out_cap: usize,
+    modules_out_len: *mut usize,
+) {
+    let modules = registry::list();
+    for (i, (module, activation_height)) in modules.iter().take(modules_out_cap).enumerate() {
+        let mut info = ModuleInfo {
+            id: [0; MODULE_ID_MAX_LEN + 1],
+            version: module.version,
+            activation_height: *activation_height,
+        };
+        for (c, b) in info.id.iter_mut().zip(module.id.bytes()) {
+            *c = b as c_char;
+        }
+        modules_out.add(i).write(info);
+    }
+    *modules_out_len = modules.len();
+}
+
+/// Returns 1 if a module is registered under `module_id` and `version`, and 0 otherwise.
+///
 /// # Safety
 ///
-/// Should validate file path
+/// `module_id` must be a NUL terminated string
 #[no_mangle]
-pub unsafe extern "C" fn ainrt_register_dex_module(dex_module_file_path: *const c_char) -> i32 {
-    let dex_module_file_path = CStr::from_ptr(dex_module_file_path).to_str().unwrap();
-    match register_dex_module(dex_module_file_path) {
-        Ok(_) => 1,
-        Err(_) => 0,
+pub unsafe extern "C" fn ainrt_lookup_module(module_id: *const c_char, version: u32) -> i32 {
+    match CStr::from_ptr(module_id).to_str() {
+        Ok(module_id) if registry::contains(module_id, version) => 1,
+        _ => 0,
     }
 }
 
-fn register_dex_module<P: AsRef<Path>>(path: P) -> Result<()> {
-    let engine = Engine::default();
-    let mut linker = Linker::new(&engine);
-    wasmtime_wasi::add_to_linker(&mut linker, |s| s)?;
-    let dex_module = Module::from_file(&engine, path)?;
-    let wasi = WasiCtxBuilder::new()
-        .inherit_stdio()
-        .inherit_args()?
-        .build();
+/// Writes the highest registered version of `module_id` into `version_out`. Fails with
+/// `Status::NotRegistered` if no version of the module is registered.
+///
+/// # Safety
+///
+/// `module_id` must be a NUL terminated string
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_latest_module_version(
+    module_id: *const c_char,
+    version_out: *mut u32,
+) -> Status {
+    status((|| {
+        let module_id = CStr::from_ptr(module_id).to_str()?;
+        *version_out = registry::latest_version(module_id).ok_or_else(|| {
+            NotRegistered(format!("module {} has no registered version", module_id))
+        })?;
+        Ok(())
+    })())
+}
+
+/// Writes the version of `module_id` that calls at block `height` run into `version_out`.
+/// Fails with `Status::NotRegistered` if no version of the module is active at that height.
+///
+/// # Safety
+///
+/// `module_id` must be a NUL terminated string
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_active_module_version(
+    module_id: *const c_char,
+    height: u32,
+    version_out: *mut u32,
+) -> Status {
+    status((|| {
+        let module_id = CStr::from_ptr(module_id).to_str()?;
+        *version_out = registry::active_version(module_id, height).ok_or_else(|| {
+            NotRegistered(format!(
+                "module {} has no version active at height {}",
+                module_id, height
+            ))
+        })?;
+        Ok(())
+    })())
+}
+
+/// Caches compiled modules in `dir`, so registering a module compiled before skips compiling
+/// it again. Cache files are authenticated with the `key_len` bytes at `key`, which must be at
+/// least 32 bytes of secret, random data kept by the node, and a file that fails to
+/// authenticate is compiled again and replaced. A null `dir` disables the cache.
+///
+/// # Safety
+///
+/// `dir` must be null or a NUL terminated string. `key` must be null or point to `key_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_set_module_cache_dir(
+    dir: *const c_char,
+    key: *const u8,
+    key_len: usize,
+) -> Status {
+    status((|| {
+        let cache = match dir.as_ref() {
+            Some(_) => {
+                let dir = CStr::from_ptr(dir).to_str()?;
+                let key = match key.is_null() {
+                    true => &[],
+                    false => std::slice::from_raw_parts(key, key_len),
+                };
+                Some(cache::ModuleCache::new(dir.into(), key)?)
+            }
+            None => None,
+        };
+        cache::set(cache);
+        Ok(())
+    })())
+}
+
+/// Sets the fuel every module call starts with. A call that uses it all up fails with
+/// `Status::OutOfFuel`. Nodes must agree on the limit used while validating blocks.
+#[no_mangle]
+pub extern "C" fn ainrt_set_fuel_limit(fuel_limit: u64) {
+    registry::set_fuel_limit(fuel_limit);
+}
+
+/// Fuel consumed by the last module call made on this thread, whether it succeeded or not
+#[no_mangle]
+pub extern "C" fn ainrt_last_fuel_consumed() -> u64 {
+    registry::last_fuel_consumed()
+}
 
-    let mut store = Store::new(&engine, wasi);
-    let (_, instance) = Dex::instantiate(&mut store, &dex_module, &mut linker)?;
-    MODULEMAP.insert(DEX_MODULE_ID, instance);
-    STOREMAP.insert(DEX_MODULE_ID, store);
-    Ok(())
+/// Copies the reason the last failed call on this thread failed into `buf` as a NUL terminated
+/// string, truncated to fit, and returns its full length in bytes. Calls that succeed leave it
+/// unchanged.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of `buf_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_last_error_message(buf: *mut c_char, buf_len: usize) -> usize {
+    let message = last_error();
+    write_error_message(&message, buf, buf_len);
+    message.len()
 }
 
+/// Swaps `token_in` against `poolpair` and writes the amount bought into `amount_out`. The spot
+/// price of the pool before the trade must be within `max_price`, and when `min_output` is given
+/// so must the realized price of the trade, fees and slippage included.
+///
+/// Every `ainrt_call_dex_*` function runs the version of the dex module active at the `height`
+/// it is given, see `ainrt_register_module`. They return `Status::Ok` on success, and otherwise
+/// the status of the failure, whose reason `ainrt_last_error_message` returns. Outputs are only
+/// written on success.
+///
 /// # Safety
 ///
-/// TODO
+/// `min_output` may be null when no minimum output is required. The dex fees taken are written
+/// to `dex_fee_in` and `dex_fee_out`, either of which may be null.
 #[no_mangle]
+#[allow(clippy::too_many_arguments)]
 pub unsafe extern "C" fn ainrt_call_dex_swap(
+    height: u32,
     poolpair: *mut PoolPair,
     token_in: &TokenAmount,
     max_price: &PoolPrice,
+    min_output: *const i64,
+    dex_fee: &DexFee,
     post_bayfront_gardens: bool,
-) -> i64 {
-    let pp = *poolpair;
-    match dex_swap(pp, *token_in, *max_price, post_bayfront_gardens) {
-        Ok(res) => {
+    amount_out: *mut i64,
+    dex_fee_in: *mut i64,
+    dex_fee_out: *mut i64,
+) -> Status {
+    let min_output = min_output.as_ref().copied();
+    status(
+        dex_swap(
+            height,
+            *poolpair,
+            *token_in,
+            *max_price,
+            min_output,
+            *dex_fee,
+            post_bayfront_gardens,
+        )
+        .map(|res| {
             *poolpair = res.pool_pair;
-            res.slop_swap_result
-        }
-        Err(_) => 0,
-    }
+            *amount_out = res.slop_swap_result;
+            if let Some(dex_fee_in) = dex_fee_in.as_mut() {
+                *dex_fee_in = res.dex_fee_in;
+            }
+            if let Some(dex_fee_out) = dex_fee_out.as_mut() {
+                *dex_fee_out = res.dex_fee_out;
+            }
+        }),
+    )
 }
 
 fn dex_swap(
+    height: u32,
     poolpair: PoolPair,
     token_in: TokenAmount,
     max_price: PoolPrice,
+    min_output: Option<i64>,
+    dex_fee: DexFee,
     post_bayfront_gardens: bool,
 ) -> Result<SwapResult> {
-    let dex = Dex::new(
-        STOREMAP
-            .get_mut(DEX_MODULE_ID)
-            .ok_or_else(|| anyhow!("module not found"))?
-            .value_mut(),
-        MODULEMAP
-            .get(DEX_MODULE_ID)
-            .ok_or_else(|| anyhow!("module not found"))?
-            .value(),
-    )?;
-    let result = dex.swap(
-        &mut STOREMAP.get_mut("dex").unwrap().value_mut(),
-        poolpair,
-        token_in,
-        max_price,
-        post_bayfront_gardens,
-    )?;
-    result.map_err(|e| anyhow!(format!("{:?}", e)))
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.swap(
+            store,
+            poolpair,
+            token_in,
+            max_price,
+            min_output,
+            dex_fee,
+            post_bayfront_gardens,
+        )?)
+    })?;
+    Ok(result?)
+}
+
+/// Runs the same swap as `ainrt_call_dex_swap` without updating `poolpair`, writing the amount
+/// bought into `amount_out`, each step of the trade into `steps_out` and each fee taken into
+/// `events_out`.
+///
+/// # Safety
+///
+/// `steps_out` must have room for `steps_out_cap` steps. Their total count is written to
+/// `steps_out_len`, and only the first `steps_out_cap` are written when there are more.
+/// `events_out` must have room for 3 events, and their count is written to `events_out_len`.
+/// `min_output` follows the same rules as in `ainrt_call_dex_swap`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn ainrt_call_dex_swap_trace(
+    height: u32,
+    poolpair: &PoolPair,
+    token_in: &TokenAmount,
+    max_price: &PoolPrice,
+    min_output: *const i64,
+    dex_fee: &DexFee,
+    post_bayfront_gardens: bool,
+    amount_out: *mut i64,
+    steps_out: *mut SwapStep,
+    steps_out_cap: usize,
+    steps_out_len: *mut usize,
+    events_out: *mut SwapEvent,
+    events_out_len: *mut usize,
+) -> Status {
+    let min_output = min_output.as_ref().copied();
+    status(
+        dex_swap_trace(
+            height,
+            *poolpair,
+            *token_in,
+            *max_price,
+            min_output,
+            *dex_fee,
+            post_bayfront_gardens,
+        )
+        .map(|res| {
+            *amount_out = res.swap_result.slop_swap_result;
+            let steps = &res.steps[..res.steps.len().min(steps_out_cap)];
+            std::ptr::copy_nonoverlapping(steps.as_ptr(), steps_out, steps.len());
+            *steps_out_len = res.steps.len();
+            std::ptr::copy_nonoverlapping(res.events.as_ptr(), events_out, res.events.len());
+            *events_out_len = res.events.len();
+        }),
+    )
+}
+
+fn dex_swap_trace(
+    height: u32,
+    poolpair: PoolPair,
+    token_in: TokenAmount,
+    max_price: PoolPrice,
+    min_output: Option<i64>,
+    dex_fee: DexFee,
+    post_bayfront_gardens: bool,
+) -> Result<SwapTraceResult> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.swap_trace(
+            store,
+            poolpair,
+            token_in,
+            max_price,
+            min_output,
+            dex_fee,
+            post_bayfront_gardens,
+        )?)
+    })?;
+    Ok(result?)
+}
+
+/// Applies `operations` one after another to `poolpairs`, each against the pools as left by
+/// the ones before it. A failed operation leaves the pools unchanged and the rest still run, so
+/// the batch returns `Status::Ok` unless it could not be run at all. The status of each
+/// operation is written to `statuses`, and the reason the first failed operation failed,
+/// prefixed with its index, becomes the last error message.
+///
+/// # Safety
+///
+/// `poolpairs` must point to `poolpairs_len` pool pairs, which are updated in place.
+/// `operations` must point to `operations_len` operations, and `outputs` and `statuses` must
+/// have room for as many entries. The output of operation `i` is written to `outputs[i]` when
+/// `statuses[i]` is `Status::Ok`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn ainrt_call_dex_swap_batch(
+    height: u32,
+    poolpairs: *mut PoolPair,
+    poolpairs_len: usize,
+    operations: *const SwapOperation,
+    operations_len: usize,
+    post_bayfront_gardens: bool,
+    outputs: *mut SwapOutput,
+    statuses: *mut Status,
+) -> Status {
+    let pps = std::slice::from_raw_parts_mut(poolpairs, poolpairs_len);
+    let ops = std::slice::from_raw_parts(operations, operations_len);
+    let outputs = std::slice::from_raw_parts_mut(outputs, operations_len);
+    let statuses = std::slice::from_raw_parts_mut(statuses, operations_len);
+    status(
+        dex_swap_batch(height, pps, ops, post_bayfront_gardens).map(|res| {
+            for (pp, updated) in pps.iter_mut().zip(res.pool_pairs) {
+                *pp = updated;
+            }
+            let mut failed = false;
+            for (i, result) in res.results.into_iter().enumerate() {
+                match result {
+                    Ok(output) => {
+                        outputs[i] = output;
+                        statuses[i] = Status::Ok;
+                    }
+                    Err(e) => {
+                        if !failed {
+                            set_last_error(format!("operation {}: {}", i, e));
+                            failed = true;
+                        }
+                        statuses[i] = (&e).into();
+                    }
+                }
+            }
+        }),
+    )
+}
+
+fn dex_swap_batch(
+    height: u32,
+    poolpairs: &[PoolPair],
+    operations: &[SwapOperation],
+    post_bayfront_gardens: bool,
+) -> Result<SwapBatchResult> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.swap_batch(store, poolpairs, operations, post_bayfront_gardens)?)
+    })?;
+    Ok(result?)
+}
+
+/// Swaps `token_in` through `poolpairs` in order and writes the amount bought into
+/// `amount_out`. Each hop takes the dex fee at the same index of `dex_fees` and the commission
+/// of its pool, like `ainrt_call_dex_swap`. `max_price` bounds the spot price of each pool
+/// before its hop, and the price paid for the whole route, input per unit of final output.
+///
+/// # Safety
+///
+/// `poolpairs` and `dex_fees` must both point to `poolpairs_len` elements. The pool pairs are
+/// updated in place on success.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn ainrt_call_dex_composite_swap(
+    height: u32,
+    poolpairs: *mut PoolPair,
+    dex_fees: *const DexFee,
+    poolpairs_len: usize,
+    token_in: &TokenAmount,
+    max_price: &PoolPrice,
+    post_bayfront_gardens: bool,
+    amount_out: *mut i64,
+) -> Status {
+    let pps = std::slice::from_raw_parts_mut(poolpairs, poolpairs_len);
+    let dex_fees = std::slice::from_raw_parts(dex_fees, poolpairs_len);
+    status(
+        dex_composite_swap(
+            height,
+            pps,
+            *token_in,
+            *max_price,
+            dex_fees,
+            post_bayfront_gardens,
+        )
+        .map(|res| {
+            for (pp, updated) in pps.iter_mut().zip(res.pool_pairs) {
+                *pp = updated;
+            }
+            *amount_out = res.slop_swap_result;
+        }),
+    )
+}
+
+fn dex_composite_swap(
+    height: u32,
+    poolpairs: &[PoolPair],
+    token_in: TokenAmount,
+    max_price: PoolPrice,
+    dex_fees: &[DexFee],
+    post_bayfront_gardens: bool,
+) -> Result<CompositeSwapResult> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.composite_swap(
+            store,
+            poolpairs,
+            token_in,
+            max_price,
+            dex_fees,
+            post_bayfront_gardens,
+        )?)
+    })?;
+    Ok(result?)
+}
+
+/// Finds the route from `token_in` to `token_to` with the highest output, writing that output
+/// into `amount_out`, the indices of its pool pairs into `path_out` and their count into
+/// `path_out_len`. Routes are compared after the dex fee of each pool, given at the same index
+/// of `dex_fees`, and its commission.
+///
+/// # Safety
+///
+/// `poolpairs` and `dex_fees` must both point to `poolpairs_len` elements and `path_out` must
+/// have room for `max_hops` indices.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn ainrt_call_dex_best_path(
+    height: u32,
+    poolpairs: *const PoolPair,
+    dex_fees: *const DexFee,
+    poolpairs_len: usize,
+    token_in: &TokenAmount,
+    token_to: DctId,
+    max_hops: u32,
+    max_price: &PoolPrice,
+    post_bayfront_gardens: bool,
+    amount_out: *mut i64,
+    path_out: *mut u32,
+    path_out_len: *mut usize,
+) -> Status {
+    let pps = std::slice::from_raw_parts(poolpairs, poolpairs_len);
+    let dex_fees = std::slice::from_raw_parts(dex_fees, poolpairs_len);
+    status(
+        dex_best_path(
+            height,
+            pps,
+            *token_in,
+            token_to,
+            max_hops,
+            *max_price,
+            dex_fees,
+            post_bayfront_gardens,
+        )
+        .map(|res| {
+            let path = res.best.path;
+            std::ptr::copy_nonoverlapping(path.as_ptr(), path_out, path.len());
+            *path_out_len = path.len();
+            *amount_out = res.best.slop_swap_result;
+        }),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dex_best_path(
+    height: u32,
+    poolpairs: &[PoolPair],
+    token_in: TokenAmount,
+    token_to: DctId,
+    max_hops: u32,
+    max_price: PoolPrice,
+    dex_fees: &[DexFee],
+    post_bayfront_gardens: bool,
+) -> Result<BestPathResult> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.best_path(
+            store,
+            poolpairs,
+            token_in,
+            token_to,
+            max_hops,
+            max_price,
+            dex_fees,
+            post_bayfront_gardens,
+        )?)
+    })?;
+    Ok(result?)
+}
+
+/// Writes the amount `token_in` would swap to against `poolpair` into `amount_out`, after the
+/// same dex fees and commission as `ainrt_call_dex_swap`, without a price limit and without
+/// updating the pool.
+///
+/// # Safety
+///
+/// `amount_out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_quote(
+    height: u32,
+    poolpair: &PoolPair,
+    token_in: &TokenAmount,
+    dex_fee: &DexFee,
+    post_bayfront_gardens: bool,
+    amount_out: *mut i64,
+) -> Status {
+    status(
+        dex_quote(
+            height,
+            *poolpair,
+            *token_in,
+            *dex_fee,
+            post_bayfront_gardens,
+        )
+        .map(|res| *amount_out = res),
+    )
+}
+
+fn dex_quote(
+    height: u32,
+    poolpair: PoolPair,
+    token_in: TokenAmount,
+    dex_fee: DexFee,
+    post_bayfront_gardens: bool,
+) -> Result<i64> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.quote(store, poolpair, token_in, dex_fee, post_bayfront_gardens)?)
+    })?;
+    Ok(result?)
+}
+
+/// Adds `amount_a` and `amount_b` to the reserves of `poolpair` and writes the liquidity minted
+/// for them into `liquidity_out`.
+///
+/// # Safety
+///
+/// `poolpair` is updated in place on success
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_add_liquidity(
+    height: u32,
+    poolpair: *mut PoolPair,
+    amount_a: i64,
+    amount_b: i64,
+    liquidity_out: *mut i64,
+) -> Status {
+    status(
+        dex_add_liquidity(height, *poolpair, amount_a, amount_b).map(|res| {
+            *poolpair = res.pool_pair;
+            *liquidity_out = res.liquidity;
+        }),
+    )
+}
+
+fn dex_add_liquidity(
+    height: u32,
+    poolpair: PoolPair,
+    amount_a: i64,
+    amount_b: i64,
+) -> Result<LiquidityResult> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.add_liquidity(store, poolpair, amount_a, amount_b)?)
+    })?;
+    Ok(result?)
+}
+
+/// Burns `liquidity` from `poolpair`, writing the amounts of both tokens paid out for it into
+/// `amount_a` and `amount_b`.
+///
+/// # Safety
+///
+/// `poolpair` is updated in place on success
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_remove_liquidity(
+    height: u32,
+    poolpair: *mut PoolPair,
+    liquidity: i64,
+    amount_a: *mut i64,
+    amount_b: *mut i64,
+) -> Status {
+    status(
+        dex_remove_liquidity(height, *poolpair, liquidity).map(|res| {
+            *poolpair = res.pool_pair;
+            *amount_a = res.amount_a;
+            *amount_b = res.amount_b;
+        }),
+    )
+}
+
+fn dex_remove_liquidity(
+    height: u32,
+    poolpair: PoolPair,
+    liquidity: i64,
+) -> Result<LiquidityResult> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.remove_liquidity(store, poolpair, liquidity)?)
+    })?;
+    Ok(result?)
+}
+
+/// Validates `config` and `owner_address` and writes a new, empty pool pair built from them into
+/// `poolpair_out`.
+///
+/// # Safety
+///
+/// `owner_address` must be a NUL terminated string
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_create_pool_pair(
+    height: u32,
+    config: &PoolPairConfig,
+    owner_address: *const c_char,
+    poolpair_out: *mut PoolPair,
+) -> Status {
+    status((|| {
+        let owner_address = CStr::from_ptr(owner_address).to_str()?;
+        *poolpair_out = dex_create_pool_pair(height, *config, owner_address)?;
+        Ok(())
+    })())
+}
+
+fn dex_create_pool_pair(
+    height: u32,
+    config: PoolPairConfig,
+    owner_address: &str,
+) -> Result<PoolPair> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.create_pool_pair(store, config, owner_address)?)
+    })?;
+    Ok(result?)
+}
+
+/// Changes the status and commission of `poolpair`, validating the commission the same way as
+/// `ainrt_call_dex_create_pool_pair`. Swaps through a pool with its status off fail with
+/// `Status::PoolDisabled`.
+///
+/// # Safety
+///
+/// `pool_status` and `commission` may each be null to keep the current value. `poolpair` is
+/// updated in place on success.
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_update_pool_pair(
+    height: u32,
+    poolpair: *mut PoolPair,
+    pool_status: *const bool,
+    commission: *const u32,
+) -> Status {
+    status((|| {
+        *poolpair = dex_update_pool_pair(
+            height,
+            *poolpair,
+            pool_status.as_ref().copied(),
+            commission.as_ref().copied(),
+        )?;
+        Ok(())
+    })())
+}
+
+fn dex_update_pool_pair(
+    height: u32,
+    poolpair: PoolPair,
+    status: Option<bool>,
+    commission: Option<u32>,
+) -> Result<PoolPair> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.update_pool_pair(store, poolpair, status, commission)?)
+    })?;
+    Ok(result?)
+}
+
+/// Adds the spot prices of `poolpair`, held since its last update, to its cumulative prices and
+/// moves it to `height`.
+///
+/// # Safety
+///
+/// `poolpair` is updated in place on success
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_accumulate_price(
+    height: u32,
+    poolpair: *mut PoolPair,
+) -> Status {
+    status(dex_accumulate_price(height, *poolpair).map(|res| *poolpair = res))
+}
+
+fn dex_accumulate_price(height: u32, poolpair: PoolPair) -> Result<PoolPair> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.accumulate_price(store, poolpair, height)?)
+    })?;
+    Ok(result?)
+}
+
+/// Writes the time-weighted average prices of a pool between the `start` and `end` snapshots
+/// into `price_ab` and `price_ba`.
+///
+/// # Safety
+///
+/// `price_ab` and `price_ba` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_twap(
+    height: u32,
+    start: &PoolPair,
+    end: &PoolPair,
+    price_ab: *mut PoolPrice,
+    price_ba: *mut PoolPrice,
+) -> Status {
+    status(dex_twap(height, *start, *end).map(|res| {
+        *price_ab = res.price_ab;
+        *price_ba = res.price_ba;
+    }))
+}
+
+fn dex_twap(height: u32, start: PoolPair, end: PoolPair) -> Result<TwapResult> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.twap(store, start, end)?)
+    })?;
+    Ok(result?)
+}
+
+/// Accrues the rewards of `poolpair` up to `height` into the reward per share of each of its
+/// reward pools.
+///
+/// # Safety
+///
+/// `rewards` must point to `rewards_len` reward pools, which are updated in place on success
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_distribute(
+    height: u32,
+    poolpair: &PoolPair,
+    rewards: *mut RewardPool,
+    rewards_len: usize,
+) -> Status {
+    let rewards = std::slice::from_raw_parts_mut(rewards, rewards_len);
+    status(dex_distribute(height, *poolpair, rewards).map(|res| {
+        for (reward, updated) in rewards.iter_mut().zip(res) {
+            *reward = updated;
+        }
+    }))
+}
+
+fn dex_distribute(
+    height: u32,
+    poolpair: PoolPair,
+    rewards: &[RewardPool],
+) -> Result<Vec<RewardPool>> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.distribute(store, poolpair, rewards, height)?)
+    })?;
+    Ok(result?)
+}
+
+/// Writes the rewards owed to an owner of `liquidity` in `poolpair` into `amounts`, one entry per
+/// reward pool.
+///
+/// # Safety
+///
+/// `rewards`, `paid` and `amounts` must all point to `rewards_len` entries. `paid` holds the
+/// reward per share of each reward pool at the owner's last claim, zero for reward pools added
+/// since, and is moved up to the current reward per share on success.
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_claim(
+    height: u32,
+    poolpair: &PoolPair,
+    rewards: *const RewardPool,
+    rewards_len: usize,
+    liquidity: i64,
+    paid: *mut RewardPerShare,
+    amounts: *mut TokenAmount,
+) -> Status {
+    let rewards = std::slice::from_raw_parts(rewards, rewards_len);
+    let paid = std::slice::from_raw_parts_mut(paid, rewards_len);
+    status(
+        dex_claim(height, *poolpair, rewards, liquidity, paid).map(|res| {
+            paid.copy_from_slice(&res.paid);
+            std::ptr::copy_nonoverlapping(res.amounts.as_ptr(), amounts, res.amounts.len());
+        }),
+    )
+}
+
+fn dex_claim(
+    height: u32,
+    poolpair: PoolPair,
+    rewards: &[RewardPool],
+    liquidity: i64,
+    paid: &[RewardPerShare],
+) -> Result<ClaimResult> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.claim(store, poolpair, rewards, liquidity, paid)?)
+    })?;
+    Ok(result?)
+}
+
+/// Writes the canonical encoding of `pools` into `bytes_out` and its length into
+/// `bytes_out_len`. Fails with `Status::InvalidInput` when two pools share an id.
+///
+/// # Safety
+///
+/// `pools` must point to `pools_len` pool entries, and `bytes_out` must have room for
+/// `bytes_out_cap` bytes. Only the first `bytes_out_cap` bytes are written when the encoding is
+/// longer, so callers can pass 0 to learn its length.
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_serialize_pools(
+    height: u32,
+    pools: *const PoolEntry,
+    pools_len: usize,
+    bytes_out: *mut u8,
+    bytes_out_cap: usize,
+    bytes_out_len: *mut usize,
+) -> Status {
+    let pools = std::slice::from_raw_parts(pools, pools_len);
+    status(dex_serialize_pools(height, pools).map(|res| {
+        let bytes = &res[..res.len().min(bytes_out_cap)];
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), bytes_out, bytes.len());
+        *bytes_out_len = res.len();
+    }))
+}
+
+fn dex_serialize_pools(height: u32, pools: &[PoolEntry]) -> Result<Vec<u8>> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.serialize_pools(store, pools)?)
+    })?;
+    Ok(result?)
+}
+
+/// Decodes pools written by `ainrt_call_dex_serialize_pools` into `pools_out`, in ascending id
+/// order, and writes their count into `pools_out_len`.
+///
+/// # Safety
+///
+/// `bytes` must point to `bytes_len` bytes, and `pools_out` must have room for `pools_out_cap`
+/// entries. Only the first `pools_out_cap` are written when there are more.
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_deserialize_pools(
+    height: u32,
+    bytes: *const u8,
+    bytes_len: usize,
+    pools_out: *mut PoolEntry,
+    pools_out_cap: usize,
+    pools_out_len: *mut usize,
+) -> Status {
+    let bytes = std::slice::from_raw_parts(bytes, bytes_len);
+    status(dex_deserialize_pools(height, bytes).map(|res| {
+        let pools = &res[..res.len().min(pools_out_cap)];
+        std::ptr::copy_nonoverlapping(pools.as_ptr(), pools_out, pools.len());
+        *pools_out_len = res.len();
+    }))
+}
+
+fn dex_deserialize_pools(height: u32, bytes: &[u8]) -> Result<Vec<PoolEntry>> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.deserialize_pools(store, bytes)?)
+    })?;
+    Ok(result?)
+}
+
+/// Writes the SHA-256 of the canonical encoding of `pools` into `hash_out`, to be persisted or
+/// compared as the state root of the pool set.
+///
+/// # Safety
+///
+/// `pools` must point to `pools_len` pool entries
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_pools_hash(
+    height: u32,
+    pools: *const PoolEntry,
+    pools_len: usize,
+    hash_out: &mut [u8; 32],
+) -> Status {
+    let pools = std::slice::from_raw_parts(pools, pools_len);
+    status((|| {
+        let hash = dex_pools_hash(height, pools)?;
+        if hash.len() != hash_out.len() {
+            bail!("unexpected hash length");
+        }
+        hash_out.copy_from_slice(&hash);
+        Ok(())
+    })())
+}
+
+fn dex_pools_hash(height: u32, pools: &[PoolEntry]) -> Result<Vec<u8>> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.pools_hash(store, pools)?)
+    })?;
+    Ok(result?)
+}
+
+/// Writes the DFIP-2206-D dex stabilization fee for the given algorithmic DUSD, DUSD supply and
+/// fee coefficient into `fee_out`. The module computes it with the same `ain-coin` function as
+/// `ain-core`'s `calc_dex_fee`, and the result is meant to be passed back as a `DexFee`
+/// percentage of DUSD swaps.
+///
+/// # Safety
+///
+/// `fee_out` must be valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn ainrt_call_dex_stabilization_fee(
+    height: u32,
+    algo_dusd: i64,
+    dusd_supply: i64,
+    coefficient: i64,
+    fee_out: *mut i64,
+) -> Status {
+    status(
+        dex_stabilization_fee(height, algo_dusd, dusd_supply, coefficient)
+            .map(|fee| *fee_out = fee),
+    )
+}
+
+fn dex_stabilization_fee(
+    height: u32,
+    algo_dusd: i64,
+    dusd_supply: i64,
+    coefficient: i64,
+) -> Result<i64> {
+    let mut dex_module = registry::active(DEX_MODULE_ID, height)?;
+    let result = dex_module.metered(|store, instance| {
+        let dex = Dex::new(&mut *store, instance)?;
+        Ok(dex.dex_stabilization_fee(store, algo_dusd, dusd_supply, coefficient)?)
+    })?;
+    Ok(result?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{dex_swap, register_dex_module, PoolPair, PoolPrice, TokenAmount};
+    use crate::bridge::runtime;
+    use crate::dex_bindings::{CumulativePrice, Dex, DexFee, PoolPairConfig, PoolType};
+    use crate::error::{last_error, status, LimitExceeded, OutOfFuel, Status};
+    use crate::limits::ModuleLimits;
+    use crate::wasi::WasiMode;
+    use crate::{
+        cache, dex_add_liquidity, dex_composite_swap, dex_create_pool_pair, dex_quote,
+        dex_stabilization_fee, dex_swap, dex_swap_batch, dex_update_pool_pair, register_dex_module,
+        register_module_bytes, registry, PoolPair, PoolPrice, SwapOperation, TokenAmount,
+    };
+    use sha2::{Digest, Sha256};
     use std::path::PathBuf;
+    use std::sync::Once;
     use std::time::Instant;
+    use wit_bindgen_wasmtime::anyhow;
     const COIN: i64 = 100_000_000;
+    const HEIGHT: u32 = 1;
+    const NO_FEE: DexFee = DexFee {
+        in_pct_ab: 0,
+        out_pct_ab: 0,
+        in_pct_ba: 0,
+        out_pct_ba: 0,
+    };
+    const BURN_ADDRESS: &str = "8defichainBurnAddressXXXXXXXdRQkSm";
+
+    /// The dex module built by `make build-wasm-pkg`
+    fn dex_wasm_path() -> PathBuf {
+        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        d.pop();
+        d.join("pkg/modules-wasm/dex.wasm")
+    }
+
+    /// Registers the dex module as version 1 from height 0. Tests run in parallel against the
+    /// process wide registry, and a version can only be registered once.
+    fn register_dex() {
+        static REGISTER: Once = Once::new();
+        REGISTER.call_once(|| {
+            register_dex_module(dex_wasm_path(), 1, 0, ModuleLimits::default()).unwrap()
+        });
+    }
+
+    /// A constant product pool of tokens 1 and 2 without commission
+    fn pool_pair_config() -> PoolPairConfig {
+        PoolPairConfig {
+            token_a: 1,
+            token_b: 2,
+            commission: 0,
+            status: true,
+            pool_type: PoolType::ConstantProduct,
+            amplification: 0,
+            weight_a: 0,
+            weight_b: 0,
+        }
+    }
+
+    fn create_pool_pair(config: PoolPairConfig) -> PoolPair {
+        dex_create_pool_pair(HEIGHT, config, BURN_ADDRESS).unwrap()
+    }
+
+    /// A constant product pool without commission or price history
+    fn pool_pair(token_a: u32, token_b: u32, reserve_a: i64, reserve_b: i64) -> PoolPair {
+        PoolPair {
+            token_a,
+            token_b,
+            commission: 0,
+            reserve_a,
+            reserve_b,
+            total_liquidity: 1000 * COIN,
+            block_commission_a: 0,
+            block_commission_b: 0,
+            pool_type: PoolType::ConstantProduct,
+            amplification: 0,
+            weight_a: 0,
+            weight_b: 0,
+            price_ab_cumulative: CumulativePrice { high: 0, low: 0 },
+            price_ba_cumulative: CumulativePrice { high: 0, low: 0 },
+            price_height: 0,
+            status: true,
+        }
+    }
+
     #[test]
     fn text_swap() {
         let gold = 1;
         let silver = 2;
 
-        let mut pool_pair = PoolPair {
+        register_dex();
+
+        let pool_pair = create_pool_pair(PoolPairConfig {
             token_a: gold,
             token_b: silver,
             commission: (0.1_f64 * COIN as f64) as u32,
-            reserve_a: 200 * COIN,
-            reserve_b: 1000 * COIN,
-            total_liquidity: 1000 * COIN,
-            block_commission_a: 0,
-            block_commission_b: 0,
-        };
-        let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        d.pop();
-        let path = d.join("pkg/modules-wasm/dex.wasm");
-        register_dex_module(path).unwrap();
+            ..pool_pair_config()
+        });
+        let mut pool_pair = dex_add_liquidity(HEIGHT, pool_pair, 200 * COIN, 1000 * COIN)
+            .unwrap()
+            .pool_pair;
 
         let token_in = TokenAmount {
             token_id: silver,
@@ -129,7 +1233,8 @@ mod tests {
         };
         let instant = Instant::now();
         for i in 1..21 {
-            let result = dex_swap(pool_pair, token_in, max_price, true).unwrap();
+            let result =
+                dex_swap(HEIGHT, pool_pair, token_in, max_price, None, NO_FEE, true).unwrap();
             println!(
                 "Result {}: {:#?}",
                 i,
@@ -139,4 +1244,505 @@ mod tests {
         }
         println!("21 swaps took {}ms", instant.elapsed().as_millis())
     }
+
+    #[test]
+    fn test_composite_swap() {
+        let gold = 1;
+        let silver = 2;
+        let bronze = 3;
+
+        let gold_silver = pool_pair(gold, silver, 200 * COIN, 1000 * COIN);
+        let silver_bronze = pool_pair(silver, bronze, 1000 * COIN, 5000 * COIN);
+        register_dex();
+
+        let token_in = TokenAmount {
+            token_id: gold,
+            amount: COIN,
+        };
+        let max_price = PoolPrice {
+            integer: 100 * COIN,
+            fraction: 0,
+        };
+        let result = dex_composite_swap(
+            HEIGHT,
+            &[gold_silver, silver_bronze],
+            token_in,
+            max_price,
+            &[NO_FEE, NO_FEE],
+            true,
+        )
+        .unwrap();
+
+        let first = dex_swap(HEIGHT, gold_silver, token_in, max_price, None, NO_FEE, true).unwrap();
+        let second_in = TokenAmount {
+            token_id: silver,
+            amount: first.slop_swap_result,
+        };
+        let second = dex_swap(
+            HEIGHT,
+            silver_bronze,
+            second_in,
+            max_price,
+            None,
+            NO_FEE,
+            true,
+        )
+        .unwrap();
+        assert_eq!(result.slop_swap_result, second.slop_swap_result);
+        assert_eq!(result.pool_pairs[0].reserve_b, first.pool_pair.reserve_b);
+        assert_eq!(result.pool_pairs[1].reserve_b, second.pool_pair.reserve_b);
+    }
+
+    #[test]
+    fn test_module_registry() {
+        let path = dex_wasm_path();
+        let id = "registry-test";
+        for (version, activation_height) in [(2, 100), (1, 10)] {
+            let module =
+                registry::compile(&path, ModuleLimits::default(), WasiMode::Consensus).unwrap();
+            registry::insert(id, version, activation_height, module).unwrap();
+        }
+
+        assert!(registry::contains(id, 1) && registry::contains(id, 2));
+        assert_eq!(registry::latest_version(id), Some(2));
+        let versions: Vec<_> = registry::list()
+            .into_iter()
+            .filter(|(key, _)| key.id == id)
+            .map(|(key, activation_height)| (key.version, activation_height))
+            .collect();
+        assert_eq!(versions, [(1, 10), (2, 100)]);
+
+        assert_eq!(registry::active_version(id, 9), None);
+        assert_eq!(registry::active_version(id, 10), Some(1));
+        assert_eq!(registry::active_version(id, 99), Some(1));
+        assert_eq!(registry::active_version(id, 100), Some(2));
+        assert!(registry::active(id, 9).is_err());
+        assert_eq!(registry::active(id, 150).unwrap().key().version, 2);
+
+        // A higher version may not activate before a lower one, and a registered version is
+        // never replaced
+        let module =
+            registry::compile(&path, ModuleLimits::default(), WasiMode::Consensus).unwrap();
+        assert!(registry::insert(id, 3, 50, module).is_err());
+        let module =
+            registry::compile(&path, ModuleLimits::default(), WasiMode::Consensus).unwrap();
+        let err = registry::insert(id, 1, 10, module).unwrap_err();
+        assert_eq!(Status::from(&err), Status::InvalidArgument);
+        assert!(err.to_string().contains("already registered"));
+
+        registry::remove(id, 2).unwrap();
+        assert_eq!(registry::latest_version(id), Some(1));
+        assert_eq!(registry::active_version(id, 100), Some(1));
+        assert!(registry::remove(id, 2).is_err());
+        assert!(registry::insert(
+            "",
+            1,
+            0,
+            registry::compile(&path, ModuleLimits::default(), WasiMode::Consensus).unwrap()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_concurrent_calls() {
+        register_dex();
+
+        let pool_pair = create_pool_pair(pool_pair_config());
+        let pool_pair = dex_add_liquidity(HEIGHT, pool_pair, 200 * COIN, 1000 * COIN)
+            .unwrap()
+            .pool_pair;
+        let token_in = TokenAmount {
+            token_id: 2,
+            amount: 10 * COIN,
+        };
+
+        // Each thread gets an instance of its own, so they all run at once and agree
+        let quotes: Vec<_> = (0..8)
+            .map(|_| {
+                std::thread::spawn(move || {
+                    (0..20)
+                        .map(|_| dex_quote(HEIGHT, pool_pair, token_in, NO_FEE, true).unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect();
+        assert!(quotes.iter().all(|quote| *quote == quotes[0]));
+    }
+
+    #[test]
+    fn test_fuel_metering() {
+        let module = registry::compile(
+            dex_wasm_path(),
+            ModuleLimits::default(),
+            WasiMode::Consensus,
+        )
+        .unwrap();
+        let config = pool_pair_config();
+        // Every call runs on a new instance, like the ones `registry::active` hands out
+        let create_pool_pair = |limit| {
+            module
+                .instantiate()
+                .unwrap()
+                .metered_with_limit(limit, |store, instance| {
+                    let dex = Dex::new(&mut *store, instance)?;
+                    Ok(dex.create_pool_pair(store, config, BURN_ADDRESS)?)
+                })
+        };
+
+        create_pool_pair(registry::DEFAULT_FUEL_LIMIT)
+            .unwrap()
+            .unwrap();
+        let consumed = registry::last_fuel_consumed();
+        assert!(consumed > 0);
+
+        // The same call costs the same fuel every time
+        create_pool_pair(registry::DEFAULT_FUEL_LIMIT)
+            .unwrap()
+            .unwrap();
+        assert_eq!(registry::last_fuel_consumed(), consumed);
+
+        let err = create_pool_pair(consumed / 2).unwrap_err();
+        assert!(err.is::<OutOfFuel>());
+        assert_eq!(Status::from(&err), Status::OutOfFuel);
+    }
+
+    #[test]
+    fn test_fresh_instance_per_call() {
+        // Counts its calls in both a global and linear memory
+        let counter = br#"(module
+            (memory (export "memory") 1)
+            (global $calls (mut i32) (i32.const 0))
+            (func (export "bump") (result i32)
+                (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+                (i32.store (i32.const 0) (i32.add (i32.load (i32.const 0)) (i32.const 1)))
+                (i32.add (global.get $calls) (i32.load (i32.const 0)))))"#;
+        let hash: [u8; 32] = Sha256::digest(counter).into();
+        let limits = ModuleLimits::default();
+        let module = registry::compile_bytes(counter, &hash, limits, WasiMode::Consensus).unwrap();
+        let id = "fresh-instance-test";
+        registry::insert(id, 1, 0, module).unwrap();
+
+        // Nothing a call leaves behind is seen by the next one, so each costs the same fuel
+        let mut fuel = vec![];
+        for _ in 0..3 {
+            let mut module = registry::active(id, HEIGHT).unwrap();
+            let calls = module
+                .metered(|store, instance| {
+                    let bump = instance.get_typed_func::<(), i32, _>(&mut *store, "bump")?;
+                    Ok(bump.call(&mut *store, ())?)
+                })
+                .unwrap();
+            assert_eq!(calls, 2);
+            fuel.push(registry::last_fuel_consumed());
+        }
+        assert!(fuel.iter().all(|consumed| *consumed == fuel[0]));
+    }
+
+    #[test]
+    fn test_module_limits() {
+        let path = dex_wasm_path();
+
+        // The module's initial memory alone is past a zero byte limit
+        let limits = ModuleLimits {
+            memory_size: 0,
+            ..ModuleLimits::default()
+        };
+        let module = registry::compile(&path, limits, WasiMode::Consensus).unwrap();
+        let err = module.instantiate().err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<LimitExceeded>(),
+            Some(LimitExceeded::MemorySize { limit: 0, .. })
+        ));
+
+        let limits = ModuleLimits {
+            table_elements: 0,
+            ..ModuleLimits::default()
+        };
+        let module = registry::compile(&path, limits, WasiMode::Consensus).unwrap();
+        let err = module.instantiate().err().unwrap();
+        assert!(matches!(
+            err.downcast_ref::<LimitExceeded>(),
+            Some(LimitExceeded::TableElements { limit: 0, .. })
+        ));
+
+        let module = registry::compile(&path, ModuleLimits::default(), WasiMode::Consensus);
+        assert!(module.unwrap().instantiate().is_ok());
+    }
+
+    #[test]
+    fn test_instance_limit() {
+        let limits = ModuleLimits {
+            instances: 1,
+            ..ModuleLimits::default()
+        };
+        let id = "instances-test";
+        let module = registry::compile(dex_wasm_path(), limits, WasiMode::Consensus).unwrap();
+        registry::insert(id, 1, 0, module).unwrap();
+
+        // The one instance allowed is busy until the first call is done with it, and a second
+        // caller waits for it rather than failing
+        let first = registry::active(id, HEIGHT).unwrap();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let second = std::thread::spawn(move || {
+            let instance = registry::active(id, HEIGHT);
+            sender.send(()).unwrap();
+            instance.map(|_| ())
+        });
+        assert!(receiver
+            .recv_timeout(std::time::Duration::from_millis(200))
+            .is_err());
+        drop(first);
+        receiver.recv().unwrap();
+        second.join().unwrap().unwrap();
+
+        let limits = ModuleLimits {
+            instances: 0,
+            ..ModuleLimits::default()
+        };
+        assert!(registry::compile(dex_wasm_path(), limits, WasiMode::Consensus).is_err());
+    }
+
+    #[test]
+    fn test_consensus_wasi() {
+        let clock = br#"(module
+            (import "wasi_snapshot_preview1" "clock_time_get"
+                (func $clock_time_get (param i32 i64 i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "now") (result i64)
+                (drop (call $clock_time_get (i32.const 0) (i64.const 1) (i32.const 8)))
+                (i64.load (i32.const 8))))"#;
+        let hash: [u8; 32] = Sha256::digest(clock).into();
+        let mut now = |mode| {
+            let limits = ModuleLimits::default();
+            let module = registry::compile_bytes(clock, &hash, limits, mode).unwrap();
+            let mut module = module.instantiate().unwrap();
+            let now = module
+                .instance
+                .get_typed_func::<(), i64, _>(&mut module.store, "now")
+                .unwrap();
+            now.call(&mut module.store, ()).unwrap()
+        };
+        assert_eq!(now(WasiMode::Consensus), 0);
+        assert!(now(WasiMode::Tooling) > 0);
+
+        // Filesystem access is only linked in tooling mode
+        let fs = br#"(module
+            (import "wasi_snapshot_preview1" "path_open"
+                (func (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+            (memory (export "memory") 1))"#;
+        let hash: [u8; 32] = Sha256::digest(fs).into();
+        let limits = ModuleLimits::default();
+        let err = registry::compile_bytes(fs, &hash, limits, WasiMode::Consensus)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("path_open"));
+        let module = registry::compile_bytes(fs, &hash, limits, WasiMode::Tooling).unwrap();
+        assert!(module.instantiate().is_ok());
+    }
+
+    #[test]
+    fn test_module_cache() {
+        let bytes = std::fs::read(dex_wasm_path()).unwrap();
+        let dir = std::env::temp_dir().join(format!("ainrt-cache-{}", std::process::id()));
+        let engine = registry::engine(&ModuleLimits::default()).unwrap();
+        let cache = cache::ModuleCache::new(dir.clone(), &[7; 32]).unwrap();
+
+        cache.compile(&engine, &bytes).unwrap();
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        let cached = std::fs::read(&files[0]).unwrap();
+        cache.compile(&engine, &bytes).unwrap();
+        assert_eq!(std::fs::read(&files[0]).unwrap(), cached);
+
+        // A corrupted artifact, one that carries a plain checksum and one authenticated with
+        // another key are each compiled again and replaced by an authentic one, which is then
+        // loaded as it is
+        let mut corrupted = cached.clone();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 1;
+        let mut checksummed = Sha256::digest(&cached[32..]).to_vec();
+        checksummed.extend(&cached[32..]);
+        let other_dir = dir.join("other");
+        let other = cache::ModuleCache::new(other_dir.clone(), &[8; 32]).unwrap();
+        other.compile(&engine, &bytes).unwrap();
+        let other_file = std::fs::read_dir(&other_dir).unwrap().next().unwrap();
+        let other_key = std::fs::read(other_file.unwrap().path()).unwrap();
+        for bad in [corrupted, checksummed, other_key] {
+            std::fs::write(&files[0], &bad).unwrap();
+            cache.compile(&engine, &bytes).unwrap();
+            let rewritten = std::fs::read(&files[0]).unwrap();
+            assert_ne!(rewritten, bad);
+            cache.compile(&engine, &bytes).unwrap();
+            assert_eq!(std::fs::read(&files[0]).unwrap(), rewritten);
+        }
+
+        assert!(cache::ModuleCache::new(dir.clone(), &[7; 31]).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_register_module_bytes() {
+        let mut bytes = std::fs::read(dex_wasm_path()).unwrap();
+        let hash: [u8; 32] = Sha256::digest(&bytes).into();
+        let id = "bytes-test";
+
+        register_module_bytes(
+            id,
+            1,
+            0,
+            ModuleLimits::default(),
+            WasiMode::Consensus,
+            &bytes,
+            &hash,
+        )
+        .unwrap();
+        assert_eq!(registry::active_version(id, 0), Some(1));
+
+        // A tampered module is refused before it is compiled
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        let err = register_module_bytes(
+            id,
+            2,
+            0,
+            ModuleLimits::default(),
+            WasiMode::Consensus,
+            &bytes,
+            &hash,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("hash mismatch"));
+        assert!(!registry::contains(id, 2));
+    }
+
+    #[test]
+    fn test_stable_swap_quote() {
+        let dusd = 1;
+        let usdt = 2;
+
+        let pool_pair = PoolPair {
+            pool_type: PoolType::StableSwap,
+            amplification: 100,
+            ..pool_pair(dusd, usdt, 100_000 * COIN, 100_000 * COIN)
+        };
+        register_dex();
+
+        let token_in = TokenAmount {
+            token_id: usdt,
+            amount: 1_000 * COIN,
+        };
+        let max_price = PoolPrice {
+            integer: 2,
+            fraction: 0,
+        };
+        let quote = dex_quote(HEIGHT, pool_pair, token_in, NO_FEE, true).unwrap();
+        let result = dex_swap(HEIGHT, pool_pair, token_in, max_price, None, NO_FEE, true).unwrap();
+        assert_eq!(quote, result.slop_swap_result);
+        assert!(quote > 999 * COIN && quote < 1_000 * COIN);
+    }
+
+    #[test]
+    fn test_dex_stabilization_fee() {
+        register_dex();
+
+        // 40% of the DUSD supply is algorithmic: 1.8^(0.6 - 0.5) - 1
+        let fee = dex_stabilization_fee(HEIGHT, 400, 1000, 180_000_000).unwrap();
+        assert_eq!(fee, 6_054_048);
+        let err = dex_stabilization_fee(HEIGHT, 400, 0, 180_000_000).unwrap_err();
+        assert_eq!(Status::from(&err), Status::InvalidInput);
+    }
+
+    #[test]
+    fn test_status_codes() {
+        register_dex();
+
+        let pool_pair = create_pool_pair(pool_pair_config());
+
+        // Failures reported by the module keep their own status
+        let token_in = TokenAmount {
+            token_id: 1,
+            amount: 0,
+        };
+        let err = dex_quote(HEIGHT, pool_pair, token_in, NO_FEE, true).unwrap_err();
+        assert_eq!(Status::from(&err), Status::InvalidInput);
+
+        // An operation on a pool the batch does not hold names its index
+        let operation = SwapOperation {
+            pool_index: 1,
+            token_in: TokenAmount {
+                token_id: 1,
+                amount: COIN,
+            },
+            max_price: PoolPrice {
+                integer: COIN,
+                fraction: 0,
+            },
+            dex_fee: NO_FEE,
+        };
+        let batch = dex_swap_batch(HEIGHT, &[pool_pair], &[operation], true).unwrap();
+        let err = batch.results[0].as_ref().unwrap_err();
+        assert_eq!(Status::from(err), Status::PoolNotFound);
+        assert_eq!(err.to_string(), "No pool pair at index 1");
+
+        // Swaps through a disabled pool are refused before anything else about the pool
+        let disabled = dex_update_pool_pair(HEIGHT, pool_pair, Some(false), None).unwrap();
+        let err = dex_swap(
+            HEIGHT,
+            disabled,
+            operation.token_in,
+            operation.max_price,
+            None,
+            NO_FEE,
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(Status::from(&err), Status::PoolDisabled);
+        assert_eq!(err.to_string(), "Pool trading is turned off!");
+
+        let err = registry::active("missing", HEIGHT).err().unwrap();
+        assert_eq!(Status::from(&err), Status::NotRegistered);
+
+        // The reason is kept for the calling thread only
+        let message = err.to_string();
+        assert_eq!(status(Err(err)), Status::NotRegistered);
+        assert_eq!(last_error(), message);
+        std::thread::spawn(|| assert_eq!(last_error(), ""))
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_bridge_types() {
+        let pool_pair = PoolPair {
+            total_liquidity: 700 * COIN,
+            block_commission_a: 1,
+            block_commission_b: 2,
+            pool_type: PoolType::Weighted,
+            weight_a: 80,
+            weight_b: 20,
+            price_ab_cumulative: CumulativePrice { high: 1, low: 2 },
+            price_ba_cumulative: CumulativePrice { high: 3, low: 4 },
+            price_height: HEIGHT,
+            ..pool_pair(1, 2, 1_000 * COIN, 500 * COIN)
+        };
+        let shared = runtime::PoolPair::from(pool_pair);
+        assert_eq!(shared.pool_type, runtime::PoolType::Weighted);
+        assert_eq!(shared.price_ba_cumulative.low, 4);
+        let back = PoolPair::try_from(shared).unwrap();
+        assert_eq!(runtime::PoolPair::from(back), shared);
+
+        // C++ can pass any discriminant
+        let unknown = runtime::PoolPair {
+            pool_type: runtime::PoolType { repr: 3 },
+            ..shared
+        };
+        let err = anyhow::Error::from(PoolPair::try_from(unknown).unwrap_err());
+        assert_eq!(Status::from(&err), Status::InvalidArgument);
+    }
 }
@@ -0,0 +1,77 @@
+use crate::error::LimitExceeded;
+use wit_bindgen_wasmtime::wasmtime::ResourceLimiter;
+
+/// Caps on what a module instance may allocate, fixed when the module is registered
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModuleLimits {
+    /// Largest size any linear memory may grow to, in bytes
+    pub memory_size: usize,
+    /// Most elements any table may hold
+    pub table_elements: u32,
+    /// Most instances of a module version that may exist at once, which is one per call in
+    /// progress and the number of slots in its instance pool. Calls that would need another one
+    /// wait until a call in progress is done.
+    pub instances: usize,
+}
+
+impl Default for ModuleLimits {
+    fn default() -> Self {
+        ModuleLimits {
+            memory_size: 64 << 20,
+            table_elements: 10_000,
+            instances: 16,
+        }
+    }
+}
+
+/// Enforces `ModuleLimits` on a store. It remembers the first growth it refused, so the failing
+/// call can report that instead of whatever trap the module raised when it could not allocate.
+pub struct ModuleLimiter {
+    limits: ModuleLimits,
+    exceeded: Option<LimitExceeded>,
+}
+
+impl ModuleLimiter {
+    pub fn new(limits: ModuleLimits) -> Self {
+        ModuleLimiter {
+            limits,
+            exceeded: None,
+        }
+    }
+
+    /// The first growth refused since the last time this was called
+    pub fn take_exceeded(&mut self) -> Option<LimitExceeded> {
+        self.exceeded.take()
+    }
+}
+
+impl ResourceLimiter for ModuleLimiter {
+    fn memory_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> bool {
+        if desired > self.limits.memory_size {
+            self.exceeded.get_or_insert(LimitExceeded::MemorySize {
+                limit: self.limits.memory_size,
+                desired,
+            });
+            return false;
+        }
+        true
+    }
+
+    fn table_growing(&mut self, _current: u32, desired: u32, _maximum: Option<u32>) -> bool {
+        if desired > self.limits.table_elements {
+            self.exceeded.get_or_insert(LimitExceeded::TableElements {
+                limit: self.limits.table_elements,
+                desired,
+            });
+            return false;
+        }
+        true
+    }
+
+    fn instances(&self) -> usize {
+        // Every store holds the one instance of its module; `limits.instances` is enforced
+        // across stores by the registry
+        1
+    }
+}
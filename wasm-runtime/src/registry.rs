@@ -0,0 +1,445 @@
+use crate::cache;
+use crate::error::{InvalidArgument, NotRegistered, OutOfFuel};
+use crate::limits::{ModuleLimiter, ModuleLimits};
+use crate::wasi::{self, WasiMode};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use sha2::{Digest, Sha256};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use wasmtime_wasi::WasiCtx;
+use wit_bindgen_wasmtime::anyhow::Result;
+use wit_bindgen_wasmtime::wasmtime::{
+    Config, Engine, Instance, InstanceAllocationStrategy, InstanceLimits, InstancePre, Linker,
+    Module, PoolingAllocationStrategy, Store,
+};
+
+/// Longest module id accepted, in bytes, so ids fit in `ModuleInfo`
+pub const MODULE_ID_MAX_LEN: usize = 63;
+
+/// Pages of the largest memory a 32-bit wasm module can address, which every slot of an engine's
+/// instance pool is sized for
+const MAX_MEMORY_PAGES: u64 = 0x1_0000;
+
+/// Fuel every module call starts with unless `set_fuel_limit` changed it. One unit is roughly
+/// one wasm instruction, and the heaviest dex calls use a small fraction of it.
+pub const DEFAULT_FUEL_LIMIT: u64 = 1_000_000_000;
+
+static FUEL_LIMIT: AtomicU64 = AtomicU64::new(DEFAULT_FUEL_LIMIT);
+
+thread_local! {
+    static LAST_FUEL_CONSUMED: Cell<u64> = Cell::new(0);
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModuleKey {
+    pub id: String,
+    pub version: u32,
+}
+
+/// What a module's store holds besides the module itself
+pub struct ModuleState {
+    wasi: WasiCtx,
+    limiter: ModuleLimiter,
+}
+
+impl ModuleState {
+    fn new(limits: ModuleLimits, mode: WasiMode) -> Result<Self> {
+        Ok(ModuleState {
+            wasi: wasi::build_ctx(mode)?,
+            limiter: ModuleLimiter::new(limits),
+        })
+    }
+}
+
+/// An instantiated module together with the store it lives in
+pub struct LoadedModule {
+    pub store: Store<ModuleState>,
+    pub instance: Instance,
+}
+
+impl LoadedModule {
+    /// Runs `f` with `fuel_limit()` fuel, see `metered_with_limit`
+    pub fn metered<T>(
+        &mut self,
+        f: impl FnOnce(&mut Store<ModuleState>, &Instance) -> Result<T>,
+    ) -> Result<T> {
+        self.metered_with_limit(fuel_limit(), f)
+    }
+
+    /// Runs `f` with exactly `limit` fuel in the store, whatever instantiation or a previous call
+    /// left. The fuel it consumes is recorded for `last_fuel_consumed`, and a call that fails
+    /// after using up its budget fails with `OutOfFuel`. Fuel is counted per instruction, and
+    /// `active` gives every call an instance that nothing ran on but its start function, so a
+    /// call costs the same on every node. A call that fails after being refused memory or table
+    /// growth fails with `LimitExceeded`.
+    pub fn metered_with_limit<T>(
+        &mut self,
+        limit: u64,
+        f: impl FnOnce(&mut Store<ModuleState>, &Instance) -> Result<T>,
+    ) -> Result<T> {
+        let remaining = self.store.consume_fuel(0).unwrap_or(0);
+        if remaining < limit {
+            self.store.add_fuel(limit - remaining)?;
+        } else if remaining > limit {
+            self.store.consume_fuel(remaining - limit)?;
+        }
+
+        let consumed_before = self.store.fuel_consumed().unwrap_or(0);
+        let result = f(&mut self.store, &self.instance);
+        let consumed = self.store.fuel_consumed().unwrap_or(0) - consumed_before;
+        let out_of_fuel = result.is_err() && consumed >= limit;
+        LAST_FUEL_CONSUMED.with(|last| last.set(consumed));
+        let exceeded = self.store.data_mut().limiter.take_exceeded();
+        if out_of_fuel {
+            return Err(OutOfFuel { limit }.into());
+        }
+        match (result, exceeded) {
+            (Err(_), Some(exceeded)) => Err(exceeded.into()),
+            (result, _) => result,
+        }
+    }
+}
+
+/// A compiled module with what it is instantiated with, so instances can be created on demand
+#[derive(Clone)]
+pub struct CompiledModule {
+    engine: Engine,
+    /// The module with its imports resolved, which only leaves the store to create per instance
+    pre: InstancePre<ModuleState>,
+    limits: ModuleLimits,
+    mode: WasiMode,
+}
+
+impl CompiledModule {
+    /// Resolves the imports of `module` against WASI in `mode` once, for every later instance
+    fn new(engine: Engine, module: Module, limits: ModuleLimits, mode: WasiMode) -> Result<Self> {
+        wasi::check_imports(&module, mode)?;
+        let mut linker = Linker::new(&engine);
+        wasi::add_to_linker(&mut linker, mode, |s: &mut ModuleState| &mut s.wasi)?;
+        // WASI is made of host functions, which belong to no store, so the result can be
+        // instantiated in any store of the engine
+        let mut store = Store::new(&engine, ModuleState::new(limits, mode)?);
+        let pre = linker.instantiate_pre(&mut store, &module)?;
+        Ok(CompiledModule {
+            engine,
+            pre,
+            limits,
+            mode,
+        })
+    }
+
+    /// Instantiates the module in a new store of its own, with WASI in its mode, in a free slot
+    /// of the engine's instance pool. Its memories start from the module's initial data, which
+    /// wasmtime maps copy-on-write where the platform allows instead of copying it. The instance
+    /// may never grow past its limits.
+    pub fn instantiate(&self) -> Result<LoadedModule> {
+        let mut store = Store::new(&self.engine, ModuleState::new(self.limits, self.mode)?);
+        store.limiter(|s| &mut s.limiter);
+        // Start functions run during instantiation and are metered like any call
+        store.add_fuel(fuel_limit())?;
+        let instance = self.pre.instantiate(&mut store).map_err(|e| {
+            match store.data_mut().limiter.take_exceeded() {
+                // Initial memories and tables too large for the limits are refused here
+                Some(exceeded) => exceeded.into(),
+                None => e,
+            }
+        })?;
+        Ok(LoadedModule { store, instance })
+    }
+}
+
+/// A registered module version. Every call gets a new instance of its own in one of the
+/// `ModuleLimits::instances` slots of the version's instance pool, so none sees what an earlier
+/// one left in memory, globals or tables. Callers past that many wait for a slot to be free.
+pub struct RegisteredModule {
+    /// First block height calls are dispatched to this version at
+    activation_height: u32,
+    compiled: CompiledModule,
+    /// Instances in use by a call
+    live: Mutex<usize>,
+    /// Notified whenever an instance is released
+    released: Condvar,
+}
+
+impl RegisteredModule {
+    /// Claims room for one more instance, waiting for one to be released while the limit is
+    /// reached. Block validation must not fail because other callers, such as RPC, are busy.
+    fn reserve_instance(&self) {
+        let limit = self.compiled.limits.instances;
+        let mut live = self.live.lock().unwrap();
+        while *live >= limit {
+            live = self.released.wait(live).unwrap();
+        }
+        *live += 1;
+    }
+
+    fn release_instance(&self) {
+        *self.live.lock().unwrap() -= 1;
+        self.released.notify_one();
+    }
+}
+
+lazy_static! {
+    /// Registered versions by module id. Changes to the versions of an id are made under its
+    /// entry lock, so they are checked against each other and never against a stale view.
+    static ref MODULES: DashMap<String, BTreeMap<u32, Arc<RegisteredModule>>> = DashMap::new();
+}
+
+/// A new instance of a registered module version, for one caller. It is dropped with the
+/// store it lives in once the caller is done, which returns its slot to the pool.
+pub struct ModuleInstance {
+    key: ModuleKey,
+    module: LoadedModule,
+    /// Declared after `module`, so the store gives its slot back to the engine's pool before
+    /// the next waiting caller is let in
+    _slot: InstanceSlot,
+}
+
+impl ModuleInstance {
+    pub fn key(&self) -> &ModuleKey {
+        &self.key
+    }
+}
+
+impl Deref for ModuleInstance {
+    type Target = LoadedModule;
+
+    fn deref(&self) -> &LoadedModule {
+        &self.module
+    }
+}
+
+impl DerefMut for ModuleInstance {
+    fn deref_mut(&mut self) -> &mut LoadedModule {
+        &mut self.module
+    }
+}
+
+/// A slot reserved under a module version's instance limit, released when dropped
+struct InstanceSlot(Arc<RegisteredModule>);
+
+impl Drop for InstanceSlot {
+    fn drop(&mut self) {
+        self.0.release_instance();
+    }
+}
+
+/// Compiles the module at `path` without registering it. Its instances run with WASI in
+/// `mode`, and it fails to compile if it imports anything `mode` does not allow.
+pub fn compile<P: AsRef<Path>>(
+    path: P,
+    limits: ModuleLimits,
+    mode: WasiMode,
+) -> Result<CompiledModule> {
+    let engine = engine(&limits)?;
+    let module = cache::compile(&engine, &std::fs::read(path)?)?;
+    CompiledModule::new(engine, module, limits, mode)
+}
+
+/// Compiles the module in `bytes` like `compile`. The SHA-256 of `bytes` must be
+/// `expected_sha256`, which is checked before anything is compiled.
+pub fn compile_bytes(
+    bytes: &[u8],
+    expected_sha256: &[u8; 32],
+    limits: ModuleLimits,
+    mode: WasiMode,
+) -> Result<CompiledModule> {
+    let hash: [u8; 32] = Sha256::digest(bytes).into();
+    if hash != *expected_sha256 {
+        return Err(InvalidArgument(format!(
+            "module hash mismatch: expected {}, got {}",
+            to_hex(expected_sha256),
+            to_hex(&hash)
+        ))
+        .into());
+    }
+    let engine = engine(&limits)?;
+    let module = cache::compile(&engine, bytes)?;
+    CompiledModule::new(engine, module, limits, mode)
+}
+
+/// An engine that meters fuel, so module calls can be bounded, and allocates instances from a
+/// pool of `limits.instances` slots. Wasmtime resets a slot's memories and tables when the store
+/// using it is dropped and keeps the module's copy-on-write image mapped for the next instance of
+/// it, which is what makes an instance per call cheap. Slots are sized for any memory and table
+/// a module may start with, so `ModuleLimiter` alone enforces `limits` on them.
+pub(crate) fn engine(limits: &ModuleLimits) -> Result<Engine> {
+    if limits.instances == 0 {
+        return Err(InvalidArgument("module instance limit must be at least 1".into()).into());
+    }
+    let instance_limits = InstanceLimits {
+        count: u32::try_from(limits.instances)?,
+        memory_pages: MAX_MEMORY_PAGES,
+        table_elements: limits
+            .table_elements
+            .max(InstanceLimits::default().table_elements),
+        ..InstanceLimits::default()
+    };
+    let mut config = Config::new();
+    config
+        .consume_fuel(true)
+        .allocation_strategy(InstanceAllocationStrategy::Pooling {
+            strategy: PoolingAllocationStrategy::ReuseAffinity,
+            instance_limits,
+        });
+    Engine::new(&config)
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Registers `module` under `id` and `version`, which must not be registered yet. Calls are
+/// dispatched to it from `activation_height` on, until a later version activates, so activation
+/// heights may not go down as versions go up. The module is instantiated once here, so a module
+/// that cannot be instantiated fails to register.
+pub fn insert(
+    id: &str,
+    version: u32,
+    activation_height: u32,
+    module: CompiledModule,
+) -> Result<()> {
+    if id.is_empty() || id.len() > MODULE_ID_MAX_LEN {
+        let message = format!("module id must be 1 to {} bytes long", MODULE_ID_MAX_LEN);
+        return Err(InvalidArgument(message).into());
+    }
+    module.instantiate()?;
+    let registered = Arc::new(RegisteredModule {
+        activation_height,
+        compiled: module,
+        live: Mutex::new(0),
+        released: Condvar::new(),
+    });
+
+    // Checked and inserted under one lock, so concurrent registrations of the same id cannot
+    // both pass the checks against a view without the other
+    let mut versions = MODULES.entry(id.to_string()).or_default();
+    if versions.contains_key(&version) {
+        return Err(InvalidArgument(format!(
+            "module {} version {} is already registered",
+            id, version
+        ))
+        .into());
+    }
+    let out_of_order = versions.iter().any(|(&other, registered)| {
+        (other < version && registered.activation_height > activation_height)
+            || (other > version && registered.activation_height < activation_height)
+    });
+    if out_of_order {
+        return Err(InvalidArgument(format!(
+            "module {} version {} activates out of order with its other versions",
+            id, version
+        ))
+        .into());
+    }
+    versions.insert(version, registered);
+    Ok(())
+}
+
+pub fn remove(id: &str, version: u32) -> Result<()> {
+    let removed = MODULES
+        .get_mut(id)
+        .and_then(|mut versions| versions.remove(&version));
+    MODULES.remove_if(id, |_, versions| versions.is_empty());
+    removed.map(|_| ()).ok_or_else(|| {
+        let message = format!("module {} version {} not found", id, version);
+        NotRegistered(message).into()
+    })
+}
+
+/// Every registered module and its activation height, ordered by id and then version
+pub fn list() -> Vec<(ModuleKey, u32)> {
+    let mut modules = MODULES
+        .iter()
+        .flat_map(|entry| {
+            let id = entry.key().clone();
+            entry
+                .value()
+                .iter()
+                .map(|(&version, registered)| {
+                    let key = ModuleKey {
+                        id: id.clone(),
+                        version,
+                    };
+                    (key, registered.activation_height)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    modules.sort_by(|a, b| a.0.cmp(&b.0));
+    modules
+}
+
+pub fn contains(id: &str, version: u32) -> bool {
+    MODULES
+        .get(id)
+        .is_some_and(|versions| versions.contains_key(&version))
+}
+
+pub fn latest_version(id: &str) -> Option<u32> {
+    MODULES
+        .get(id)
+        .and_then(|versions| versions.keys().next_back().copied())
+}
+
+/// The version of module `id` that calls at block `height` run, which is the highest version
+/// activated at or below it. It only depends on `height`, so blocks replayed after a reorg run
+/// the same code as the first time.
+pub fn active_version(id: &str, height: u32) -> Option<u32> {
+    active_module(id, height).map(|(version, _)| version)
+}
+
+fn active_module(id: &str, height: u32) -> Option<(u32, Arc<RegisteredModule>)> {
+    MODULES
+        .get(id)?
+        .iter()
+        .rev()
+        .find(|(_, registered)| registered.activation_height <= height)
+        .map(|(&version, registered)| (version, Arc::clone(registered)))
+}
+
+/// A new instance of the version of module `id` active at block `height`, for the caller alone.
+/// No lock is held while it is in use. While every instance the version allows is in use by
+/// other callers, it waits for one of them to be done.
+pub fn active(id: &str, height: u32) -> Result<ModuleInstance> {
+    let (version, registered) = active_module(id, height).ok_or_else(|| {
+        NotRegistered(format!(
+            "module {} has no version active at height {}",
+            id, height
+        ))
+    })?;
+    let key = ModuleKey {
+        id: id.to_string(),
+        version,
+    };
+
+    registered.reserve_instance();
+    let slot = InstanceSlot(registered);
+    let module = slot.0.compiled.instantiate()?;
+    Ok(ModuleInstance {
+        key,
+        module,
+        _slot: slot,
+    })
+}
+
+/// Fuel each module call starts with
+pub fn fuel_limit() -> u64 {
+    FUEL_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Changes the fuel each module call starts with. Every node must use the same limit for calls
+/// made while validating blocks, or they could disagree on which calls run out of fuel.
+pub fn set_fuel_limit(limit: u64) {
+    FUEL_LIMIT.store(limit, Ordering::Relaxed);
+}
+
+/// Fuel consumed by the last module call made on this thread
+pub fn last_fuel_consumed() -> u64 {
+    LAST_FUEL_CONSUMED.with(Cell::get)
+}
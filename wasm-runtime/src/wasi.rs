@@ -0,0 +1,133 @@
+use crate::error::InvalidArgument;
+use wasmtime_wasi::{WasiCtx, WasiCtxBuilder};
+use wit_bindgen_wasmtime::anyhow::Result;
+use wit_bindgen_wasmtime::wasmtime::{Caller, Extern, Linker, Module, Trap};
+
+const WASI_MODULE: &str = "wasi_snapshot_preview1";
+
+/// WASI functions a consensus module may import. None of them can reach the filesystem, the
+/// network or the host's stdio, and the clock and random ones are replaced by `add_stubs`.
+const CONSENSUS_IMPORTS: &[&str] = &[
+    "args_get",
+    "args_sizes_get",
+    "environ_get",
+    "environ_sizes_get",
+    "fd_close",
+    "fd_fdstat_get",
+    "fd_prestat_get",
+    "fd_prestat_dir_name",
+    "fd_read",
+    "fd_seek",
+    "fd_write",
+    "proc_exit",
+    "sched_yield",
+    "clock_res_get",
+    "clock_time_get",
+    "random_get",
+];
+
+/// The WASI environment a module runs in
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WasiMode {
+    /// For modules run while validating blocks. There are no args, environment, stdio or
+    /// preopened directories, clocks always read zero and random bytes are all zero, so every
+    /// node sees the same thing. Modules importing any other WASI function fail to register.
+    Consensus,
+    /// For tooling only: the module inherits the node's stdio and args and may import any WASI
+    /// function, including the real clocks and randomness. Never use it for consensus code.
+    Tooling,
+}
+
+/// Fails if `module` imports anything `mode` does not allow
+pub fn check_imports(module: &Module, mode: WasiMode) -> Result<()> {
+    if mode == WasiMode::Tooling {
+        return Ok(());
+    }
+    for import in module.imports() {
+        if import.module() != WASI_MODULE || !CONSENSUS_IMPORTS.contains(&import.name()) {
+            return Err(InvalidArgument(format!(
+                "module imports {}::{}, which is not allowed in consensus modules",
+                import.module(),
+                import.name()
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+pub fn build_ctx(mode: WasiMode) -> Result<WasiCtx> {
+    Ok(match mode {
+        WasiMode::Consensus => WasiCtxBuilder::new().build(),
+        WasiMode::Tooling => WasiCtxBuilder::new()
+            .inherit_stdio()
+            .inherit_args()?
+            .build(),
+    })
+}
+
+/// Adds WASI to `linker`, with the clock and random functions replaced by deterministic stubs in
+/// consensus mode
+pub fn add_to_linker<T: Send + 'static>(
+    linker: &mut Linker<T>,
+    mode: WasiMode,
+    get_ctx: impl Fn(&mut T) -> &mut WasiCtx + Send + Sync + Copy + 'static,
+) -> Result<()> {
+    wasmtime_wasi::add_to_linker(linker, get_ctx)?;
+    if mode == WasiMode::Consensus {
+        add_stubs(linker)?;
+    }
+    Ok(())
+}
+
+fn add_stubs<T: Send + 'static>(linker: &mut Linker<T>) -> Result<()> {
+    linker.allow_shadowing(true);
+    linker.func_wrap(
+        WASI_MODULE,
+        "clock_res_get",
+        |mut caller: Caller<'_, T>, _id: i32, resolution: i32| {
+            write_guest(&mut caller, resolution, &1u64.to_le_bytes())
+        },
+    )?;
+    linker.func_wrap(
+        WASI_MODULE,
+        "clock_time_get",
+        |mut caller: Caller<'_, T>, _id: i32, _precision: i64, time: i32| {
+            write_guest(&mut caller, time, &0u64.to_le_bytes())
+        },
+    )?;
+    linker.func_wrap(
+        WASI_MODULE,
+        "random_get",
+        |mut caller: Caller<'_, T>, buf: i32, buf_len: i32| {
+            guest_slice(&mut caller, buf, buf_len as u32 as usize)?.fill(0);
+            Ok(0)
+        },
+    )?;
+    linker.allow_shadowing(false);
+    Ok(())
+}
+
+/// Copies `bytes` into the guest memory at `ptr`, returning the WASI errno for success
+fn write_guest<T>(caller: &mut Caller<'_, T>, ptr: i32, bytes: &[u8]) -> Result<i32, Trap> {
+    guest_slice(caller, ptr, bytes.len())?.copy_from_slice(bytes);
+    Ok(0)
+}
+
+/// The `len` bytes of guest memory at `ptr`, or a trap if any of them is out of bounds
+fn guest_slice<'a, T>(
+    caller: &'a mut Caller<'_, T>,
+    ptr: i32,
+    len: usize,
+) -> Result<&'a mut [u8], Trap> {
+    let memory = match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => memory,
+        _ => return Err(Trap::new("module does not export its memory")),
+    };
+    let start = ptr as u32 as usize;
+    start
+        .checked_add(len)
+        .and_then(|end| memory.data_mut(caller).get_mut(start..end))
+        .ok_or_else(|| Trap::new("out of bounds memory access"))
+}